@@ -0,0 +1,64 @@
+//! Reads the requests ACME clients make of external DNS-01 hook programs.
+//!
+//! * certbot runs the hook without arguments for both `--manual-auth-hook`
+//!   and `--manual-cleanup-hook`, passing `CERTBOT_DOMAIN` and
+//!   `CERTBOT_VALIDATION`, and sets `CERTBOT_AUTH_OUTPUT` only when cleaning up.
+//! * lego's exec provider passes `present|cleanup FQDN VALUE`, or with
+//!   `EXEC_MODE=RAW`, `present|cleanup -- DOMAIN TOKEN KEY_AUTH`.
+//! * acme.sh and other scripts pass `present|cleanup FQDN VALUE`.
+
+use super::{CHALLENGE_LABEL, txt_value};
+use crate::{Error, Result};
+
+/// What a DNS-01 hook was asked to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookAction {
+  /// Publish `value` in the challenge record of `identifier`.
+  Present { identifier: String, value: String },
+  /// Remove `value` from the challenge record of `identifier`.
+  Cleanup { identifier: String, value: String },
+}
+
+impl HookAction {
+  /// Determines the action from a hook's arguments and certbot's environment.
+  ///
+  /// # Arguments
+  /// * `args` - The mode (`auth`, `present` or `cleanup`) followed by its arguments.
+  ///   Without any, certbot is assumed.
+  /// * `env` - Returns the value of an environment variable, or `None` if it is not set.
+  pub fn parse<S: AsRef<str>>(args: &[S], env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+    let mode = args.first().map(AsRef::as_ref);
+    let rest: Vec<&str> = args.iter().skip(1).map(AsRef::as_ref).collect();
+    let (identifier, value) = match rest.as_slice() {
+      // lego RAW mode: the domain and key authorization, from which the digest is computed.
+      ["--", domain, _token, key_authorization] => (domain.to_string(), txt_value(key_authorization)),
+      // lego default mode, acme.sh and scripts: the challenge FQDN and the TXT value.
+      [fqdn, value] => {
+        let fqdn = fqdn.trim_end_matches('.');
+        let identifier = fqdn.strip_prefix(CHALLENGE_LABEL).and_then(|rest| rest.strip_prefix('.')).unwrap_or(fqdn);
+        (identifier.to_string(), value.to_string())
+      }
+      [] => {
+        let domain = env("CERTBOT_DOMAIN").ok_or_else(|| invalid("CERTBOT_DOMAIN is not set"))?;
+        let value = env("CERTBOT_VALIDATION").ok_or_else(|| invalid("CERTBOT_VALIDATION is not set"))?;
+        (domain, value)
+      }
+      _ => return Err(invalid("Expected FQDN VALUE, -- DOMAIN TOKEN KEY_AUTH, or no arguments after the mode")),
+    };
+    let cleanup = match mode {
+      Some("present") | Some("auth") => false,
+      Some("cleanup") => true,
+      None => env("CERTBOT_AUTH_OUTPUT").is_some(),
+      Some(other) => return Err(invalid(&format!("Unknown mode '{}'", other))),
+    };
+    Ok(if cleanup {
+      HookAction::Cleanup { identifier, value }
+    } else {
+      HookAction::Present { identifier, value }
+    })
+  }
+}
+
+fn invalid(message: &str) -> Error {
+  Error::InvalidInput(message.to_string())
+}
@@ -0,0 +1,302 @@
+//! # ACME DNS-01 Challenges
+//!
+//! Solves ACME DNS-01 challenges (RFC 8555, section 8.4) using a registrar's
+//! DNS records.
+//!
+//! [`Dns01Solver`] publishes the `_acme-challenge` TXT record for an
+//! identifier in the zone that contains it, waits until every authoritative
+//! nameserver for that zone serves the record, and removes it again once the
+//! certificate authority has validated it. The solver is independent of any
+//! particular ACME client library: it only needs the identifier and the key
+//! authorization the library provides.
+//!
+//! To use this module, you must enable the `acme` feature in your `Cargo.toml`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use registrar::acme::Dns01Solver;
+//! use registrar::porkbun::Porkbun;
+//! use std::sync::Arc;
+//!
+//! # async fn run(client: Porkbun, key_authorization: &str) -> registrar::Result<()> {
+//! let solver = Dns01Solver::new(Arc::new(client))?;
+//! let result = solver
+//!   .solve("www.example.com", key_authorization, || async {
+//!     // Tell the ACME server the challenge is ready and poll the order here.
+//!     Ok::<_, registrar::Error>(())
+//!   })
+//!   .await;
+//! result
+//! # }
+//! ```
+
+use crate::lookup::DnsLookup;
+use crate::records::{DnsRecords, Record};
+use crate::{Error, Result};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub mod hook;
+
+pub use hook::HookAction;
+
+/// The label prepended to an identifier to form the challenge record name.
+const CHALLENGE_LABEL: &str = "_acme-challenge";
+
+/// The default time to wait for the record to reach every authoritative server.
+const DEFAULT_PROPAGATION_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// The default delay between propagation checks.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Computes the TXT record value for a key authorization: the unpadded
+/// base64url encoding of its SHA-256 digest.
+pub fn txt_value(key_authorization: &str) -> String {
+  URL_SAFE_NO_PAD.encode(Sha256::digest(key_authorization.as_bytes()))
+}
+
+/// A challenge record that has been published and must be cleaned up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dns01Challenge {
+  /// The registered domain (zone) the record was created in.
+  pub zone: String,
+  /// The fully qualified name of the TXT record (e.g., "_acme-challenge.www.example.com").
+  pub fqdn: String,
+  /// The TXT record value.
+  pub value: String,
+  /// The registrar's ID of the created record.
+  pub record_id: String,
+}
+
+/// Publishes and removes DNS-01 challenge records through a registrar.
+pub struct Dns01Solver {
+  records: Arc<dyn DnsRecords>,
+  resolver: DnsLookup,
+  authoritative: Option<DnsLookup>,
+  zones: Vec<String>,
+  ttl: Option<u32>,
+  propagation_timeout: Duration,
+  poll_interval: Duration,
+}
+
+impl Dns01Solver {
+  /// Creates a solver that finds zones and nameservers with the system resolver.
+  ///
+  /// # Arguments
+  /// * `records` - The registrar hosting the DNS for the identifiers being validated.
+  pub fn new(records: Arc<dyn DnsRecords>) -> Result<Self> {
+    Ok(Self::with_resolver(records, DnsLookup::system()?))
+  }
+
+  /// Creates a solver that uses the given recursive resolver to find zones and nameservers.
+  pub fn with_resolver(records: Arc<dyn DnsRecords>, resolver: DnsLookup) -> Self {
+    Self {
+      records,
+      resolver,
+      authoritative: None,
+      zones: Vec::new(),
+      ttl: None,
+      propagation_timeout: DEFAULT_PROPAGATION_TIMEOUT,
+      poll_interval: DEFAULT_POLL_INTERVAL,
+    }
+  }
+
+  /// Declares a zone hosted at the registrar, skipping zone discovery for names within it.
+  ///
+  /// The longest declared zone containing an identifier is used.
+  pub fn with_zone(mut self, zone: &str) -> Self {
+    self.zones.push(normalize(zone));
+    self
+  }
+
+  /// Checks propagation against the given servers instead of the zone's published nameservers,
+  /// for example a hidden primary or servers not reachable on port 53.
+  pub fn with_authoritative(mut self, authoritative: DnsLookup) -> Self {
+    self.authoritative = Some(authoritative);
+    self
+  }
+
+  /// Sets the TTL of challenge records. Defaults to the registrar's default.
+  pub fn with_ttl(mut self, ttl: u32) -> Self {
+    self.ttl = Some(ttl);
+    self
+  }
+
+  /// Sets how long to wait for the record to be served by every authoritative server,
+  /// and how often to check. Defaults to five minutes, checking every five seconds.
+  pub fn with_propagation(mut self, timeout: Duration, poll_interval: Duration) -> Self {
+    self.propagation_timeout = timeout;
+    self.poll_interval = poll_interval;
+    self
+  }
+
+  /// Publishes the challenge record and waits until it is served.
+  ///
+  /// If the record does not propagate in time it is removed before the error is returned.
+  ///
+  /// # Arguments
+  /// * `identifier` - The domain name being validated. A leading `*.` is ignored.
+  /// * `key_authorization` - The key authorization from the ACME challenge.
+  pub async fn present(&self, identifier: &str, key_authorization: &str) -> Result<Dns01Challenge> {
+    self.present_value(identifier, &txt_value(key_authorization)).await
+  }
+
+  /// Publishes a precomputed TXT value and waits until it is served.
+  ///
+  /// Use this when the ACME client supplies the digest itself, as certbot's
+  /// `CERTBOT_VALIDATION` and lego's exec provider do.
+  pub async fn present_value(&self, identifier: &str, value: &str) -> Result<Dns01Challenge> {
+    let (zone, host) = self.locate(identifier).await?;
+    let mut record = Record::new(host.as_str(), "TXT", value);
+    record.ttl = self.ttl;
+    let record_id = self.records.create_record(&zone, &record).await?;
+    let challenge = Dns01Challenge {
+      fqdn: record.fqdn(&zone),
+      zone,
+      value: value.to_string(),
+      record_id,
+    };
+
+    if let Err(e) = self.wait_for_propagation(&challenge).await {
+      // The wait failed, so remove the record now; the propagation error is the one worth reporting.
+      let _ = self.cleanup(&challenge).await;
+      return Err(e);
+    }
+    Ok(challenge)
+  }
+
+  /// Removes a challenge record created by [`Dns01Solver::present`].
+  pub async fn cleanup(&self, challenge: &Dns01Challenge) -> Result<()> {
+    self.records.delete_record(&challenge.zone, &challenge.record_id).await
+  }
+
+  /// Removes the challenge records for `identifier` carrying `value`, for
+  /// callers such as hook scripts that did not keep the [`Dns01Challenge`].
+  ///
+  /// Succeeds without changes if no such record exists.
+  pub async fn cleanup_value(&self, identifier: &str, value: &str) -> Result<()> {
+    let (zone, host) = self.locate(identifier).await?;
+    for record in self.records.list_records(&zone).await? {
+      let content = record.content.trim_matches('"');
+      if record.record_type == "TXT" && record.host.eq_ignore_ascii_case(&host) && content == value {
+        self.records.delete_record(&zone, record.require_id()?).await?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Presents the challenge, runs `validate` once it is served, and removes the
+  /// record afterwards whether or not validation succeeded.
+  ///
+  /// `validate` typically tells the ACME server the challenge is ready and
+  /// polls until the authorization is valid. A cleanup failure is only
+  /// reported if `validate` succeeded.
+  pub async fn solve<F, Fut, T, E>(
+    &self,
+    identifier: &str,
+    key_authorization: &str,
+    validate: F,
+  ) -> std::result::Result<T, E>
+  where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = std::result::Result<T, E>>,
+    E: From<Error>,
+  {
+    let challenge = self.present(identifier, key_authorization).await?;
+    let result = validate().await;
+    let cleanup = self.cleanup(&challenge).await;
+    let value = result?;
+    cleanup?;
+    Ok(value)
+  }
+
+  /// Returns the zone hosting the challenge record for `identifier` and the record's host within it.
+  async fn locate(&self, identifier: &str) -> Result<(String, String)> {
+    let identifier = normalize(identifier.strip_prefix("*.").unwrap_or(identifier));
+    let zone = self.find_zone(&identifier).await?;
+    let fqdn = format!("{}.{}", CHALLENGE_LABEL, identifier);
+    let host = fqdn[..fqdn.len() - zone.len() - 1].to_string();
+    Ok((zone, host))
+  }
+
+  /// Finds the registered zone containing `name`: the longest declared zone, or
+  /// otherwise the closest enclosing name that has NS records.
+  async fn find_zone(&self, name: &str) -> Result<String> {
+    if let Some(zone) = self
+      .zones
+      .iter()
+      .filter(|zone| is_within(name, zone))
+      .max_by_key(|zone| zone.len())
+    {
+      return Ok(zone.clone());
+    }
+    let labels: Vec<&str> = name.split('.').collect();
+    // Stop before the top-level domain, which a registrar cannot host.
+    for start in 0..labels.len().saturating_sub(1) {
+      let candidate = labels[start..].join(".");
+      if !self.resolver.ns(&candidate).await?.is_empty() {
+        return Ok(candidate);
+      }
+    }
+    Err(Error::Dns(format!("Could not find the zone containing '{}'", name)))
+  }
+
+  /// Polls every authoritative server for the zone until all of them serve the challenge value.
+  ///
+  /// A failed lookup, such as a timeout or `SERVFAIL`, is retried like a
+  /// server that does not serve the value yet. If the deadline passes, the
+  /// last lookup error is reported.
+  async fn wait_for_propagation(&self, challenge: &Dns01Challenge) -> Result<()> {
+    let authoritative = match &self.authoritative {
+      Some(authoritative) => authoritative.clone(),
+      None => self.resolver.authoritative_for(&challenge.zone).await?,
+    };
+    let deadline = tokio::time::Instant::now() + self.propagation_timeout;
+    loop {
+      let mut pending = None;
+      let mut failure = None;
+      for server in authoritative.per_server() {
+        let served = match server.txt(&challenge.fqdn).await {
+          Ok(served) => served,
+          Err(e) => {
+            failure = Some(e);
+            Vec::new()
+          }
+        };
+        if !served.contains(&challenge.value) {
+          pending = server.servers().first().copied();
+          break;
+        }
+      }
+      let Some(server) = pending else {
+        return Ok(());
+      };
+      if tokio::time::Instant::now() + self.poll_interval > deadline {
+        return Err(match failure {
+          Some(e) => Error::Dns(format!(
+            "Timed out waiting for {} to serve the TXT record at {}: {}",
+            server, challenge.fqdn, e
+          )),
+          None => Error::Dns(format!(
+            "Timed out waiting for {} to serve the TXT record at {}",
+            server, challenge.fqdn
+          )),
+        });
+      }
+      tokio::time::sleep(self.poll_interval).await;
+    }
+  }
+}
+
+fn normalize(name: &str) -> String {
+  name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+fn is_within(name: &str, zone: &str) -> bool {
+  name == zone || name.ends_with(&format!(".{}", zone))
+}
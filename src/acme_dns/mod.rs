@@ -0,0 +1,248 @@
+//! # acme-dns Compatible Server
+//!
+//! Implements the HTTP API of [acme-dns](https://github.com/joohoi/acme-dns),
+//! which Traefik, cert-manager, lego and certbot-dns-acmedns already support,
+//! on top of a registrar's DNS records.
+//!
+//! `POST /register` creates an account with a random subdomain under the
+//! configured domain (e.g., `d420c923-bbd7-4056-ab64-c3ca54c9b3cf.acme.example.com`).
+//! The user points `_acme-challenge.their-domain` at it with a CNAME record once.
+//! `POST /update`, authenticated with the `X-Api-User` and `X-Api-Key`
+//! headers, then publishes the challenge TXT record for that subdomain only,
+//! so ACME clients never hold the registrar's API keys. Like acme-dns, the two
+//! most recent values are kept so a wildcard and an apex certificate can be
+//! validated together.
+//!
+//! To use this module, you must enable the `acme-dns` feature in your `Cargo.toml`.
+
+use crate::records::{DnsRecords, Record};
+use crate::{Error, Result};
+use axum::extract::{ConnectInfo, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+
+pub mod types;
+
+use types::{AccountStore, parse_cidr, sha256_hex};
+pub use types::{
+  Account, AcmeDnsConfig, AcmeDnsError, PublishedTxt, RegisterRequest, Registration, UpdateRequest,
+};
+
+/// The number of TXT values kept per account.
+const KEPT_VALUES: usize = 2;
+
+/// An acme-dns compatible server backed by a registrar's DNS records.
+#[derive(Clone)]
+pub struct AcmeDnsServer {
+  records: Arc<dyn DnsRecords>,
+  config: Arc<AcmeDnsConfig>,
+  store: Arc<Mutex<AccountStore>>,
+}
+
+impl AcmeDnsServer {
+  /// Creates a server, loading existing accounts from the configured storage file.
+  ///
+  /// # Arguments
+  /// * `records` - The registrar hosting `config.zone`.
+  /// * `config` - The domain to create accounts under and where to store them.
+  pub fn new(records: Arc<dyn DnsRecords>, mut config: AcmeDnsConfig) -> Result<Self> {
+    config.domain = config.domain.trim_end_matches('.').to_ascii_lowercase();
+    config.zone = config.zone.trim_end_matches('.').to_ascii_lowercase();
+    if !(config.domain == config.zone || config.domain.ends_with(&format!(".{}", config.zone))) {
+      return Err(Error::InvalidInput(format!(
+        "acme-dns domain '{}' is not within zone '{}'",
+        config.domain, config.zone
+      )));
+    }
+    let store = match &config.storage {
+      Some(path) => AccountStore::load(path)?,
+      None => AccountStore::default(),
+    };
+    Ok(Self {
+      records,
+      config: Arc::new(config),
+      store: Arc::new(Mutex::new(store)),
+    })
+  }
+
+  /// Creates an account with a random subdomain and password.
+  ///
+  /// # Arguments
+  /// * `request` - The networks allowed to update the account.
+  pub async fn register(&self, request: RegisterRequest) -> std::result::Result<Registration, AcmeDnsError> {
+    if self.config.disable_registration {
+      return Err(AcmeDnsError::RegistrationDisabled);
+    }
+    if request.allow_from.iter().any(|cidr| parse_cidr(cidr).is_none()) {
+      return Err(AcmeDnsError::BadAllowFrom);
+    }
+    let password = format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())[..40].to_string();
+    let account = Account {
+      username: uuid::Uuid::new_v4().to_string(),
+      password_sha256: sha256_hex(&password),
+      subdomain: uuid::Uuid::new_v4().to_string(),
+      allow_from: request.allow_from,
+      txt: Vec::new(),
+    };
+    let registration = Registration {
+      username: account.username.clone(),
+      password,
+      fulldomain: format!("{}.{}", account.subdomain, self.config.domain),
+      subdomain: account.subdomain.clone(),
+      allow_from: account.allow_from.clone(),
+    };
+
+    let mut store = self.store.lock().await;
+    store.accounts.push(account);
+    self.persist(&store)?;
+    Ok(registration)
+  }
+
+  /// Publishes a TXT value for an account's subdomain, removing values older than the previous one.
+  ///
+  /// # Arguments
+  /// * `username` - The `X-Api-User` header.
+  /// * `password` - The `X-Api-Key` header.
+  /// * `request` - The subdomain and TXT value.
+  /// * `client_ip` - The address the request came from, checked against the account's `allowfrom`.
+  pub async fn update(
+    &self,
+    username: &str,
+    password: &str,
+    request: &UpdateRequest,
+    client_ip: IpAddr,
+  ) -> std::result::Result<(), AcmeDnsError> {
+    let mut store = self.store.lock().await;
+    let account = store
+      .accounts
+      .iter_mut()
+      .find(|account| account.username == username)
+      .filter(|account| account.verify(password) && account.allows(client_ip))
+      .ok_or(AcmeDnsError::Forbidden)?;
+    if account.subdomain != request.subdomain {
+      return Err(AcmeDnsError::BadSubdomain);
+    }
+    if !is_challenge_value(&request.txt) {
+      return Err(AcmeDnsError::BadTxt);
+    }
+    if account.txt.iter().any(|published| published.value == request.txt) {
+      return Ok(());
+    }
+
+    let fqdn = format!("{}.{}", account.subdomain, self.config.domain);
+    let host = fqdn[..fqdn.len() - self.config.zone.len() - 1].to_string();
+    let mut record = Record::new(host, "TXT", request.txt.clone());
+    record.ttl = self.config.ttl;
+    let record_id = self
+      .records
+      .create_record(&self.config.zone, &record)
+      .await
+      .map_err(internal)?;
+    account.txt.push(PublishedTxt {
+      value: request.txt.clone(),
+      record_id,
+    });
+    // Forget a value only once its record is deleted, so a failed deletion is retried by the next update.
+    let mut pruned = Ok(());
+    while account.txt.len() > KEPT_VALUES {
+      if let Err(e) = self.records.delete_record(&self.config.zone, &account.txt[0].record_id).await {
+        pruned = Err(internal(e));
+        break;
+      }
+      account.txt.remove(0);
+    }
+    self.persist(&store)?;
+    pruned
+  }
+
+  /// Builds the HTTP routes: `/register`, `/update` and `/health`.
+  pub fn router(self) -> Router {
+    Router::new()
+      .route("/register", post(handle_register))
+      .route("/update", post(handle_update))
+      .route("/health", get(|| async { StatusCode::OK }))
+      .with_state(self)
+  }
+
+  /// Serves requests on `listener` until the future is dropped.
+  pub async fn serve(self, listener: TcpListener) -> Result<()> {
+    let app = self.router().into_make_service_with_connect_info::<SocketAddr>();
+    axum::serve(listener, app).await?;
+    Ok(())
+  }
+
+  fn persist(&self, store: &AccountStore) -> std::result::Result<(), AcmeDnsError> {
+    match &self.config.storage {
+      Some(path) => store.save(path).map_err(internal),
+      None => Ok(()),
+    }
+  }
+
+  fn client_ip(&self, peer: SocketAddr, headers: &HeaderMap) -> IpAddr {
+    let forwarded = self
+      .config
+      .trust_forwarded_for
+      .then(|| headers.get("x-forwarded-for")?.to_str().ok()?.split(',').next()?.trim().parse().ok())
+      .flatten();
+    forwarded.unwrap_or(peer.ip()).to_canonical()
+  }
+}
+
+fn internal(e: Error) -> AcmeDnsError {
+  AcmeDnsError::Internal(e.to_string())
+}
+
+/// Whether `value` looks like a DNS-01 digest: 43 characters of unpadded base64url.
+fn is_challenge_value(value: &str) -> bool {
+  value.len() == 43 && value.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+impl IntoResponse for AcmeDnsError {
+  fn into_response(self) -> Response {
+    let status = match self {
+      AcmeDnsError::Forbidden | AcmeDnsError::RegistrationDisabled => StatusCode::UNAUTHORIZED,
+      AcmeDnsError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+      _ => StatusCode::BAD_REQUEST,
+    };
+    (status, Json(serde_json::json!({ "error": self.to_string() }))).into_response()
+  }
+}
+
+async fn handle_register(State(server): State<AcmeDnsServer>, body: axum::body::Bytes) -> Response {
+  // The body is optional; an empty one registers an account without `allowfrom`.
+  let request = if body.iter().all(u8::is_ascii_whitespace) {
+    RegisterRequest::default()
+  } else {
+    match serde_json::from_slice(&body) {
+      Ok(request) => request,
+      Err(_) => return AcmeDnsError::BadAllowFrom.into_response(),
+    }
+  };
+  match server.register(request).await {
+    Ok(registration) => (StatusCode::CREATED, Json(registration)).into_response(),
+    Err(e) => e.into_response(),
+  }
+}
+
+async fn handle_update(
+  State(server): State<AcmeDnsServer>,
+  ConnectInfo(peer): ConnectInfo<SocketAddr>,
+  headers: HeaderMap,
+  body: axum::body::Bytes,
+) -> Response {
+  let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok()).unwrap_or_default();
+  let Ok(request) = serde_json::from_slice::<UpdateRequest>(&body) else {
+    return AcmeDnsError::BadTxt.into_response();
+  };
+  let client_ip = server.client_ip(peer, &headers);
+  match server.update(header("x-api-user"), header("x-api-key"), &request, client_ip).await {
+    Ok(()) => Json(serde_json::json!({ "txt": request.txt })).into_response(),
+    Err(e) => e.into_response(),
+  }
+}
@@ -0,0 +1,194 @@
+//! Contains the acme-dns server configuration, account storage and API payloads.
+
+use crate::secret::constant_time_eq;
+use crate::{Result, config};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+/// The configuration of an acme-dns compatible server.
+///
+/// ```yaml
+/// domain: acme.example.com      # Accounts get <subdomain>.acme.example.com
+/// zone: example.com             # The registrar zone containing `domain`
+/// storage: /var/lib/registrar/acme-dns.json
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct AcmeDnsConfig {
+  /// The name under which account subdomains are created.
+  pub domain: String,
+  /// The registered domain (zone) containing `domain`.
+  pub zone: String,
+  /// The file holding registered accounts. Without it, accounts only live in memory.
+  #[serde(default)]
+  pub storage: Option<PathBuf>,
+  /// Rejects `/register` requests, so that only existing accounts can be used.
+  #[serde(default)]
+  pub disable_registration: bool,
+  /// The TTL of challenge records. `None` uses the registrar's default.
+  #[serde(default)]
+  pub ttl: Option<u32>,
+  /// Takes the client address from the first `X-Forwarded-For` entry. Enable only behind a reverse proxy.
+  #[serde(default)]
+  pub trust_forwarded_for: bool,
+}
+
+impl AcmeDnsConfig {
+  /// Parses a configuration from YAML text.
+  pub fn from_yaml(yaml: &str) -> Result<Self> {
+    config::parse_yaml(yaml, "acme-dns")
+  }
+
+  /// Loads a configuration file. A file that cannot be read is an `Error::Io`.
+  pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+    config::load_yaml(path.as_ref(), "acme-dns")
+  }
+}
+
+/// A TXT record published for an account.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PublishedTxt {
+  pub value: String,
+  pub record_id: String,
+}
+
+/// A registered account, as persisted in the storage file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Account {
+  pub username: String,
+  /// The hexadecimal SHA-256 digest of the generated password.
+  pub password_sha256: String,
+  pub subdomain: String,
+  /// The networks allowed to update this account (e.g., "192.0.2.0/24"). Empty allows all.
+  #[serde(default)]
+  pub allow_from: Vec<String>,
+  /// The most recent TXT records, oldest first. At most two are kept.
+  #[serde(default)]
+  pub txt: Vec<PublishedTxt>,
+}
+
+impl Account {
+  /// Checks a password against the stored digest.
+  pub(crate) fn verify(&self, password: &str) -> bool {
+    constant_time_eq(sha256_hex(password).as_bytes(), self.password_sha256.as_bytes())
+  }
+
+  /// Whether `ip` may update this account.
+  pub(crate) fn allows(&self, ip: IpAddr) -> bool {
+    self.allow_from.is_empty() || self.allow_from.iter().any(|cidr| cidr_contains(cidr, ip))
+  }
+}
+
+/// The persisted set of accounts.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub(crate) struct AccountStore {
+  pub accounts: Vec<Account>,
+}
+
+impl AccountStore {
+  /// Loads the accounts from `path`, or starts empty if it does not exist.
+  pub fn load(path: &Path) -> Result<Self> {
+    if !path.exists() {
+      return Ok(Self::default());
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+  }
+
+  /// Writes the accounts to `path`, replacing it atomically.
+  pub fn save(&self, path: &Path) -> Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    std::fs::write(&temp_path, serde_json::to_vec_pretty(self)?)?;
+    std::fs::rename(&temp_path, path)?;
+    Ok(())
+  }
+}
+
+/// The body of a `/register` request.
+#[derive(Deserialize, Debug, Default)]
+pub struct RegisterRequest {
+  #[serde(default, rename = "allowfrom")]
+  pub allow_from: Vec<String>,
+}
+
+/// The response to a successful `/register` request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Registration {
+  pub username: String,
+  pub password: String,
+  /// The name to point `_acme-challenge` CNAME records at.
+  pub fulldomain: String,
+  pub subdomain: String,
+  #[serde(rename = "allowfrom")]
+  pub allow_from: Vec<String>,
+}
+
+/// The body of an `/update` request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateRequest {
+  pub subdomain: String,
+  pub txt: String,
+}
+
+/// Why an acme-dns request was refused. `Display` gives the API's error code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AcmeDnsError {
+  /// Wrong credentials, or an address outside the account's `allowfrom`.
+  Forbidden,
+  /// The subdomain does not belong to the account.
+  BadSubdomain,
+  /// The TXT value is not a 43-character base64url digest.
+  BadTxt,
+  /// An `allowfrom` entry is not a valid network.
+  BadAllowFrom,
+  /// Registration is disabled.
+  RegistrationDisabled,
+  /// The registrar or the account storage failed.
+  Internal(String),
+}
+
+impl std::fmt::Display for AcmeDnsError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(match self {
+      AcmeDnsError::Forbidden => "forbidden",
+      AcmeDnsError::BadSubdomain => "bad_subdomain",
+      AcmeDnsError::BadTxt => "bad_txt",
+      AcmeDnsError::BadAllowFrom => "invalid_allowfrom_cidr",
+      AcmeDnsError::RegistrationDisabled => "registration_disabled",
+      AcmeDnsError::Internal(_) => "internal_error",
+    })
+  }
+}
+
+pub(crate) fn sha256_hex(value: &str) -> String {
+  Sha256::digest(value.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Parses a network such as "192.0.2.0/24" or "2001:db8::/32". A bare address is a single host.
+pub(crate) fn parse_cidr(cidr: &str) -> Option<(IpAddr, u8)> {
+  let (address, prefix) = match cidr.split_once('/') {
+    Some((address, prefix)) => (address, Some(prefix)),
+    None => (cidr, None),
+  };
+  let address: IpAddr = address.trim().parse().ok()?;
+  let max = if address.is_ipv4() { 32 } else { 128 };
+  let prefix = match prefix {
+    Some(prefix) => prefix.trim().parse::<u8>().ok().filter(|prefix| *prefix <= max)?,
+    None => max,
+  };
+  Some((address, prefix))
+}
+
+fn cidr_contains(cidr: &str, ip: IpAddr) -> bool {
+  let Some((network, prefix)) = parse_cidr(cidr) else {
+    return false;
+  };
+  let (network, ip, bits) = match (network, ip.to_canonical()) {
+    (IpAddr::V4(network), IpAddr::V4(ip)) => (u32::from(network) as u128, u32::from(ip) as u128, 32),
+    (IpAddr::V6(network), IpAddr::V6(ip)) => (u128::from(network), u128::from(ip), 128),
+    _ => return false,
+  };
+  let shift = bits - u32::from(prefix);
+  shift >= 128 || (network >> shift) == (ip >> shift)
+}
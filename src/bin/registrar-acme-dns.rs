@@ -0,0 +1,68 @@
+//! An acme-dns compatible API server that publishes challenges at Porkbun or Name.com.
+//!
+//! ACME clients with acme-dns support (Traefik, cert-manager, lego,
+//! certbot-dns-acmedns) register an account once and afterwards only update
+//! the TXT record of their own subdomain, without holding the registrar's API keys.
+//!
+//! ```sh
+//! registrar-acme-dns --server-config /etc/registrar/acme-dns.yaml --listen 0.0.0.0:8053
+//! ```
+//!
+//! Requires the `acme-dns-server` feature.
+
+use clap::Parser;
+use registrar::acme_dns::{AcmeDnsConfig, AcmeDnsServer};
+use registrar::config::Config;
+use registrar::{Error, Provider, Result};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Serves the acme-dns API for domains at Porkbun or Name.com")]
+struct Args {
+  /// The server configuration naming the account domain, its zone and the account storage.
+  #[arg(long, env = "REGISTRAR_ACME_DNS_CONFIG")]
+  server_config: PathBuf,
+
+  /// The address to listen on. Put a TLS-terminating proxy in front when exposed publicly.
+  #[arg(long, env = "REGISTRAR_ACME_DNS_LISTEN", default_value = "127.0.0.1:8053")]
+  listen: SocketAddr,
+
+  /// The registrar hosting the zone, if several are configured.
+  #[arg(long, env = "REGISTRAR_PROVIDER")]
+  provider: Option<Provider>,
+
+  /// The configured account to use, if several are configured.
+  #[arg(long, env = "REGISTRAR_ACCOUNT")]
+  account: Option<String>,
+
+  /// The registrar credentials (default: ~/.config/registrar/config.yaml).
+  #[arg(long, env = "REGISTRAR_CONFIG")]
+  config: Option<PathBuf>,
+}
+
+async fn run(args: Args) -> Result<()> {
+  let registry = Config::load_with_env(args.config.as_deref())?.registry()?;
+  let account = registry.select(args.account.as_deref(), args.provider)?;
+  let records = registry.dns_records(account)?;
+  let server = AcmeDnsServer::new(records.into(), AcmeDnsConfig::load(&args.server_config)?)?;
+
+  let listener = tokio::net::TcpListener::bind(args.listen).await?;
+  eprintln!("Listening on {}", args.listen);
+  tokio::select! {
+    result = server.serve(listener) => result,
+    signal = tokio::signal::ctrl_c() => signal.map_err(Error::from),
+  }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+  match run(Args::parse()).await {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("registrar-acme-dns: {}", e);
+      ExitCode::FAILURE
+    }
+  }
+}
@@ -0,0 +1,103 @@
+//! A DNS-01 hook for ACME clients that run external programs.
+//!
+//! Publishes and removes `_acme-challenge` TXT records on Porkbun or Name.com
+//! using the credentials from the registrar configuration file or the environment.
+//!
+//! * certbot: `certbot certonly --manual --preferred-challenges dns
+//!   --manual-auth-hook registrar-acme-hook --manual-cleanup-hook registrar-acme-hook`.
+//!   The hook reads `CERTBOT_DOMAIN` and `CERTBOT_VALIDATION`, and knows it is
+//!   cleaning up because certbot sets `CERTBOT_AUTH_OUTPUT`.
+//! * lego: `EXEC_PATH=registrar-acme-hook lego --dns exec ...`, in the default
+//!   mode (`present|cleanup FQDN VALUE`) or with `EXEC_MODE=RAW`
+//!   (`present|cleanup -- DOMAIN TOKEN KEY_AUTH`).
+//! * acme.sh and other scripts: call `registrar-acme-hook present FQDN VALUE`
+//!   and `registrar-acme-hook cleanup FQDN VALUE`.
+//!
+//! Requires the `acme-hook` feature.
+
+use clap::Parser;
+use registrar::acme::{Dns01Solver, HookAction};
+use registrar::config::Config;
+use registrar::lookup::DnsLookup;
+use registrar::{Error, Provider, Result};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+const MODES: &str = "\
+Modes:
+  auth | cleanup                             certbot hooks (CERTBOT_DOMAIN, CERTBOT_VALIDATION)
+  present | cleanup FQDN VALUE               lego exec provider, acme.sh and scripts
+  present | cleanup -- DOMAIN TOKEN KEYAUTH  lego exec provider with EXEC_MODE=RAW
+
+Without a mode, certbot is assumed: cleanup if CERTBOT_AUTH_OUTPUT is set, otherwise auth.";
+
+#[derive(Parser, Debug)]
+#[command(
+  version,
+  about = "Publishes and removes ACME DNS-01 challenge records at Porkbun or Name.com",
+  after_help = MODES
+)]
+struct Args {
+  /// The configuration file (default: ~/.config/registrar/config.yaml).
+  #[arg(long, env = "REGISTRAR_CONFIG")]
+  config: Option<PathBuf>,
+
+  /// The registrar hosting the domain, if several are configured.
+  #[arg(long, env = "REGISTRAR_PROVIDER")]
+  provider: Option<Provider>,
+
+  /// The configured account to use, if several are configured.
+  #[arg(long, env = "REGISTRAR_ACCOUNT")]
+  account: Option<String>,
+
+  /// Seconds to wait for the record to be served.
+  #[arg(long, env = "EXEC_PROPAGATION_TIMEOUT", default_value_t = 300)]
+  propagation_timeout: u64,
+
+  /// Seconds between propagation checks.
+  #[arg(long, env = "EXEC_POLLING_INTERVAL", default_value_t = 5)]
+  polling_interval: u64,
+
+  /// The mode and its arguments.
+  #[arg(value_name = "MODE [ARGS]", trailing_var_arg = true, allow_hyphen_values = true)]
+  args: Vec<String>,
+}
+
+async fn run(args: Args) -> Result<()> {
+  let action = HookAction::parse(&args.args, |name| std::env::var(name).ok())?;
+
+  let registry = Config::load_with_env(args.config.as_deref())?.registry()?;
+  let records = registry.dns_records(registry.select(args.account.as_deref(), args.provider)?)?;
+  let solver = Dns01Solver::with_resolver(records.into(), DnsLookup::system()?).with_propagation(
+    Duration::from_secs(args.propagation_timeout),
+    Duration::from_secs(args.polling_interval),
+  );
+
+  match action {
+    HookAction::Present { identifier, value } => {
+      let challenge = solver.present_value(&identifier, &value).await?;
+      eprintln!("Published TXT {} in zone {}", challenge.fqdn, challenge.zone);
+    }
+    HookAction::Cleanup { identifier, value } => {
+      solver.cleanup_value(&identifier, &value).await?;
+      eprintln!("Removed the challenge record for {}", identifier);
+    }
+  }
+  Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+  match run(Args::parse()).await {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e @ Error::InvalidInput(_)) => {
+      eprintln!("registrar-acme-hook: {}", e);
+      ExitCode::from(2)
+    }
+    Err(e) => {
+      eprintln!("registrar-acme-hook: {}", e);
+      ExitCode::FAILURE
+    }
+  }
+}
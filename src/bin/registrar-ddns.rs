@@ -0,0 +1,166 @@
+//! A dynamic DNS daemon for domains hosted at Porkbun or Name.com.
+//!
+//! Keeps A/AAAA records pointed at this machine's public addresses, using the
+//! credentials from the registrar configuration file or the environment.
+//!
+//! ```sh
+//! registrar-ddns --domain example.com --host home --state-file /var/lib/registrar/ddns.json
+//! ```
+//!
+//! Requires the `ddns-daemon` feature.
+
+use clap::{Parser, ValueEnum};
+use registrar::config::{AccountRegistry, Config};
+use registrar::ddns::{DdnsUpdater, IpSource, LocalAddress, UpdateReport};
+use registrar::{Error, Provider, Result};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How to discover an address family.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+  /// Porkbun for IPv4 if Porkbun credentials are configured, otherwise the local address.
+  Auto,
+  /// Porkbun's `ping` endpoint.
+  Porkbun,
+  /// The address of the local interface used to reach the internet.
+  Local,
+  /// Do not publish this family.
+  Off,
+}
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Keeps A/AAAA records at Porkbun or Name.com pointed at this machine")]
+struct Args {
+  /// The registered domain whose records are updated.
+  #[arg(long, env = "REGISTRAR_DDNS_DOMAIN")]
+  domain: String,
+
+  /// A host to update, relative to the domain. Use `@` for the apex. Repeatable.
+  #[arg(long = "host", required = true, env = "REGISTRAR_DDNS_HOSTS", value_delimiter = ',')]
+  hosts: Vec<String>,
+
+  /// The registrar hosting the domain, if several are configured.
+  #[arg(long, env = "REGISTRAR_PROVIDER")]
+  provider: Option<Provider>,
+
+  /// The configured account to use, if several are configured.
+  #[arg(long, env = "REGISTRAR_ACCOUNT")]
+  account: Option<String>,
+
+  /// The configuration file (default: ~/.config/registrar/config.yaml).
+  #[arg(long, env = "REGISTRAR_CONFIG")]
+  config: Option<PathBuf>,
+
+  /// How to discover the public IPv4 address.
+  #[arg(long, value_enum, default_value_t = Source::Auto)]
+  ipv4: Source,
+
+  /// How to discover the public IPv6 address.
+  #[arg(long, value_enum, default_value_t = Source::Auto)]
+  ipv6: Source,
+
+  /// The TTL of updated records, in seconds.
+  #[arg(long)]
+  ttl: Option<u32>,
+
+  /// Seconds between checks.
+  #[arg(long, default_value_t = 300)]
+  interval: u64,
+
+  /// Remembers the published addresses so unchanged runs make no API calls.
+  #[arg(long, env = "REGISTRAR_DDNS_STATE")]
+  state_file: Option<PathBuf>,
+
+  /// Update once and exit instead of running continuously.
+  #[arg(long)]
+  once: bool,
+}
+
+fn ip_source(
+  registry: &AccountRegistry,
+  account: &str,
+  family: &str,
+  source: Source,
+) -> Result<Option<Arc<dyn IpSource>>> {
+  let source = match source {
+    Source::Auto if family == "ipv4" && porkbun_account(registry, account).is_ok() => Source::Porkbun,
+    Source::Auto => Source::Local,
+    other => other,
+  };
+  Ok(match source {
+    Source::Porkbun => Some(Arc::new(registry.porkbun(porkbun_account(registry, account)?)?)),
+    Source::Local => Some(Arc::new(LocalAddress)),
+    Source::Off | Source::Auto => None,
+  })
+}
+
+/// The Porkbun account to ask for the public address: `account` if it is at
+/// Porkbun, otherwise the one [`AccountRegistry::select`] picks there.
+fn porkbun_account<'a>(registry: &'a AccountRegistry, account: &'a str) -> Result<&'a str> {
+  if registry.provider(account)? == Provider::Porkbun {
+    return Ok(account);
+  }
+  registry.select(None, Some(Provider::Porkbun))
+}
+
+fn log(result: &Result<UpdateReport>) {
+  match result {
+    Ok(report) if report.skipped => {}
+    Ok(report) => {
+      for host in &report.hosts {
+        eprintln!("{} -> {}: {:?}", host.fqdn, host.ip, host.change);
+      }
+      if report.hosts.is_empty() {
+        eprintln!("No public address found; nothing to update");
+      }
+    }
+    Err(e) => eprintln!("Update failed: {}", e),
+  }
+}
+
+async fn run(args: Args) -> Result<()> {
+  let registry = Config::load_with_env(args.config.as_deref())?.registry()?;
+  let account = registry.select(args.account.as_deref(), args.provider)?;
+  let records = registry.dns_records(account)?;
+
+  let mut updater = DdnsUpdater::new(records.into(), &args.domain);
+  for host in &args.hosts {
+    updater = updater.with_host(host);
+  }
+  if let Some(source) = ip_source(&registry, account, "ipv4", args.ipv4)? {
+    updater = updater.with_ipv4_source(source);
+  }
+  if let Some(source) = ip_source(&registry, account, "ipv6", args.ipv6)? {
+    updater = updater.with_ipv6_source(source);
+  }
+  if let Some(ttl) = args.ttl {
+    updater = updater.with_ttl(ttl);
+  }
+  if let Some(path) = &args.state_file {
+    updater = updater.with_state_file(path)?;
+  }
+
+  if args.once {
+    let result = updater.update().await;
+    log(&result);
+    return result.map(|_| ());
+  }
+  tokio::select! {
+    _ = updater.run(Duration::from_secs(args.interval), log) => Ok(()),
+    signal = tokio::signal::ctrl_c() => signal.map_err(Error::from),
+  }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+  match run(Args::parse()).await {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("registrar-ddns: {}", e);
+      ExitCode::FAILURE
+    }
+  }
+}
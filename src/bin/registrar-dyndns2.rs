@@ -0,0 +1,70 @@
+//! A dyndns2 protocol server that updates DNS records at Porkbun or Name.com.
+//!
+//! Routers and NAS boxes configured for a "custom" or "dyndns2" provider send
+//! their updates here, authenticating with the per-device accounts listed in
+//! the server configuration rather than the registrar's API keys.
+//!
+//! ```sh
+//! registrar-dyndns2 --users /etc/registrar/dyndns2.yaml --listen 0.0.0.0:8245
+//! ```
+//!
+//! Requires the `dyndns2-server` feature.
+
+use clap::Parser;
+use registrar::config::Config;
+use registrar::dyndns2::{Dyndns2Config, Dyndns2Server};
+use registrar::{Error, Provider, Result};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Serves dyndns2 updates for domains at Porkbun or Name.com")]
+struct Args {
+  /// The server configuration listing zones and device accounts.
+  #[arg(long, env = "REGISTRAR_DYNDNS2_USERS")]
+  users: PathBuf,
+
+  /// The address to listen on. Put a TLS-terminating proxy in front when exposed publicly.
+  #[arg(long, env = "REGISTRAR_DYNDNS2_LISTEN", default_value = "127.0.0.1:8245")]
+  listen: SocketAddr,
+
+  /// The registrar hosting the zones, if several are configured.
+  #[arg(long, env = "REGISTRAR_PROVIDER")]
+  provider: Option<Provider>,
+
+  /// The configured account to use, if several are configured.
+  #[arg(long, env = "REGISTRAR_ACCOUNT")]
+  account: Option<String>,
+
+  /// The registrar credentials (default: ~/.config/registrar/config.yaml).
+  #[arg(long, env = "REGISTRAR_CONFIG")]
+  config: Option<PathBuf>,
+}
+
+async fn run(args: Args) -> Result<()> {
+  let registry = Config::load_with_env(args.config.as_deref())?.registry()?;
+  let account = registry.select(args.account.as_deref(), args.provider)?;
+  let records = registry.dns_records(account)?;
+  let server = Dyndns2Server::new(records.into(), Dyndns2Config::load(&args.users)?)?
+    .with_error_log(Arc::new(|hostname, e| eprintln!("Updating {} failed: {}", hostname, e)));
+
+  let listener = tokio::net::TcpListener::bind(args.listen).await?;
+  eprintln!("Listening on {}", args.listen);
+  tokio::select! {
+    result = server.serve(listener) => result,
+    signal = tokio::signal::ctrl_c() => signal.map_err(Error::from),
+  }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+  match run(Args::parse()).await {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("registrar-dyndns2: {}", e);
+      ExitCode::FAILURE
+    }
+  }
+}
@@ -0,0 +1,83 @@
+//! An external-dns webhook provider for zones at Porkbun or Name.com.
+//!
+//! Run it as a sidecar of external-dns started with `--provider=webhook`:
+//!
+//! ```sh
+//! registrar-external-dns --domain-filter example.com --min-ttl 600
+//! ```
+//!
+//! Requires the `external-dns-server` feature.
+
+use clap::Parser;
+use registrar::config::Config;
+use registrar::external_dns::ExternalDnsProvider;
+use registrar::{Error, Provider, Result};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Serves the external-dns webhook protocol for domains at Porkbun or Name.com")]
+struct Args {
+  /// A registered domain to manage. Repeatable.
+  #[arg(long = "domain-filter", required = true, env = "REGISTRAR_EXTERNAL_DNS_DOMAINS", value_delimiter = ',')]
+  domains: Vec<String>,
+
+  /// A name, within a managed domain, to leave alone along with everything below it. Repeatable.
+  #[arg(long = "exclude-domains", env = "REGISTRAR_EXTERNAL_DNS_EXCLUDE", value_delimiter = ',')]
+  exclude: Vec<String>,
+
+  /// Raises lower TTLs to this value. Set it to the registrar's minimum.
+  #[arg(long)]
+  min_ttl: Option<u32>,
+
+  /// The address to listen on. external-dns expects the webhook on localhost:8888.
+  #[arg(long, env = "REGISTRAR_EXTERNAL_DNS_LISTEN", default_value = "127.0.0.1:8888")]
+  listen: SocketAddr,
+
+  /// The registrar hosting the domains, if several are configured.
+  #[arg(long, env = "REGISTRAR_PROVIDER")]
+  provider: Option<Provider>,
+
+  /// The configured account to use, if several are configured.
+  #[arg(long, env = "REGISTRAR_ACCOUNT")]
+  account: Option<String>,
+
+  /// The registrar credentials (default: ~/.config/registrar/config.yaml).
+  #[arg(long, env = "REGISTRAR_CONFIG")]
+  config: Option<PathBuf>,
+}
+
+async fn run(args: Args) -> Result<()> {
+  let registry = Config::load_with_env(args.config.as_deref())?.registry()?;
+  let account = registry.select(args.account.as_deref(), args.provider)?;
+  let records = registry.dns_records(account)?;
+  let mut provider = ExternalDnsProvider::new(records.into());
+  for domain in &args.domains {
+    provider = provider.with_zone(domain);
+  }
+  for domain in &args.exclude {
+    provider = provider.with_exclude(domain);
+  }
+  if let Some(min_ttl) = args.min_ttl {
+    provider = provider.with_min_ttl(min_ttl);
+  }
+
+  let listener = tokio::net::TcpListener::bind(args.listen).await?;
+  eprintln!("Listening on {}", args.listen);
+  tokio::select! {
+    result = provider.serve(listener) => result,
+    signal = tokio::signal::ctrl_c() => signal.map_err(Error::from),
+  }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+  match run(Args::parse()).await {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("registrar-external-dns: {}", e);
+      ExitCode::FAILURE
+    }
+  }
+}
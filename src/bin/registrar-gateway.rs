@@ -0,0 +1,69 @@
+//! A REST gateway handing out scoped tokens over one set of registrar credentials.
+//!
+//! ```sh
+//! registrar-gateway --server-config /etc/registrar/gateway.yaml --listen 0.0.0.0:8080
+//! ```
+//!
+//! Requires the `gateway-server` feature.
+
+use clap::Parser;
+use registrar::config::Config;
+use registrar::gateway::{Gateway, GatewayConfig, file_audit_sink};
+use registrar::{Error, Provider, Result};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Serves scoped REST access to domains at Porkbun or Name.com")]
+struct Args {
+  /// The tokens, their scopes and the audit log.
+  #[arg(long, env = "REGISTRAR_GATEWAY_CONFIG")]
+  server_config: PathBuf,
+
+  /// The address to listen on.
+  #[arg(long, env = "REGISTRAR_GATEWAY_LISTEN", default_value = "127.0.0.1:8080")]
+  listen: SocketAddr,
+
+  /// The registrar hosting the domains, if several are configured.
+  #[arg(long, env = "REGISTRAR_PROVIDER")]
+  provider: Option<Provider>,
+
+  /// The configured account to use, if several are configured.
+  #[arg(long, env = "REGISTRAR_ACCOUNT")]
+  account: Option<String>,
+
+  /// The registrar credentials (default: ~/.config/registrar/config.yaml).
+  #[arg(long, env = "REGISTRAR_CONFIG")]
+  config: Option<PathBuf>,
+}
+
+async fn run(args: Args) -> Result<()> {
+  let server_config = GatewayConfig::load(&args.server_config)?;
+  let registry = Config::load_with_env(args.config.as_deref())?.registry()?;
+  let account = registry.select(args.account.as_deref(), args.provider)?;
+  let audit_log = server_config.audit_log.clone();
+  let mut gateway = Gateway::new(registry.dns_records(account)?.into(), server_config)?
+    .with_portfolio(registry.portfolio(account)?.into());
+  if let Some(path) = audit_log {
+    gateway = gateway.with_audit_sink(file_audit_sink(path)?);
+  }
+
+  let listener = tokio::net::TcpListener::bind(args.listen).await?;
+  eprintln!("Listening on {}", args.listen);
+  tokio::select! {
+    result = gateway.serve(listener) => result,
+    signal = tokio::signal::ctrl_c() => signal.map_err(Error::from),
+  }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+  match run(Args::parse()).await {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("registrar-gateway: {}", e);
+      ExitCode::FAILURE
+    }
+  }
+}
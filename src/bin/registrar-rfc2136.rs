@@ -0,0 +1,72 @@
+//! An RFC 2136 DNS UPDATE server that applies updates to zones at Porkbun or Name.com.
+//!
+//! Tools that speak dynamic DNS updates, such as `nsupdate`,
+//! certbot-dns-rfc2136 and Kea DHCP, send TSIG-signed UPDATE messages here
+//! instead of to an authoritative name server.
+//!
+//! ```sh
+//! registrar-rfc2136 --server-config /etc/registrar/rfc2136.yaml --listen 127.0.0.1:5300
+//! ```
+//!
+//! Requires the `rfc2136-server` feature.
+
+use clap::Parser;
+use registrar::config::Config;
+use registrar::rfc2136::{Rfc2136Config, Rfc2136Server};
+use registrar::{Error, Provider, Result};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Applies RFC 2136 DNS updates to domains at Porkbun or Name.com")]
+struct Args {
+  /// The server configuration listing the TSIG keys and their zones.
+  #[arg(long, env = "REGISTRAR_RFC2136_CONFIG")]
+  server_config: PathBuf,
+
+  /// The address to listen on, over both UDP and TCP.
+  #[arg(long, env = "REGISTRAR_RFC2136_LISTEN", default_value = "127.0.0.1:5300")]
+  listen: SocketAddr,
+
+  /// The registrar hosting the zones, if several are configured.
+  #[arg(long, env = "REGISTRAR_PROVIDER")]
+  provider: Option<Provider>,
+
+  /// The configured account to use, if several are configured.
+  #[arg(long, env = "REGISTRAR_ACCOUNT")]
+  account: Option<String>,
+
+  /// The registrar credentials (default: ~/.config/registrar/config.yaml).
+  #[arg(long, env = "REGISTRAR_CONFIG")]
+  config: Option<PathBuf>,
+}
+
+async fn run(args: Args) -> Result<()> {
+  let registry = Config::load_with_env(args.config.as_deref())?.registry()?;
+  let account = registry.select(args.account.as_deref(), args.provider)?;
+  let records = registry.dns_records(account)?;
+  let server = Rfc2136Server::new(records.into(), Rfc2136Config::load(&args.server_config)?)?
+    .with_error_log(Arc::new(|zone, e| eprintln!("Updating {} failed: {}", zone, e)));
+
+  let socket = tokio::net::UdpSocket::bind(args.listen).await?;
+  let listener = tokio::net::TcpListener::bind(args.listen).await?;
+  eprintln!("Listening on {}", args.listen);
+  tokio::select! {
+    result = server.clone().serve_udp(socket) => result,
+    result = server.serve_tcp(listener) => result,
+    signal = tokio::signal::ctrl_c() => signal.map_err(Error::from),
+  }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+  match run(Args::parse()).await {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("registrar-rfc2136: {}", e);
+      ExitCode::FAILURE
+    }
+  }
+}
@@ -0,0 +1,146 @@
+//! The `dns` subcommands.
+
+use crate::output::{self, Format};
+use clap::{Args, Subcommand};
+use registrar::records::{DnsRecords, Record};
+use registrar::{Error, Result};
+use serde_json::json;
+
+#[derive(Subcommand, Debug)]
+pub enum DnsCommand {
+  /// Lists a domain's records.
+  List {
+    /// The registered domain.
+    domain: String,
+    /// Only records with this host. Use `@` for the apex.
+    #[arg(long)]
+    host: Option<String>,
+    /// Only records of this type.
+    #[arg(long = "type")]
+    record_type: Option<String>,
+  },
+  /// Shows one record.
+  Get {
+    /// The registered domain.
+    domain: String,
+    /// The record's ID, as shown by `dns list`.
+    id: String,
+  },
+  /// Creates a record and prints its ID.
+  Create {
+    /// The registered domain.
+    domain: String,
+    #[command(flatten)]
+    fields: RecordFields,
+  },
+  /// Changes a record. Fields not given keep their current values.
+  Update {
+    /// The registered domain.
+    domain: String,
+    /// The record's ID, as shown by `dns list`.
+    id: String,
+    #[command(flatten)]
+    fields: RecordFields,
+  },
+  /// Deletes a record.
+  Delete {
+    /// The registered domain.
+    domain: String,
+    /// The record's ID, as shown by `dns list`.
+    id: String,
+  },
+}
+
+/// The fields of a record given on the command line.
+#[derive(Args, Debug)]
+pub struct RecordFields {
+  /// The name relative to the domain. Use `@` for the apex.
+  #[arg(long)]
+  host: Option<String>,
+  /// The record type (e.g., A, CNAME, TXT).
+  #[arg(long = "type")]
+  record_type: Option<String>,
+  /// The record data.
+  #[arg(long)]
+  content: Option<String>,
+  /// The TTL in seconds.
+  #[arg(long)]
+  ttl: Option<u32>,
+  /// The priority of MX and SRV records.
+  #[arg(long)]
+  priority: Option<u32>,
+}
+
+impl RecordFields {
+  /// Overwrites the fields of `record` that were given.
+  fn apply(self, mut record: Record) -> Record {
+    if let Some(host) = self.host {
+      record.host = relative_host(&host);
+    }
+    if let Some(record_type) = self.record_type {
+      record.record_type = record_type.to_ascii_uppercase();
+    }
+    if let Some(content) = self.content {
+      record.content = content;
+    }
+    record.ttl = self.ttl.or(record.ttl);
+    record.priority = self.priority.or(record.priority);
+    record
+  }
+
+  /// Builds a new record, which needs a type and content.
+  fn into_record(self) -> Result<Record> {
+    let (Some(record_type), Some(content)) = (&self.record_type, &self.content) else {
+      return Err(Error::InvalidInput("A new record needs --type and --content".to_string()));
+    };
+    let record = Record::new("", record_type, content.as_str());
+    Ok(self.apply(record))
+  }
+}
+
+/// Converts `@` to the empty host used for the apex.
+fn relative_host(host: &str) -> String {
+  if host == "@" { String::new() } else { host.to_string() }
+}
+
+/// Finds the record `id` in `domain`.
+async fn find(records: &dyn DnsRecords, domain: &str, id: &str) -> Result<Record> {
+  records
+    .list_records(domain)
+    .await?
+    .into_iter()
+    .find(|record| record.id.as_deref() == Some(id))
+    .ok_or_else(|| Error::InvalidInput(format!("No record '{}' in {}", id, domain)))
+}
+
+/// Runs a `dns` subcommand.
+pub async fn run(records: &dyn DnsRecords, command: DnsCommand, format: Format) -> Result<()> {
+  match command {
+    DnsCommand::List { domain, host, record_type } => {
+      let host = host.as_deref().map(relative_host);
+      let listed: Vec<Record> = records
+        .list_records(&domain)
+        .await?
+        .into_iter()
+        .filter(|record| host.as_ref().is_none_or(|host| record.host.eq_ignore_ascii_case(host)))
+        .filter(|record| record_type.as_ref().is_none_or(|t| record.record_type.eq_ignore_ascii_case(t)))
+        .collect();
+      output::list(format, &listed)
+    }
+    DnsCommand::Get { domain, id } => output::item(format, &find(records, &domain, &id).await?),
+    DnsCommand::Create { domain, fields } => {
+      let record = fields.into_record()?;
+      let id = records.create_record(&domain, &record).await?;
+      output::outcome(format, &format!("Created record {}", id), json!({ "id": id }))
+    }
+    DnsCommand::Update { domain, id, fields } => {
+      let record = fields.apply(find(records, &domain, &id).await?);
+      records.update_record(&domain, &record).await?;
+      output::outcome(format, &format!("Updated record {}", id), json!({ "id": id }))
+    }
+    DnsCommand::Delete { domain, id } => {
+      records.delete_record(&domain, &id).await?;
+      output::outcome(format, &format!("Deleted record {}", id), json!({ "id": id }))
+    }
+  }
+}
@@ -0,0 +1,143 @@
+//! The `domains` subcommands.
+
+use crate::ProviderChoice;
+use crate::output::{self, Format};
+use chrono::{Duration, Utc};
+use clap::Subcommand;
+use registrar::config::AccountRegistry;
+use registrar::portfolio::{DomainPortfolio, DomainSummary};
+use registrar::{Error, Result};
+use serde_json::json;
+
+#[derive(Subcommand, Debug)]
+pub enum DomainsCommand {
+  /// Lists the domains in the account, soonest to expire first.
+  List {
+    /// Only domains expiring within this period (e.g., `60d`, `8w`, or a number of days).
+    #[arg(long, value_parser = parse_period)]
+    expiring_within: Option<Duration>,
+  },
+  /// Shows a domain, including its nameservers.
+  Show {
+    /// The registered domain.
+    domain: String,
+  },
+  /// Replaces a domain's nameservers.
+  SetNs {
+    /// The registered domain.
+    domain: String,
+    /// The new nameservers.
+    #[arg(required = true)]
+    nameservers: Vec<String>,
+  },
+  /// Locks a domain against transfers.
+  Lock {
+    /// The registered domain.
+    domain: String,
+  },
+  /// Unlocks a domain so it can be transferred.
+  Unlock {
+    /// The registered domain.
+    domain: String,
+  },
+}
+
+/// Parses a period such as `60d` or `8w`. A bare number is a number of days; negative periods are rejected.
+fn parse_period(value: &str) -> std::result::Result<Duration, String> {
+  let (number, unit) = match value.strip_suffix(['d', 'D']) {
+    Some(number) => (number, 1),
+    None => match value.strip_suffix(['w', 'W']) {
+      Some(number) => (number, 7),
+      None => (value, 1),
+    },
+  };
+  let number: i64 = number.trim().parse().map_err(|_| format!("'{}' is not a period such as 60d or 8w", value))?;
+  if number < 0 {
+    return Err(format!("'{}' is negative; give a period such as 60d or 8w", value));
+  }
+  number
+    .checked_mul(unit)
+    .and_then(Duration::try_days)
+    .ok_or_else(|| format!("'{}' is too long a period", value))
+}
+
+/// The accounts a command applies to: every account for `--provider all`,
+/// otherwise the one named or the one selected for the provider.
+fn accounts(
+  registry: &AccountRegistry,
+  account: Option<&str>,
+  choice: Option<ProviderChoice>,
+) -> Result<Vec<Box<dyn DomainPortfolio>>> {
+  let names: Vec<&str> = match choice {
+    Some(ProviderChoice::All) if account.is_none() => registry.names().collect(),
+    Some(ProviderChoice::All) => {
+      return Err(Error::InvalidInput("`--provider all` cannot be combined with `--account`".to_string()));
+    }
+    Some(ProviderChoice::One(provider)) => vec![registry.select(account, Some(provider))?],
+    None => vec![registry.select(account, None)?],
+  };
+  if names.is_empty() {
+    return Err(Error::InvalidInput("No registrar credentials are configured".to_string()));
+  }
+  names.into_iter().map(|name| registry.portfolio(name)).collect()
+}
+
+/// The account holding `domain`. With several accounts, each one's listing is searched.
+async fn owner(accounts: Vec<Box<dyn DomainPortfolio>>, domain: &str) -> Result<Box<dyn DomainPortfolio>> {
+  if accounts.len() == 1 {
+    return Ok(accounts.into_iter().next().unwrap());
+  }
+  for account in accounts {
+    let domains = account.list_domains().await?;
+    if domains.iter().any(|summary| summary.name.eq_ignore_ascii_case(domain)) {
+      return Ok(account);
+    }
+  }
+  Err(Error::InvalidInput(format!("No configured account holds {}", domain)))
+}
+
+/// Runs a `domains` subcommand.
+pub async fn run(
+  registry: &AccountRegistry,
+  account: Option<&str>,
+  choice: Option<ProviderChoice>,
+  command: DomainsCommand,
+  format: Format,
+) -> Result<()> {
+  let accounts = accounts(registry, account, choice)?;
+  match command {
+    DomainsCommand::List { expiring_within } => {
+      let mut domains: Vec<DomainSummary> = Vec::new();
+      for account in &accounts {
+        domains.extend(account.list_domains().await?);
+      }
+      if let Some(period) = expiring_within {
+        let cutoff = Utc::now()
+          .checked_add_signed(period)
+          .ok_or_else(|| Error::InvalidInput("The --expiring-within period is too long".to_string()))?;
+        domains.retain(|domain| domain.expires.is_some_and(|expires| expires <= cutoff));
+      }
+      // Domains without a known expiry sort last.
+      domains.sort_by(|a, b| (a.expires.is_none(), a.expires, &a.name).cmp(&(b.expires.is_none(), b.expires, &b.name)));
+      output::list(format, &domains)
+    }
+    DomainsCommand::Show { domain } => {
+      let summary = owner(accounts, &domain).await?.get_domain(&domain).await?;
+      output::item(format, &summary)
+    }
+    DomainsCommand::SetNs { domain, nameservers } => {
+      let nameservers: Vec<&str> = nameservers.iter().map(String::as_str).collect();
+      owner(accounts, &domain).await?.set_nameservers(&domain, &nameservers).await?;
+      let text = format!("Set the nameservers of {} to {}", domain, nameservers.join(", "));
+      output::outcome(format, &text, json!({ "domain": domain, "nameservers": nameservers }))
+    }
+    DomainsCommand::Lock { domain } => set_lock(accounts, domain, true, format).await,
+    DomainsCommand::Unlock { domain } => set_lock(accounts, domain, false, format).await,
+  }
+}
+
+async fn set_lock(accounts: Vec<Box<dyn DomainPortfolio>>, domain: String, locked: bool, format: Format) -> Result<()> {
+  owner(accounts, &domain).await?.set_lock(&domain, locked).await?;
+  let text = format!("{} {}", if locked { "Locked" } else { "Unlocked" }, domain);
+  output::outcome(format, &text, json!({ "domain": domain, "locked": locked }))
+}
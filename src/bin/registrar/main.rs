@@ -0,0 +1,116 @@
+//! A command-line tool for domains and DNS records at Porkbun or Name.com.
+//!
+//! ```sh
+//! registrar dns list example.com --type A
+//! registrar dns create example.com --host www --type A --content 192.0.2.1 --ttl 600
+//! registrar --output json dns get example.com 123456
+//! registrar domains list --expiring-within 60d --provider all
+//! registrar --account agency dns list example.net
+//! registrar tui
+//! ```
+//!
+//! Credentials come from the configuration file, with `PORKBUN_API_KEY`,
+//! `PORKBUN_SECRET_KEY`, `NAMECOM_USERNAME` and `NAMECOM_TOKEN` taking precedence.
+//!
+//! Requires the `cli` feature. The `tui` subcommand also requires the `tui` feature.
+
+mod dns;
+mod domains;
+mod output;
+#[cfg(feature = "tui")]
+mod tui;
+
+use clap::{Parser, Subcommand};
+use output::Format;
+use registrar::config::Config;
+use registrar::{Error, Provider, Result};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+/// The registrars a command applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderChoice {
+  One(Provider),
+  /// Every registrar with credentials. Only `domains` commands accept it.
+  All,
+}
+
+impl FromStr for ProviderChoice {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    if s.eq_ignore_ascii_case("all") {
+      return Ok(ProviderChoice::All);
+    }
+    Provider::from_str(s).map(ProviderChoice::One)
+  }
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "registrar", version, about = "Manages domains and DNS records at Porkbun or Name.com")]
+struct Args {
+  /// The registrar to use, if several are configured. `domains` commands also accept `all`.
+  #[arg(long, global = true, env = "REGISTRAR_PROVIDER")]
+  provider: Option<ProviderChoice>,
+
+  /// The configured account to use, if several are configured.
+  #[arg(long, global = true, env = "REGISTRAR_ACCOUNT")]
+  account: Option<String>,
+
+  /// The configuration file (default: ~/.config/registrar/config.yaml).
+  #[arg(long, global = true, env = "REGISTRAR_CONFIG")]
+  config: Option<PathBuf>,
+
+  /// How to print results.
+  #[arg(long, short, global = true, value_enum, default_value_t = Format::Table)]
+  output: Format,
+
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+  /// Lists and changes DNS records.
+  #[command(subcommand)]
+  Dns(dns::DnsCommand),
+  /// Lists domains and changes their registration settings.
+  #[command(subcommand)]
+  Domains(domains::DomainsCommand),
+  /// Browses accounts and domains and edits zones interactively.
+  #[cfg(feature = "tui")]
+  Tui,
+}
+
+async fn run(args: Args) -> Result<()> {
+  let registry = Config::load_with_env(args.config.as_deref())?.registry()?;
+  let account = args.account.as_deref();
+  match args.command {
+    Command::Dns(command) => {
+      let provider = match args.provider {
+        Some(ProviderChoice::All) => {
+          return Err(Error::InvalidInput("`--provider all` only applies to `domains` commands".to_string()));
+        }
+        Some(ProviderChoice::One(provider)) => Some(provider),
+        None => None,
+      };
+      let records = registry.dns_records(registry.select(account, provider)?)?;
+      dns::run(records.as_ref(), command, args.output).await
+    }
+    Command::Domains(command) => domains::run(&registry, account, args.provider, command, args.output).await,
+    #[cfg(feature = "tui")]
+    Command::Tui => tui::run(&registry).await,
+  }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+  match run(Args::parse()).await {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("registrar: {}", e);
+      ExitCode::FAILURE
+    }
+  }
+}
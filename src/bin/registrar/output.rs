@@ -0,0 +1,137 @@
+//! Prints results as a table, JSON or YAML.
+
+use clap::ValueEnum;
+use chrono::Utc;
+use registrar::portfolio::DomainSummary;
+use registrar::records::Record;
+use registrar::{Error, Result};
+use serde::Serialize;
+
+/// An output format.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+  /// Aligned columns for reading.
+  Table,
+  /// Pretty-printed JSON.
+  Json,
+  /// YAML.
+  Yaml,
+}
+
+/// A value that can be printed as a table row.
+pub trait Tabular {
+  /// The column headings.
+  fn headers() -> &'static [&'static str];
+
+  /// The cells of this value's row.
+  fn row(&self) -> Vec<String>;
+}
+
+impl Tabular for Record {
+  fn headers() -> &'static [&'static str] {
+    &["ID", "HOST", "TYPE", "CONTENT", "TTL", "PRIORITY"]
+  }
+
+  fn row(&self) -> Vec<String> {
+    vec![
+      self.id.clone().unwrap_or_default(),
+      if self.host.is_empty() { "@".to_string() } else { self.host.clone() },
+      self.record_type.clone(),
+      self.content.clone(),
+      optional(self.ttl),
+      optional(self.priority),
+    ]
+  }
+}
+
+impl Tabular for DomainSummary {
+  fn headers() -> &'static [&'static str] {
+    &["PROVIDER", "DOMAIN", "EXPIRES", "DAYS LEFT", "AUTO-RENEW", "LOCKED", "NAMESERVERS"]
+  }
+
+  fn row(&self) -> Vec<String> {
+    vec![
+      self.provider.to_string(),
+      self.name.clone(),
+      optional(self.expires.map(|expires| expires.format("%Y-%m-%d"))),
+      optional(self.expires.map(|expires| (expires - Utc::now()).num_days())),
+      yes_no(self.auto_renew),
+      yes_no(self.locked),
+      self.nameservers.as_ref().map(|nameservers| nameservers.join(", ")).unwrap_or_default(),
+    ]
+  }
+}
+
+fn yes_no(value: bool) -> String {
+  if value { "yes" } else { "no" }.to_string()
+}
+
+/// Formats an optional cell, leaving it blank when absent.
+pub fn optional<T: ToString>(value: Option<T>) -> String {
+  value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Prints a list of values.
+pub fn list<T: Serialize + Tabular>(format: Format, items: &[T]) -> Result<()> {
+  match format {
+    Format::Table => {
+      print!("{}", table(T::headers(), items.iter().map(Tabular::row).collect()));
+      Ok(())
+    }
+    _ => structured(format, &items),
+  }
+}
+
+/// Prints a single value.
+pub fn item<T: Serialize + Tabular>(format: Format, item: &T) -> Result<()> {
+  match format {
+    Format::Table => {
+      print!("{}", table(T::headers(), vec![item.row()]));
+      Ok(())
+    }
+    _ => structured(format, item),
+  }
+}
+
+/// Prints the outcome of a change: `text` for tables, `value` otherwise.
+pub fn outcome(format: Format, text: &str, value: serde_json::Value) -> Result<()> {
+  match format {
+    Format::Table => {
+      println!("{}", text);
+      Ok(())
+    }
+    _ => structured(format, &value),
+  }
+}
+
+fn structured<T: Serialize + ?Sized>(format: Format, value: &T) -> Result<()> {
+  let text = match format {
+    Format::Yaml => {
+      serde_yaml::to_string(value).map_err(|e| Error::InvalidInput(format!("Cannot format output: {}", e)))?
+    }
+    _ => serde_json::to_string_pretty(value)? + "\n",
+  };
+  print!("{}", text);
+  Ok(())
+}
+
+/// Lays out rows in columns as wide as their widest cell.
+fn table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+  let mut widths: Vec<usize> = headers.iter().map(|header| header.chars().count()).collect();
+  for row in &rows {
+    for (width, cell) in widths.iter_mut().zip(row) {
+      *width = (*width).max(cell.chars().count());
+    }
+  }
+  let header: Vec<String> = headers.iter().map(|header| header.to_string()).collect();
+  let mut text = String::new();
+  for row in std::iter::once(&header).chain(&rows) {
+    let mut line = String::new();
+    for (cell, width) in row.iter().zip(&widths) {
+      line.push_str(&format!("{:<width$}  ", cell, width = width));
+    }
+    text.push_str(line.trim_end());
+    text.push('\n');
+  }
+  text
+}
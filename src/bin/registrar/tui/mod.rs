@@ -0,0 +1,255 @@
+//! The interactive terminal interface: `registrar tui`.
+//!
+//! Lists the configured accounts and their domains, and opens a zone as an
+//! editable table. Edits are validated with [`Record::validate`] as they are
+//! made, and nothing is sent to the registrar until the pending changes have
+//! been reviewed and confirmed.
+
+mod view;
+mod zone;
+
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::widgets::{ListState, TableState};
+use registrar::config::AccountRegistry;
+use registrar::portfolio::{DomainPortfolio, DomainSummary};
+use registrar::records::{DnsRecords, Record};
+use registrar::{Error, Result};
+use std::sync::Arc;
+use zone::{Change, FIELDS, ZoneEditor};
+
+/// A registrar account with credentials in the configuration.
+struct Account {
+  name: String,
+  portfolio: Arc<dyn DomainPortfolio>,
+  records: Arc<dyn DnsRecords>,
+}
+
+enum Screen {
+  Accounts(ListState),
+  Domains { account: usize, domains: Vec<DomainSummary>, state: TableState },
+  Zone(Box<ZoneEditor>),
+}
+
+struct App {
+  accounts: Vec<Account>,
+  screen: Screen,
+  /// A message shown in place of the key help until the next key press.
+  status: Option<String>,
+  /// Set when leaving a zone with pending changes has been asked for once.
+  confirm_discard: bool,
+  quit: bool,
+}
+
+/// Runs the interface until the user quits.
+pub async fn run(registry: &AccountRegistry) -> Result<()> {
+  let accounts = registry
+    .names()
+    .map(|name| {
+      Ok(Account {
+        name: name.to_string(),
+        portfolio: registry.portfolio(name)?.into(),
+        records: registry.dns_records(name)?.into(),
+      })
+    })
+    .collect::<Result<Vec<_>>>()?;
+  if accounts.is_empty() {
+    return Err(Error::InvalidInput("No registrar credentials are configured".to_string()));
+  }
+
+  let mut app = App {
+    accounts,
+    screen: Screen::Accounts(ListState::default().with_selected(Some(0))),
+    status: None,
+    confirm_discard: false,
+    quit: false,
+  };
+  let mut terminal = ratatui::init();
+  let result = app.run(&mut terminal).await;
+  ratatui::restore();
+  result
+}
+
+impl App {
+  async fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+    while !self.quit {
+      self.draw(terminal)?;
+      if let Event::Key(key) = event::read()?
+        && key.kind == KeyEventKind::Press
+      {
+        self.status = None;
+        self.handle_key(terminal, key).await?;
+      }
+    }
+    Ok(())
+  }
+
+  fn draw(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+    terminal.draw(|frame| view::draw(frame, self))?;
+    Ok(())
+  }
+
+  /// Shows `message` while a registrar call is in progress.
+  fn loading(&mut self, terminal: &mut DefaultTerminal, message: String) -> Result<()> {
+    self.status = Some(message);
+    self.draw(terminal)
+  }
+
+  async fn handle_key(&mut self, terminal: &mut DefaultTerminal, key: KeyEvent) -> Result<()> {
+    let leaving_zone = matches!(&self.screen, Screen::Zone(editor) if editor.form.is_none() && !editor.review)
+      && matches!(key.code, KeyCode::Esc | KeyCode::Char('q'));
+    if !leaving_zone {
+      self.confirm_discard = false;
+    }
+    match &mut self.screen {
+      Screen::Accounts(state) => match key.code {
+        KeyCode::Up | KeyCode::Char('k') => state.select_previous(),
+        KeyCode::Down | KeyCode::Char('j') => state.select_next(),
+        KeyCode::Enter => {
+          let account = state.selected().unwrap_or(0).min(self.accounts.len() - 1);
+          self.open_account(terminal, account).await?;
+        }
+        KeyCode::Esc | KeyCode::Char('q') => self.quit = true,
+        _ => {}
+      },
+      Screen::Domains { account, domains, state } => match key.code {
+        KeyCode::Up | KeyCode::Char('k') => state.select_previous(),
+        KeyCode::Down | KeyCode::Char('j') => state.select_next(),
+        KeyCode::Enter => {
+          if let Some(domain) = state.selected().and_then(|index| domains.get(index)) {
+            let (account, domain) = (*account, domain.name.clone());
+            self.open_zone(terminal, account, domain).await?;
+          }
+        }
+        KeyCode::Char('r') => {
+          let account = *account;
+          self.open_account(terminal, account).await?;
+        }
+        KeyCode::Esc => {
+          let selected = *account;
+          self.screen = Screen::Accounts(ListState::default().with_selected(Some(selected)));
+        }
+        KeyCode::Char('q') => self.quit = true,
+        _ => {}
+      },
+      Screen::Zone(editor) if editor.form.is_some() => handle_form_key(editor, key),
+      Screen::Zone(editor) if editor.review => match key.code {
+        KeyCode::Char('y') => self.apply(terminal).await?,
+        KeyCode::Char('n') | KeyCode::Esc => editor.review = false,
+        _ => {}
+      },
+      Screen::Zone(editor) => match key.code {
+        KeyCode::Up | KeyCode::Char('k') => editor.move_selection(-1),
+        KeyCode::Down | KeyCode::Char('j') => editor.move_selection(1),
+        KeyCode::Enter | KeyCode::Char('e') => editor.edit_selected(),
+        KeyCode::Char('a') => editor.add(),
+        KeyCode::Char('d') => editor.toggle_delete(),
+        KeyCode::Char('u') => editor.revert_selected(),
+        KeyCode::Char('w') if editor.has_changes() => editor.review = true,
+        KeyCode::Char('w') => self.status = Some("There are no changes to review".to_string()),
+        KeyCode::Char('r') if editor.has_changes() => {
+          self.status = Some("Review or undo the pending changes before reloading".to_string())
+        }
+        KeyCode::Char('r') => {
+          let (account, domain) = (editor.account, editor.domain.clone());
+          self.open_zone(terminal, account, domain).await?;
+        }
+        KeyCode::Esc | KeyCode::Char('q') if editor.has_changes() && !self.confirm_discard => {
+          self.confirm_discard = true;
+          self.status = Some("There are unsaved changes: press w to review them, or Esc again to discard".to_string());
+        }
+        KeyCode::Esc | KeyCode::Char('q') => {
+          let account = editor.account;
+          self.confirm_discard = false;
+          self.open_account(terminal, account).await?;
+        }
+        _ => {}
+      },
+    }
+    Ok(())
+  }
+
+  async fn open_account(&mut self, terminal: &mut DefaultTerminal, account: usize) -> Result<()> {
+    let name = self.accounts[account].name.clone();
+    self.loading(terminal, format!("Loading the domains in {}...", name))?;
+    match self.accounts[account].portfolio.list_domains().await {
+      Ok(mut domains) => {
+        domains.sort_by(|a, b| a.name.cmp(&b.name));
+        self.status = None;
+        let state = TableState::default().with_selected(Some(0));
+        self.screen = Screen::Domains { account, domains, state };
+      }
+      Err(e) => self.status = Some(format!("Cannot list the domains in {}: {}", name, e)),
+    }
+    Ok(())
+  }
+
+  async fn open_zone(&mut self, terminal: &mut DefaultTerminal, account: usize, domain: String) -> Result<()> {
+    self.loading(terminal, format!("Loading the records of {}...", domain))?;
+    match self.accounts[account].records.list_records(&domain).await {
+      Ok(records) => {
+        self.status = None;
+        self.screen = Screen::Zone(Box::new(ZoneEditor::new(account, domain, records)));
+      }
+      Err(e) => self.status = Some(format!("Cannot list the records of {}: {}", domain, e)),
+    }
+    Ok(())
+  }
+
+  /// Pushes the reviewed changes, stopping at the first failure, then reloads the zone.
+  async fn apply(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+    let Screen::Zone(editor) = &self.screen else { return Ok(()) };
+    let (account, domain, changes) = (editor.account, editor.domain.clone(), editor.changes());
+    self.loading(terminal, format!("Applying {} changes to {}...", changes.len(), domain))?;
+    let records = self.accounts[account].records.clone();
+    let mut applied = 0;
+    let mut failure = None;
+    for change in &changes {
+      let result = match change {
+        Change::Delete(record) => match record.id.as_deref() {
+          Some(id) => records.delete_record(&domain, id).await,
+          None => Err(Error::InvalidInput(format!("The record {} has no ID", describe(record)))),
+        },
+        Change::Update { after, .. } => records.update_record(&domain, after).await,
+        Change::Create(record) => records.create_record(&domain, record).await.map(|_| ()),
+      };
+      if let Err(e) = result {
+        failure = Some(e);
+        break;
+      }
+      applied += 1;
+    }
+    self.open_zone(terminal, account, domain).await?;
+    self.status = Some(match failure {
+      None => format!("Applied {} changes", applied),
+      Some(e) => format!("Applied {} of {} changes; then: {}", applied, changes.len(), e),
+    });
+    Ok(())
+  }
+}
+
+fn handle_form_key(editor: &mut ZoneEditor, key: KeyEvent) {
+  let Some(form) = &mut editor.form else { return };
+  match key.code {
+    KeyCode::Esc => editor.form = None,
+    KeyCode::Enter => editor.submit_form(),
+    KeyCode::Tab | KeyCode::Down => form.focus = (form.focus + 1) % FIELDS.len(),
+    KeyCode::BackTab | KeyCode::Up => form.focus = (form.focus + FIELDS.len() - 1) % FIELDS.len(),
+    KeyCode::Backspace => {
+      form.fields[form.focus].pop();
+    }
+    KeyCode::Char(c) => {
+      form.fields[form.focus].push(c);
+      form.error = None;
+    }
+    _ => {}
+  }
+}
+
+/// A record on one line, as in a zone file.
+fn describe(record: &Record) -> String {
+  let host = if record.host.is_empty() { "@" } else { record.host.as_str() };
+  let ttl = record.ttl.map(|ttl| format!(" {}", ttl)).unwrap_or_default();
+  let priority = record.priority.map(|priority| format!("{} ", priority)).unwrap_or_default();
+  format!("{}{} {} {}{}", host, ttl, record.record_type, priority, record.content)
+}
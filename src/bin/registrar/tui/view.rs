@@ -0,0 +1,127 @@
+//! Draws the interface.
+
+use super::zone::{Change, FIELDS, RowStatus, ZoneEditor};
+use super::{App, Screen, describe};
+use crate::output::Tabular;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Clear, List, Paragraph, Row, Table, Wrap};
+use registrar::portfolio::DomainSummary;
+use registrar::records::Record;
+
+const SELECTED: Style = Style::new().add_modifier(Modifier::REVERSED);
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+  let [title, body, footer] =
+    Layout::vertical([Constraint::Length(1), Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+
+  let mut breadcrumb = vec!["registrar".bold()];
+  let (account, domain) = match &app.screen {
+    Screen::Accounts(_) => (None, None),
+    Screen::Domains { account, .. } => (Some(*account), None),
+    Screen::Zone(editor) => (Some(editor.account), Some(editor.domain.as_str())),
+  };
+  if let Some(account) = account {
+    breadcrumb.push(format!(" > {}", app.accounts[account].name).into());
+  }
+  if let Some(domain) = domain {
+    breadcrumb.push(format!(" > {}", domain).into());
+  }
+  frame.render_widget(Line::from(breadcrumb), title);
+
+  let help = match &app.screen {
+    Screen::Accounts(_) => "Enter open  q quit",
+    Screen::Domains { .. } => "Enter edit zone  r reload  Esc back  q quit",
+    Screen::Zone(editor) if editor.form.is_some() => "Tab next field  Enter save  Esc cancel",
+    Screen::Zone(editor) if editor.review => "y apply  n back",
+    Screen::Zone(_) => "e edit  a add  d delete/restore  u undo  w review and apply  r reload  Esc back",
+  };
+  let footer_line = match &app.status {
+    Some(status) => Line::from(status.as_str().yellow()),
+    None => Line::from(help.dark_gray()),
+  };
+  frame.render_widget(footer_line, footer);
+
+  let accounts: Vec<&str> = app.accounts.iter().map(|account| account.name.as_str()).collect();
+  match &mut app.screen {
+    Screen::Accounts(state) => {
+      let list = List::new(accounts).block(Block::bordered().title(" Accounts ")).highlight_style(SELECTED);
+      frame.render_stateful_widget(list, body, state);
+    }
+    Screen::Domains { domains, state, .. } => {
+      let table = tabular(DomainSummary::headers(), domains.iter().map(|domain| Row::new(domain.row())))
+        .block(Block::bordered().title(format!(" Domains ({}) ", domains.len())));
+      frame.render_stateful_widget(table, body, state);
+    }
+    Screen::Zone(editor) => draw_zone(frame, body, editor),
+  }
+}
+
+fn draw_zone(frame: &mut Frame, area: Rect, editor: &mut ZoneEditor) {
+  let rows = editor.rows.iter().map(|row| {
+    let (marker, style) = match row.status() {
+      RowStatus::Unchanged => (" ", Style::new()),
+      RowStatus::Added => ("+", Style::new().fg(Color::Green)),
+      RowStatus::Modified => ("~", Style::new().fg(Color::Yellow)),
+      RowStatus::Deleted => ("-", Style::new().fg(Color::Red).add_modifier(Modifier::CROSSED_OUT)),
+    };
+    let cells = std::iter::once(marker.to_string()).chain(row.record().row()).map(Cell::from);
+    Row::new(cells).style(style)
+  });
+  let headers: Vec<&str> = std::iter::once("").chain(Record::headers().iter().copied()).collect();
+  let table = tabular(&headers, rows).block(Block::bordered().title(format!(" {} ", editor.domain)));
+  frame.render_stateful_widget(table, area, &mut editor.state);
+
+  if let Some(form) = &editor.form {
+    let title = if form.row.is_some() { " Edit record " } else { " New record " };
+    let mut lines: Vec<Line> = FIELDS
+      .iter()
+      .zip(&form.fields)
+      .enumerate()
+      .map(|(index, (label, value))| {
+        let value = if index == form.focus { Span::styled(format!("{}_", value), SELECTED) } else { value.into() };
+        Line::from(vec![format!("{:>9}: ", label).into(), value])
+      })
+      .collect();
+    if let Some(error) = &form.error {
+      lines.push(Line::default());
+      lines.push(Line::from(error.as_str().red()));
+    }
+    let area = popup(area, 70, lines.len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(Block::bordered().title(title)), area);
+  } else if editor.review {
+    let mut lines = Vec::new();
+    for change in editor.changes() {
+      match change {
+        Change::Delete(record) => lines.push(Line::from(format!("- {}", describe(&record)).red())),
+        Change::Update { before, after } => {
+          lines.push(Line::from(format!("- {}", describe(&before)).red()));
+          lines.push(Line::from(format!("+ {}", describe(&after)).green()));
+        }
+        Change::Create(record) => lines.push(Line::from(format!("+ {}", describe(&record)).green())),
+      }
+    }
+    let area = popup(area, 90, lines.len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    let title = format!(" Apply these changes to {}? ", editor.domain);
+    frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(title)), area);
+  }
+}
+
+/// A table whose columns fit their headers and contents.
+fn tabular<'a>(headers: &[&'a str], rows: impl IntoIterator<Item = Row<'a>>) -> Table<'a> {
+  let widths = headers.iter().map(|header| if header.is_empty() { Constraint::Length(1) } else { Constraint::Fill(1) });
+  Table::new(rows, widths.collect::<Vec<_>>())
+    .header(Row::new(headers.iter().copied()).bold())
+    .row_highlight_style(SELECTED)
+}
+
+/// A centered area at most `width` columns wide and `height` rows high.
+fn popup(area: Rect, width: u16, height: u16) -> Rect {
+  let width = width.min(area.width);
+  let height = height.min(area.height);
+  Rect::new(area.x + (area.width - width) / 2, area.y + (area.height - height) / 2, width, height)
+}
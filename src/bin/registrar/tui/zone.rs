@@ -0,0 +1,213 @@
+//! The editable copy of a zone and the changes made to it.
+
+use ratatui::widgets::TableState;
+use registrar::records::Record;
+use registrar::{Error, Result};
+
+/// The labels of the record form's fields, in order.
+pub const FIELDS: [&str; 5] = ["Host", "Type", "Content", "TTL", "Priority"];
+
+/// A record as listed by the registrar and as edited.
+pub struct Row {
+  /// `None` for a record added in the editor.
+  pub original: Option<Record>,
+  /// `None` for a record marked for deletion.
+  pub current: Option<Record>,
+}
+
+/// How a row differs from the registrar's copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowStatus {
+  Unchanged,
+  Added,
+  Modified,
+  Deleted,
+}
+
+impl Row {
+  pub fn status(&self) -> RowStatus {
+    match (&self.original, &self.current) {
+      (None, _) => RowStatus::Added,
+      (Some(_), None) => RowStatus::Deleted,
+      (Some(original), Some(current)) if original == current => RowStatus::Unchanged,
+      (Some(_), Some(_)) => RowStatus::Modified,
+    }
+  }
+
+  /// The record to display: the edited one, or the original for deleted rows.
+  pub fn record(&self) -> &Record {
+    self.current.as_ref().or(self.original.as_ref()).expect("a row has at least one record")
+  }
+}
+
+/// A change to push to the registrar.
+pub enum Change {
+  Delete(Record),
+  Update { before: Record, after: Record },
+  Create(Record),
+}
+
+/// The record form, for a new record or an existing row.
+pub struct Form {
+  /// The row being edited, or `None` for a new record.
+  pub row: Option<usize>,
+  pub fields: [String; 5],
+  pub focus: usize,
+  pub error: Option<String>,
+}
+
+impl Form {
+  fn new(row: Option<usize>, record: Option<&Record>) -> Self {
+    let fields = match record {
+      Some(record) => [
+        if record.host.is_empty() { "@".to_string() } else { record.host.clone() },
+        record.record_type.clone(),
+        record.content.clone(),
+        record.ttl.map(|ttl| ttl.to_string()).unwrap_or_default(),
+        record.priority.map(|priority| priority.to_string()).unwrap_or_default(),
+      ],
+      None => Default::default(),
+    };
+    Self { row, fields, focus: 0, error: None }
+  }
+
+  /// Builds and validates the record described by the form.
+  fn to_record(&self, id: Option<String>) -> Result<Record> {
+    let [host, record_type, content, ttl, priority] = &self.fields;
+    let number = |name: &str, value: &str| -> Result<Option<u32>> {
+      match value.trim() {
+        "" => Ok(None),
+        value => value.parse().map(Some).map_err(|_| Error::InvalidInput(format!("{} must be a number", name))),
+      }
+    };
+    let host = host.trim();
+    let mut record = Record::new(if host == "@" { "" } else { host }, record_type.trim(), content.trim());
+    record.id = id;
+    record.ttl = number("TTL", ttl)?;
+    record.priority = number("Priority", priority)?;
+    record.validate()?;
+    Ok(record)
+  }
+}
+
+/// A zone being edited.
+pub struct ZoneEditor {
+  pub account: usize,
+  pub domain: String,
+  pub rows: Vec<Row>,
+  pub state: TableState,
+  pub form: Option<Form>,
+  /// Whether the change review is shown.
+  pub review: bool,
+}
+
+impl ZoneEditor {
+  pub fn new(account: usize, domain: String, mut records: Vec<Record>) -> Self {
+    records.sort_by(|a, b| (&a.host, &a.record_type, &a.content).cmp(&(&b.host, &b.record_type, &b.content)));
+    let rows = records
+      .into_iter()
+      .map(|record| Row { original: Some(record.clone()), current: Some(record) })
+      .collect();
+    Self {
+      account,
+      domain,
+      rows,
+      state: TableState::default().with_selected(Some(0)),
+      form: None,
+      review: false,
+    }
+  }
+
+  /// The changes to push, deletions first so that replacements do not conflict.
+  pub fn changes(&self) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for row in &self.rows {
+      match (row.status(), &row.original, &row.current) {
+        (RowStatus::Deleted, Some(original), _) => changes.push(Change::Delete(original.clone())),
+        (RowStatus::Modified, Some(before), Some(after)) => {
+          changes.push(Change::Update { before: before.clone(), after: after.clone() })
+        }
+        _ => {}
+      }
+    }
+    let created = self.rows.iter().filter(|row| row.original.is_none()).filter_map(|row| row.current.clone());
+    changes.sort_by_key(|change| matches!(change, Change::Update { .. }));
+    changes.extend(created.map(Change::Create));
+    changes
+  }
+
+  pub fn has_changes(&self) -> bool {
+    self.rows.iter().any(|row| row.status() != RowStatus::Unchanged)
+  }
+
+  pub fn move_selection(&mut self, delta: isize) {
+    if self.rows.is_empty() {
+      return;
+    }
+    let selected = self.state.selected().unwrap_or(0) as isize + delta;
+    self.state.select(Some(selected.clamp(0, self.rows.len() as isize - 1) as usize));
+  }
+
+  fn selected(&self) -> Option<usize> {
+    self.state.selected().filter(|index| *index < self.rows.len())
+  }
+
+  /// Opens the form on the selected row, unless it is marked for deletion.
+  pub fn edit_selected(&mut self) {
+    if let Some(index) = self.selected()
+      && let Some(record) = &self.rows[index].current
+    {
+      self.form = Some(Form::new(Some(index), Some(record)));
+    }
+  }
+
+  pub fn add(&mut self) {
+    self.form = Some(Form::new(None, None));
+  }
+
+  /// Marks the selected row for deletion, or restores it. New rows are removed outright.
+  pub fn toggle_delete(&mut self) {
+    let Some(index) = self.selected() else { return };
+    let row = &mut self.rows[index];
+    match (&row.original, &row.current) {
+      (None, _) => {
+        self.rows.remove(index);
+        self.move_selection(0);
+      }
+      (Some(_), Some(_)) => row.current = None,
+      (Some(original), None) => row.current = Some(original.clone()),
+    }
+  }
+
+  /// Discards the edits to the selected row.
+  pub fn revert_selected(&mut self) {
+    let Some(index) = self.selected() else { return };
+    match self.rows[index].original.clone() {
+      Some(original) => self.rows[index].current = Some(original),
+      None => {
+        self.rows.remove(index);
+        self.move_selection(0);
+      }
+    }
+  }
+
+  /// Saves the form if its record is valid, keeping it open with the error otherwise.
+  pub fn submit_form(&mut self) {
+    let Some(form) = &mut self.form else { return };
+    let id = form.row.and_then(|index| self.rows[index].original.as_ref()).and_then(|record| record.id.clone());
+    match form.to_record(id) {
+      Ok(record) => {
+        match form.row {
+          Some(index) => self.rows[index].current = Some(record),
+          None => {
+            self.rows.push(Row { original: None, current: Some(record) });
+            self.state.select(Some(self.rows.len() - 1));
+          }
+        }
+        self.form = None;
+      }
+      Err(Error::InvalidInput(reason)) => form.error = Some(reason),
+      Err(e) => form.error = Some(e.to_string()),
+    }
+  }
+}
@@ -0,0 +1,88 @@
+//! # Blocking Clients
+//!
+//! Synchronous versions of the Porkbun and Name.com clients and their scoped
+//! clients, for build scripts and simple tools that have no async runtime.
+//!
+//! Each blocking client wraps the async client and runs its methods to
+//! completion on a private, single-threaded Tokio runtime, so requests,
+//! responses and errors are exactly those of the async API. Listings that the
+//! async API returns as streams are returned as iterators that fetch pages as
+//! they are needed.
+//!
+//! Blocking clients must not be used from within an async runtime: as with
+//! `Runtime::block_on`, doing so panics. Use the async clients there instead.
+//!
+//! To use this module, enable the `blocking` feature along with `porkbun` or
+//! `name-com`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "porkbun")]
+//! # fn main() -> registrar::Result<()> {
+//! use registrar::blocking::Porkbun;
+//!
+//! let client = Porkbun::new("pk1_...".to_string(), "sk1_...".to_string())?;
+//! println!("Your IP is {}", client.ping()?.your_ip);
+//! for record in client.dns(&"example.com".parse()?).retrieve_all_records()? {
+//!   println!("{} {} {}", record.name, record.r#type, record.content);
+//! }
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "porkbun"))]
+//! # fn main() {}
+//! ```
+
+use crate::Result;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use tokio::runtime::{Builder, Runtime};
+
+/// Defines methods that run the async method of the same name, with the same
+/// arguments, on the client's runtime.
+macro_rules! blocking {
+  ($($(#[$attr:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+    $(
+      $(#[$attr])*
+      pub fn $name(&self $(, $arg: $ty)*) -> $crate::Result<$ret> {
+        self.runtime.block_on(self.inner.$name($($arg),*))
+      }
+    )*
+  };
+}
+
+#[cfg(feature = "name-com")]
+pub mod name_com;
+#[cfg(feature = "porkbun")]
+pub mod porkbun;
+
+#[cfg(feature = "name-com")]
+pub use name_com::NameDotCom;
+#[cfg(feature = "porkbun")]
+pub use porkbun::Porkbun;
+
+/// Builds the runtime a blocking client runs its requests on.
+fn runtime() -> Result<Runtime> {
+  Ok(Builder::new_current_thread().enable_all().build()?)
+}
+
+/// An iterator over a listing, fetching each page when the items before it
+/// have been consumed.
+pub struct Iter<'a, T> {
+  runtime: &'a Runtime,
+  stream: BoxStream<'a, Result<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+  fn new(runtime: &'a Runtime, stream: BoxStream<'a, Result<T>>) -> Self {
+    Self { runtime, stream }
+  }
+}
+
+impl<T> Iterator for Iter<'_, T> {
+  type Item = Result<T>;
+
+  fn next(&mut self) -> Option<Result<T>> {
+    self.runtime.block_on(self.stream.next())
+  }
+}
@@ -0,0 +1,278 @@
+//! The blocking Name.com client and its scoped clients.
+
+use super::{Iter, runtime};
+use crate::{DomainName, Result};
+use crate::name_com::dns::types::{DnsRecord, DnsRecordPayload, DnssecCreatePayload, DnssecRecord};
+use crate::name_com::domain::types::{
+  AvailabilityResult, CreateDomainResponse, Domain, PurchasePrivacyRequest, PurchasePrivacyResponse,
+  UpdateDomainPayload,
+};
+use crate::name_com::types::{Hello, Page, PageCursor};
+use crate::name_com::url_forwarding::types::{
+  UrlForwardingCreatePayload, UrlForwardingRecord, UrlForwardingUpdatePayload,
+};
+use crate::name_com::vanity_ns::types::{VanityNameserver, VanityNsCreatePayload, VanityNsUpdatePayload};
+use futures::StreamExt;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// A blocking client for the Name.com Core V1 API.
+///
+/// Mirrors [`crate::name_com::NameDotCom`]. Cloning is cheap: clones share
+/// the HTTP connection pool and the runtime.
+#[derive(Clone)]
+pub struct NameDotCom {
+  inner: crate::name_com::NameDotCom,
+  runtime: Arc<Runtime>,
+}
+
+impl NameDotCom {
+  /// Creates a new blocking Name.com client for the production environment.
+  pub fn new(username: String, token: String) -> Result<Self> {
+    Self::from_client(crate::name_com::NameDotCom::new(username, token))
+  }
+
+  /// Creates a new blocking Name.com client for the development environment.
+  pub fn new_dev(username: String, token: String) -> Result<Self> {
+    Self::from_client(crate::name_com::NameDotCom::new_dev(username, token))
+  }
+
+  /// Creates a new blocking Name.com client for a custom environment.
+  pub fn with_host(host: String, username: String, token: String) -> Result<Self> {
+    Self::from_client(crate::name_com::NameDotCom::with_host(host, username, token))
+  }
+
+  /// Wraps an async client, such as one built from a configuration file.
+  ///
+  /// # Arguments
+  /// * `client` - The async client to run.
+  pub fn from_client(client: crate::name_com::NameDotCom) -> Result<Self> {
+    Ok(Self { inner: client, runtime: Arc::new(runtime()?) })
+  }
+
+  /// Sets how many pages of a listing are fetched at once.
+  ///
+  /// # Arguments
+  /// * `concurrency` - The number of requests in flight; `1` fetches pages one after another.
+  pub fn with_page_concurrency(mut self, concurrency: usize) -> Self {
+    self.inner = self.inner.with_page_concurrency(concurrency);
+    self
+  }
+
+  blocking! {
+    /// A simple endpoint to test connectivity to the Name.com API server.
+    fn hello(&self) -> Hello;
+  }
+
+  pub fn domains(&self) -> DomainsClient<'_> {
+    DomainsClient { inner: self.inner.domains(), runtime: &self.runtime }
+  }
+
+  pub fn dns<'a>(&'a self, domain_name: &'a DomainName) -> DnsClient<'a> {
+    DnsClient { inner: self.inner.dns(domain_name), runtime: &self.runtime }
+  }
+
+  pub fn url_forwarding<'a>(&'a self, domain_name: &'a DomainName) -> UrlForwardingClient<'a> {
+    UrlForwardingClient { inner: self.inner.url_forwarding(domain_name), runtime: &self.runtime }
+  }
+
+  pub fn vanity_ns<'a>(&'a self, domain_name: &'a DomainName) -> VanityNameserverClient<'a> {
+    VanityNameserverClient { inner: self.inner.vanity_ns(domain_name), runtime: &self.runtime }
+  }
+}
+
+/// Blocking access to domain-related functionality not specific to one domain.
+///
+/// Mirrors [`crate::name_com::domain::DomainsClient`].
+pub struct DomainsClient<'a> {
+  inner: crate::name_com::domain::DomainsClient<'a>,
+  runtime: &'a Runtime,
+}
+
+impl<'a> DomainsClient<'a> {
+  blocking! {
+    /// Retrieves a list of all domains in your account.
+    fn list(&self) -> Vec<Domain>;
+    /// Checks the availability of a list of domain names.
+    fn check_availability(&self, domain_names: &[&str]) -> Vec<AvailabilityResult>;
+    /// Registers a new domain.
+    fn create(&self, domain_name: &str) -> CreateDomainResponse;
+  }
+
+  /// Iterates over the domains in your account, fetching pages as they are needed.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn iter(&self, cursor: PageCursor) -> Iter<'a, Domain> {
+    Iter::new(self.runtime, self.inner.stream(cursor).boxed())
+  }
+
+  /// Iterates over the domains in your account a page at a time.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn pages(&self, cursor: PageCursor) -> Iter<'a, Page<Domain>> {
+    Iter::new(self.runtime, self.inner.pages(cursor).boxed())
+  }
+
+  /// Returns a client for operating on a single, specific domain.
+  pub fn domain(&self, domain_name: &'a DomainName) -> DomainClient<'a> {
+    DomainClient { inner: self.inner.domain(domain_name), runtime: self.runtime }
+  }
+}
+
+/// Blocking access to functionality for a specific domain.
+///
+/// Mirrors [`crate::name_com::domain::DomainClient`].
+pub struct DomainClient<'a> {
+  inner: crate::name_com::domain::DomainClient<'a>,
+  runtime: &'a Runtime,
+}
+
+impl DomainClient<'_> {
+  blocking! {
+    /// Retrieves the details for this specific domain.
+    fn get(&self) -> Domain;
+    /// Updates the autorenew, privacy, or lock status for the domain.
+    fn update(&self, payload: UpdateDomainPayload) -> Domain;
+    /// Retrieves the transfer authorization code (EPP code) for a domain.
+    fn get_auth_code(&self) -> String;
+    /// Sets the nameservers for the domain.
+    fn set_nameservers(&self, nameservers: &[&str]) -> Domain;
+    /// Locks the domain at the registry, preventing unauthorized transfers.
+    fn lock(&self) -> Domain;
+    /// Unlocks the domain so that it can be transferred to another registrar.
+    fn unlock(&self) -> Domain;
+    /// Enables automatic renewal for the domain.
+    fn enable_autorenew(&self) -> Domain;
+    /// Disables automatic renewal for the domain.
+    fn disable_autorenew(&self) -> Domain;
+    /// Purchases WHOIS privacy for the domain.
+    fn purchase_privacy(&self, request: PurchasePrivacyRequest<'_>) -> PurchasePrivacyResponse;
+  }
+}
+
+/// Blocking access to DNS and DNSSEC functionality for a specific domain.
+///
+/// Mirrors [`crate::name_com::dns::DnsClient`].
+pub struct DnsClient<'a> {
+  inner: crate::name_com::dns::DnsClient<'a>,
+  runtime: &'a Runtime,
+}
+
+impl<'a> DnsClient<'a> {
+  blocking! {
+    /// Retrieves a list of all DNS records for the domain.
+    fn list_records(&self) -> Vec<DnsRecord>;
+    /// Retrieves a single DNS record by its ID.
+    fn get_record(&self, record_id: i32) -> DnsRecord;
+    /// Creates a new DNS record.
+    fn create_record(&self, payload: DnsRecordPayload<'_>) -> DnsRecord;
+    /// Updates an existing DNS record.
+    fn update_record(&self, record_id: i32, payload: DnsRecordPayload<'_>) -> DnsRecord;
+    /// Deletes a DNS record.
+    fn delete_record(&self, record_id: i32) -> ();
+    /// Lists the DNSSEC records of the domain.
+    fn list_dnssec(&self) -> Vec<DnssecRecord>;
+    /// Retrieves a DNSSEC record by its digest.
+    fn get_dnssec(&self, digest: &str) -> DnssecRecord;
+    /// Creates a DNSSEC record.
+    fn create_dnssec(&self, payload: DnssecCreatePayload<'_>) -> DnssecRecord;
+    /// Deletes a DNSSEC record by its digest.
+    fn delete_dnssec(&self, digest: &str) -> ();
+  }
+
+  /// Iterates over the DNS records of the domain, fetching pages as they are needed.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn iter_records(&self, cursor: PageCursor) -> Iter<'a, DnsRecord> {
+    Iter::new(self.runtime, self.inner.stream_records(cursor).boxed())
+  }
+
+  /// Iterates over the DNS records of the domain a page at a time.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn record_pages(&self, cursor: PageCursor) -> Iter<'a, Page<DnsRecord>> {
+    Iter::new(self.runtime, self.inner.record_pages(cursor).boxed())
+  }
+}
+
+/// Blocking access to URL Forwarding functionality for a specific domain.
+///
+/// Mirrors [`crate::name_com::url_forwarding::UrlForwardingClient`].
+pub struct UrlForwardingClient<'a> {
+  inner: crate::name_com::url_forwarding::UrlForwardingClient<'a>,
+  runtime: &'a Runtime,
+}
+
+impl<'a> UrlForwardingClient<'a> {
+  blocking! {
+    /// Retrieves a list of all URL forwarding records for the domain.
+    fn list(&self) -> Vec<UrlForwardingRecord>;
+    /// Retrieves a single URL forwarding record by its host.
+    fn get(&self, host: &str) -> UrlForwardingRecord;
+    /// Creates a new URL forwarding record.
+    fn create(&self, payload: UrlForwardingCreatePayload<'_>) -> UrlForwardingRecord;
+    /// Updates an existing URL forwarding record.
+    fn update(&self, host: &str, payload: UrlForwardingUpdatePayload<'_>) -> UrlForwardingRecord;
+    /// Deletes a URL forwarding record.
+    fn delete(&self, host: &str) -> ();
+  }
+
+  /// Iterates over the URL forwarding records of the domain, fetching pages as they are needed.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn iter(&self, cursor: PageCursor) -> Iter<'a, UrlForwardingRecord> {
+    Iter::new(self.runtime, self.inner.stream(cursor).boxed())
+  }
+
+  /// Iterates over the URL forwarding records of the domain a page at a time.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn pages(&self, cursor: PageCursor) -> Iter<'a, Page<UrlForwardingRecord>> {
+    Iter::new(self.runtime, self.inner.pages(cursor).boxed())
+  }
+}
+
+/// Blocking access to Vanity Nameserver functionality for a specific domain.
+///
+/// Mirrors [`crate::name_com::vanity_ns::VanityNameserverClient`].
+pub struct VanityNameserverClient<'a> {
+  inner: crate::name_com::vanity_ns::VanityNameserverClient<'a>,
+  runtime: &'a Runtime,
+}
+
+impl<'a> VanityNameserverClient<'a> {
+  blocking! {
+    /// Retrieves a list of all vanity nameservers for the domain.
+    fn list(&self) -> Vec<VanityNameserver>;
+    /// Retrieves a single vanity nameserver by its hostname.
+    fn get(&self, hostname: &str) -> VanityNameserver;
+    /// Creates a new vanity nameserver.
+    fn create(&self, payload: VanityNsCreatePayload<'_>) -> VanityNameserver;
+    /// Updates the IP addresses of an existing vanity nameserver.
+    fn update(&self, hostname: &str, payload: VanityNsUpdatePayload<'_>) -> VanityNameserver;
+    /// Deletes a vanity nameserver.
+    fn delete(&self, hostname: &str) -> ();
+  }
+
+  /// Iterates over the vanity nameservers of the domain, fetching pages as they are needed.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn iter(&self, cursor: PageCursor) -> Iter<'a, VanityNameserver> {
+    Iter::new(self.runtime, self.inner.stream(cursor).boxed())
+  }
+
+  /// Iterates over the vanity nameservers of the domain a page at a time.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn pages(&self, cursor: PageCursor) -> Iter<'a, Page<VanityNameserver>> {
+    Iter::new(self.runtime, self.inner.pages(cursor).boxed())
+  }
+}
@@ -0,0 +1,184 @@
+//! The blocking Porkbun client and its scoped clients.
+
+use super::{Iter, runtime};
+use crate::{DomainName, Result};
+use crate::porkbun::dns::types::{
+  DnsRecord, DnsRecordCreateOptions, DnsRecordCreateResponse, DnsRecordEditOptions, DnssecRecord,
+};
+use crate::porkbun::domain::types::{
+  DomainCheckResponse, DomainInfo, GlueRecordIps, NameserverListResponse, UrlForwardRecord,
+};
+use crate::porkbun::ssl::types::SslBundleResponse;
+use crate::porkbun::types::{PingResponse, PricingResponse, StatusResponse};
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// A blocking client for the Porkbun v3 API.
+///
+/// Mirrors [`crate::porkbun::Porkbun`]. Cloning is cheap: clones share the
+/// HTTP connection pool and the runtime.
+#[derive(Clone)]
+pub struct Porkbun {
+  inner: crate::porkbun::Porkbun,
+  runtime: Arc<Runtime>,
+}
+
+impl Porkbun {
+  /// Creates a new blocking Porkbun client with the provided API and Secret keys.
+  ///
+  /// # Arguments
+  /// * `apikey` - Your Porkbun API key.
+  /// * `secretapikey` - Your Porkbun Secret API key.
+  pub fn new(apikey: String, secretapikey: String) -> Result<Self> {
+    Self::from_client(crate::porkbun::Porkbun::new(apikey, secretapikey))
+  }
+
+  /// Wraps an async client, such as one built from a configuration file.
+  ///
+  /// # Arguments
+  /// * `client` - The async client to run.
+  pub fn from_client(client: crate::porkbun::Porkbun) -> Result<Self> {
+    Ok(Self { inner: client, runtime: Arc::new(runtime()?) })
+  }
+
+  blocking! {
+    /// Pings the Porkbun API to test credentials and returns your public IP address.
+    fn ping(&self) -> PingResponse;
+    /// Pings the Porkbun API over IPv4 only.
+    fn ping_ipv4(&self) -> PingResponse;
+    /// Retrieves the pricing for all TLDs.
+    fn get_pricing(&self) -> PricingResponse;
+    /// Retrieves all domains in your account.
+    fn list_all(&self, include_labels: bool) -> Vec<DomainInfo>;
+  }
+
+  /// Iterates over the domains in your account, fetching each chunk of up to
+  /// 1000 when the domains before it have been consumed.
+  ///
+  /// # Arguments
+  /// * `include_labels` - If true, includes any labels assigned to domains.
+  /// * `start` - The number of domains to skip.
+  pub fn iter_all(&self, include_labels: bool, start: u64) -> Iter<'_, DomainInfo> {
+    Iter::new(&self.runtime, self.inner.stream_all(include_labels, start).boxed())
+  }
+
+  /// Access domain-specific functionality.
+  ///
+  /// # Arguments
+  /// * `domain` - The domain name to operate on (e.g., "example.com").
+  pub fn domain<'a>(&'a self, domain: &'a DomainName) -> Domain<'a> {
+    Domain { inner: self.inner.domain(domain), runtime: &self.runtime }
+  }
+
+  /// Access DNS-specific functionality.
+  ///
+  /// # Arguments
+  /// * `domain` - The domain name whose DNS records you want to manage.
+  pub fn dns<'a>(&'a self, domain: &'a DomainName) -> Dns<'a> {
+    Dns { inner: self.inner.dns(domain), runtime: &self.runtime }
+  }
+
+  /// Access SSL-specific functionality.
+  ///
+  /// # Arguments
+  /// * `domain` - The domain name whose SSL bundle you want to retrieve.
+  pub fn ssl<'a>(&'a self, domain: &'a DomainName) -> Ssl<'a> {
+    Ssl { inner: self.inner.ssl(domain), runtime: &self.runtime }
+  }
+}
+
+/// Blocking access to the Domain functionality of the Porkbun API.
+///
+/// Mirrors [`crate::porkbun::domain::Domain`]. Created via `Porkbun::domain(&name)`.
+pub struct Domain<'a> {
+  inner: crate::porkbun::domain::Domain<'a>,
+  runtime: &'a Runtime,
+}
+
+impl<'a> Domain<'a> {
+  blocking! {
+    /// Updates the nameservers for the domain.
+    fn update_nameservers(&self, nameservers: &[&str]) -> StatusResponse;
+    /// Retrieves the authoritative nameservers for the domain.
+    fn get_nameservers(&self) -> NameserverListResponse;
+    /// Enables or disables auto-renewal for the domain.
+    fn update_auto_renew(&self, enabled: bool) -> StatusResponse;
+    /// Retrieves all domains in your account.
+    fn list_all(&self, include_labels: bool) -> Vec<DomainInfo>;
+    /// Adds a URL forwarding record.
+    fn add_url_forward(&self, options: &UrlForwardRecord) -> StatusResponse;
+    /// Retrieves the URL forwarding records for the domain.
+    fn get_url_forwarding(&self) -> Vec<UrlForwardRecord>;
+    /// Deletes a URL forwarding record by its ID.
+    fn delete_url_forward(&self, record_id: u64) -> StatusResponse;
+    /// Checks the availability and price of the domain.
+    fn check(&self) -> DomainCheckResponse;
+    /// Creates a glue record for a subdomain.
+    fn create_glue_record(&self, subdomain: &str, ips: &[IpAddr]) -> StatusResponse;
+    /// Replaces the addresses of a glue record.
+    fn update_glue_record(&self, subdomain: &str, ips: &[IpAddr]) -> StatusResponse;
+    /// Deletes a glue record.
+    fn delete_glue_record(&self, subdomain: &str) -> StatusResponse;
+    /// Retrieves the glue records for the domain.
+    fn get_glue_records(&self) -> Vec<(String, GlueRecordIps)>;
+  }
+
+  /// Iterates over the domains in your account, fetching each chunk of up to
+  /// 1000 when the domains before it have been consumed.
+  ///
+  /// # Arguments
+  /// * `include_labels` - If true, includes any labels assigned to domains.
+  /// * `start` - The number of domains to skip.
+  pub fn iter_all(&self, include_labels: bool, start: u64) -> Iter<'a, DomainInfo> {
+    Iter::new(self.runtime, self.inner.stream_all(include_labels, start).boxed())
+  }
+}
+
+/// Blocking access to the DNS functionality of the Porkbun API.
+///
+/// Mirrors [`crate::porkbun::dns::Dns`]. Created via `Porkbun::dns(&name)`.
+pub struct Dns<'a> {
+  inner: crate::porkbun::dns::Dns<'a>,
+  runtime: &'a Runtime,
+}
+
+impl Dns<'_> {
+  blocking! {
+    /// Creates a new DNS record.
+    fn create_record(&self, options: DnsRecordCreateOptions<'_>) -> DnsRecordCreateResponse;
+    /// Edits a specific DNS record by its ID.
+    fn edit_record_by_id(&self, record_id: u64, options: DnsRecordEditOptions<'_>) -> StatusResponse;
+    /// Deletes a specific DNS record by its ID.
+    fn delete_record_by_id(&self, record_id: u64) -> StatusResponse;
+    /// Retrieves all DNS records for the domain.
+    fn retrieve_all_records(&self) -> Vec<DnsRecord>;
+    /// Retrieves a single DNS record by its ID, or `None` if there is none.
+    fn retrieve_record_by_id(&self, record_id: u64) -> Option<DnsRecord>;
+    /// Retrieves all records that match a given name and type.
+    fn retrieve_records_by_name_type(&self, record_type: &str, subdomain: &str) -> Vec<DnsRecord>;
+    /// Creates a new DNSSEC record at the registry.
+    fn create_dnssec_record(&self, record: &DnssecRecord) -> StatusResponse;
+    /// Retrieves all DNSSEC records for the domain from the registry.
+    fn get_dnssec_records(&self) -> HashMap<String, DnssecRecord>;
+    /// Deletes a DNSSEC record from the registry by its key tag.
+    fn delete_dnssec_record(&self, key_tag: &str) -> StatusResponse;
+  }
+}
+
+/// Blocking access to the SSL functionality of the Porkbun API.
+///
+/// Mirrors [`crate::porkbun::ssl::Ssl`]. Created via `Porkbun::ssl(&name)`.
+pub struct Ssl<'a> {
+  inner: crate::porkbun::ssl::Ssl<'a>,
+  runtime: &'a Runtime,
+}
+
+impl Ssl<'_> {
+  blocking! {
+    /// Retrieves the SSL certificate bundle for the domain.
+    fn retrieve_bundle(&self) -> SslBundleResponse;
+  }
+}
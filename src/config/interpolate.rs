@@ -0,0 +1,52 @@
+//! Substitutes environment variables into configuration values.
+
+use crate::{Error, Result};
+use serde_json::Value;
+
+/// Replaces `${NAME}` and `${NAME:-default}` in every string within `value`.
+/// `$${` stands for a literal `${`.
+///
+/// # Arguments
+/// * `value` - The parsed configuration.
+/// * `vars` - Returns the value of a variable, or `None` if it is not set.
+pub(crate) fn interpolate(value: &mut Value, vars: &dyn Fn(&str) -> Option<String>) -> Result<()> {
+  match value {
+    Value::String(text) if text.contains('$') => *text = expand(text, vars)?,
+    Value::Array(items) => items.iter_mut().try_for_each(|item| interpolate(item, vars))?,
+    Value::Object(fields) => fields.values_mut().try_for_each(|field| interpolate(field, vars))?,
+    _ => {}
+  }
+  Ok(())
+}
+
+fn expand(text: &str, vars: &dyn Fn(&str) -> Option<String>) -> Result<String> {
+  let mut expanded = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(start) = rest.find('$') {
+    expanded.push_str(&rest[..start]);
+    let after = &rest[start..];
+    if let Some(escaped) = after.strip_prefix("$${") {
+      expanded.push_str("${");
+      rest = escaped;
+    } else if let Some(reference) = after.strip_prefix("${") {
+      let end = reference
+        .find('}')
+        .ok_or_else(|| Error::InvalidInput(format!("Unterminated '${{' in configuration value '{}'", text)))?;
+      let (name, default) = match reference[..end].split_once(":-") {
+        Some((name, default)) => (name, Some(default)),
+        None => (&reference[..end], None),
+      };
+      let value = vars(name).filter(|value| !value.is_empty()).or_else(|| default.map(str::to_string));
+      let value = value.ok_or_else(|| {
+        Error::InvalidInput(format!("Environment variable '{}' used in the configuration is not set", name))
+      })?;
+      expanded.push_str(&value);
+      rest = &reference[end + 1..];
+    } else {
+      expanded.push('$');
+      rest = &after[1..];
+    }
+  }
+  expanded.push_str(rest);
+  Ok(expanded)
+}
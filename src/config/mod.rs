@@ -0,0 +1,347 @@
+//! # Configuration Files
+//!
+//! Loads registrar credentials from a YAML file so that command-line tools and
+//! long-running services do not need credentials in their arguments.
+//!
+//! ```yaml
+//! # ~/.config/registrar/config.yaml
+//! provider: porkbun        # Optional; used when several registrars are configured.
+//! porkbun:
+//!   apikey: "pk1_..."
+//!   secretapikey: "sk1_..."
+//! name_com:
+//!   username: "your-username"
+//!   token: "your-api-token"
+//!   host: "https://api.dev.name.com"   # Optional; defaults to production.
+//! ```
+//!
+//! Several accounts at the same registrar are declared under `accounts`, and
+//! handed out by name through an [`AccountRegistry`]:
+//!
+//! ```yaml
+//! default_account: personal
+//! accounts:
+//!   personal:
+//!     provider: porkbun
+//!     apikey: "${PORKBUN_PERSONAL_KEY}"
+//!     secretapikey: "${PORKBUN_PERSONAL_SECRET}"
+//!   agency:
+//!     provider: name-com
+//!     username: agency
+//!     token: "${NAMECOM_AGENCY_TOKEN}"
+//!     host: "${NAMECOM_HOST:-https://api.name.com}"
+//! ```
+//!
+//! `${NAME}` in any value is replaced by the environment variable `NAME`, and
+//! `${NAME:-default}` falls back to `default` when it is unset. Write `$${` for
+//! a literal `${`. With the `config-toml` feature, files ending in `.toml` are
+//! read as TOML with the same structure.
+//!
+//! Credentials can also come from the environment; see [`Config::with_env`].
+//!
+//! To use this module, you must enable the `config` feature in your `Cargo.toml`.
+
+use crate::{Error, Provider, Result};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+mod interpolate;
+mod registry;
+
+pub use registry::AccountRegistry;
+
+/// The environment variable naming an explicit configuration file.
+pub const CONFIG_ENV: &str = "REGISTRAR_CONFIG";
+
+/// The environment variable holding a Porkbun API key.
+pub const PORKBUN_API_KEY_ENV: &str = "PORKBUN_API_KEY";
+/// The environment variable holding a Porkbun secret API key.
+pub const PORKBUN_SECRET_KEY_ENV: &str = "PORKBUN_SECRET_KEY";
+/// The environment variable holding a Name.com username.
+pub const NAMECOM_USERNAME_ENV: &str = "NAMECOM_USERNAME";
+/// The environment variable holding a Name.com API token.
+pub const NAMECOM_TOKEN_ENV: &str = "NAMECOM_TOKEN";
+/// The environment variable holding the Name.com API host.
+pub const NAMECOM_HOST_ENV: &str = "NAMECOM_HOST";
+
+/// Porkbun API credentials.
+#[derive(Deserialize, Debug, Clone)]
+pub struct PorkbunCredentials {
+  pub apikey: String,
+  pub secretapikey: String,
+}
+
+impl PorkbunCredentials {
+  /// Builds a client with these credentials.
+  #[cfg(feature = "porkbun")]
+  pub fn client(&self) -> crate::porkbun::Porkbun {
+    crate::porkbun::Porkbun::new(self.apikey.clone(), self.secretapikey.clone())
+  }
+}
+
+/// Name.com API credentials.
+#[derive(Deserialize, Debug, Clone)]
+pub struct NameComCredentials {
+  pub username: String,
+  pub token: String,
+  /// The API host. Defaults to `NameDotCom::PRODUCTION_HOST`.
+  #[serde(default)]
+  pub host: Option<String>,
+}
+
+impl NameComCredentials {
+  /// Builds a client with these credentials.
+  #[cfg(feature = "name-com")]
+  pub fn client(&self) -> crate::name_com::NameDotCom {
+    use crate::name_com::NameDotCom;
+    let host = self.host.as_deref().unwrap_or(NameDotCom::PRODUCTION_HOST);
+    NameDotCom::with_host(host.trim_end_matches('/').to_string(), self.username.clone(), self.token.clone())
+  }
+}
+
+/// The credentials of a named account, tagged with its registrar.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "provider", rename_all = "kebab-case")]
+pub enum AccountConfig {
+  Porkbun(PorkbunCredentials),
+  #[serde(alias = "name_com")]
+  NameCom(NameComCredentials),
+}
+
+impl AccountConfig {
+  /// The registrar holding the account.
+  pub fn provider(&self) -> Provider {
+    match self {
+      AccountConfig::Porkbun(_) => Provider::Porkbun,
+      AccountConfig::NameCom(_) => Provider::NameCom,
+    }
+  }
+}
+
+/// The syntax of a configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+  Yaml,
+  /// Requires the `config-toml` feature.
+  Toml,
+}
+
+/// The contents of a configuration file.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct Config {
+  /// The registrar to use when more than one is configured.
+  #[serde(default)]
+  pub provider: Option<Provider>,
+  #[serde(default)]
+  pub porkbun: Option<PorkbunCredentials>,
+  #[serde(default)]
+  pub name_com: Option<NameComCredentials>,
+  /// Named accounts, for several accounts at the same registrar.
+  #[serde(default)]
+  pub accounts: BTreeMap<String, AccountConfig>,
+  /// The account to use when none is named.
+  #[serde(default)]
+  pub default_account: Option<String>,
+}
+
+impl Config {
+  /// Parses a configuration from YAML text, substituting environment variables.
+  pub fn from_yaml(yaml: &str) -> Result<Self> {
+    Self::parse(yaml, ConfigFormat::Yaml, &|name| std::env::var(name).ok())
+  }
+
+  /// Parses a configuration from TOML text, substituting environment variables.
+  #[cfg(feature = "config-toml")]
+  pub fn from_toml(toml: &str) -> Result<Self> {
+    Self::parse(toml, ConfigFormat::Toml, &|name| std::env::var(name).ok())
+  }
+
+  /// Parses a configuration, substituting `${NAME}` references through `vars`.
+  ///
+  /// # Arguments
+  /// * `text` - The configuration file's contents.
+  /// * `format` - The syntax of `text`.
+  /// * `vars` - Returns the value of a variable, or `None` if it is not set.
+  pub fn parse(text: &str, format: ConfigFormat, vars: &dyn Fn(&str) -> Option<String>) -> Result<Self> {
+    let invalid = |e: &dyn std::fmt::Display| Error::InvalidInput(format!("Invalid configuration: {}", e));
+    let mut value: serde_json::Value = match format {
+      ConfigFormat::Yaml => serde_yaml::from_str(text).map_err(|e| invalid(&e))?,
+      #[cfg(feature = "config-toml")]
+      ConfigFormat::Toml => toml::from_str(text).map_err(|e| invalid(&e))?,
+      #[cfg(not(feature = "config-toml"))]
+      ConfigFormat::Toml => {
+        return Err(Error::Unsupported("TOML configuration requires the `config-toml` feature".to_string()));
+      }
+    };
+    // An empty YAML document is an empty configuration.
+    if value.is_null() {
+      value = serde_json::Value::Object(Default::default());
+    }
+    interpolate::interpolate(&mut value, vars)?;
+    serde_json::from_value(value).map_err(|e| invalid(&e))
+  }
+
+  /// Loads a configuration file, as TOML if its name ends in `.toml` and as YAML otherwise.
+  pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+    let path = path.as_ref();
+    let contents = read(path)?;
+    let format = match path.extension() {
+      Some(extension) if extension.eq_ignore_ascii_case("toml") => ConfigFormat::Toml,
+      _ => ConfigFormat::Yaml,
+    };
+    Self::parse(&contents, format, &|name| std::env::var(name).ok())
+  }
+
+  /// Loads the configuration from [`Config::default_path`].
+  pub fn load_default() -> Result<Self> {
+    let path = Self::default_path()
+      .ok_or_else(|| Error::InvalidInput("Cannot determine the configuration file location".to_string()))?;
+    Self::load(path)
+  }
+
+  /// Loads `path`, or the file at [`Config::default_path`] if there is one, and
+  /// applies credentials from the environment with [`Config::with_env`]. Without
+  /// a file, the configuration holds only the environment's credentials.
+  ///
+  /// This is how the `registrar` binaries find their credentials.
+  pub fn load_with_env(path: Option<&Path>) -> Result<Self> {
+    let config = match path {
+      Some(path) => Self::load(path)?,
+      None => match Self::default_path() {
+        Some(path) if path.exists() => Self::load(path)?,
+        _ => Self::default(),
+      },
+    };
+    Ok(config.with_env())
+  }
+
+  /// The configuration file used when none is given: `$REGISTRAR_CONFIG` if set,
+  /// otherwise `registrar/config.yaml` in `$XDG_CONFIG_HOME` or `~/.config`.
+  pub fn default_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(CONFIG_ENV) {
+      return Some(PathBuf::from(path));
+    }
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+      .map(PathBuf::from)
+      .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("registrar").join("config.yaml"))
+  }
+
+  /// Replaces credentials with those given in the environment.
+  ///
+  /// Porkbun credentials are taken from `PORKBUN_API_KEY` and `PORKBUN_SECRET_KEY`,
+  /// and Name.com credentials from `NAMECOM_USERNAME`, `NAMECOM_TOKEN` and the
+  /// optional `NAMECOM_HOST`. A registrar is only overridden when all of its
+  /// required variables are set.
+  pub fn with_env(self) -> Self {
+    self.with_vars(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+  }
+
+  /// Like [`Config::with_env`], reading variables through `vars`.
+  ///
+  /// # Arguments
+  /// * `vars` - Returns the value of a variable, or `None` if it is not set.
+  pub fn with_vars(mut self, vars: impl Fn(&str) -> Option<String>) -> Self {
+    if let (Some(apikey), Some(secretapikey)) = (vars(PORKBUN_API_KEY_ENV), vars(PORKBUN_SECRET_KEY_ENV)) {
+      self.porkbun = Some(PorkbunCredentials { apikey, secretapikey });
+    }
+    if let (Some(username), Some(token)) = (vars(NAMECOM_USERNAME_ENV), vars(NAMECOM_TOKEN_ENV)) {
+      self.name_com = Some(NameComCredentials { username, token, host: vars(NAMECOM_HOST_ENV) });
+    }
+    self
+  }
+
+  /// The accounts in this configuration, by name.
+  pub fn registry(&self) -> Result<AccountRegistry> {
+    AccountRegistry::new(self)
+  }
+
+  /// The registrars that have credentials in this configuration.
+  pub fn configured_providers(&self) -> Vec<Provider> {
+    let configured = |provider: Provider, section: bool| {
+      section || self.accounts.values().any(|account| account.provider() == provider)
+    };
+    let mut providers = Vec::new();
+    if configured(Provider::Porkbun, self.porkbun.is_some()) {
+      providers.push(Provider::Porkbun);
+    }
+    if configured(Provider::NameCom, self.name_com.is_some()) {
+      providers.push(Provider::NameCom);
+    }
+    providers
+  }
+
+  /// Chooses the registrar to use: `requested` if given, otherwise the
+  /// configured `provider`, otherwise the only registrar with credentials.
+  pub fn select_provider(&self, requested: Option<Provider>) -> Result<Provider> {
+    if let Some(provider) = requested.or(self.provider) {
+      return Ok(provider);
+    }
+    match self.configured_providers().as_slice() {
+      [provider] => Ok(*provider),
+      [] => Err(Error::InvalidInput("No registrar credentials are configured".to_string())),
+      _ => Err(Error::InvalidInput(
+        "Several registrars are configured; choose one with `provider`".to_string(),
+      )),
+    }
+  }
+
+  /// Builds a Porkbun client from the configured credentials: the `porkbun`
+  /// section, the default account, or the only Porkbun account.
+  #[cfg(feature = "porkbun")]
+  pub fn porkbun(&self) -> Result<crate::porkbun::Porkbun> {
+    let registry = self.registry()?;
+    registry.porkbun(registry.select(None, Some(Provider::Porkbun))?)
+  }
+
+  /// Builds a Name.com client from the configured credentials: the `name_com`
+  /// section, the default account, or the only Name.com account.
+  #[cfg(feature = "name-com")]
+  pub fn name_com(&self) -> Result<crate::name_com::NameDotCom> {
+    let registry = self.registry()?;
+    registry.name_com(registry.select(None, Some(Provider::NameCom))?)
+  }
+
+  /// Builds the DNS records client for the account chosen by
+  /// [`AccountRegistry::select`] at `provider`.
+  #[cfg(any(feature = "porkbun", feature = "name-com"))]
+  pub fn dns_records(&self, provider: Provider) -> Result<Box<dyn crate::records::DnsRecords>> {
+    let registry = self.registry()?;
+    registry.dns_records(registry.select(None, Some(provider))?)
+  }
+
+  /// Builds the domain portfolio client for the account chosen by
+  /// [`AccountRegistry::select`] at `provider`.
+  #[cfg(any(feature = "porkbun", feature = "name-com"))]
+  pub fn portfolio(&self, provider: Provider) -> Result<Box<dyn crate::portfolio::DomainPortfolio>> {
+    let registry = self.registry()?;
+    registry.portfolio(registry.select(None, Some(provider))?)
+  }
+}
+
+/// Reads a configuration file. A failed read is an `Error::Io` naming the file.
+fn read(path: &Path) -> Result<String> {
+  std::fs::read_to_string(path).map_err(|e| {
+    Error::Io(std::io::Error::new(e.kind(), format!("Cannot read configuration '{}': {}", path.display(), e)))
+  })
+}
+
+/// Parses the YAML configuration of one of the crate's servers.
+///
+/// # Arguments
+/// * `yaml` - The configuration's text.
+/// * `name` - Names the configuration in errors (e.g., "dyndns2").
+pub(crate) fn parse_yaml<T: DeserializeOwned>(yaml: &str, name: &str) -> Result<T> {
+  serde_yaml::from_str(yaml).map_err(|e| Error::InvalidInput(format!("Invalid {} configuration: {}", name, e)))
+}
+
+/// Loads the YAML configuration file of one of the crate's servers.
+///
+/// # Arguments
+/// * `path` - The configuration file.
+/// * `name` - Names the configuration in errors (e.g., "dyndns2").
+pub(crate) fn load_yaml<T: DeserializeOwned>(path: &Path, name: &str) -> Result<T> {
+  parse_yaml(&read(path)?, name)
+}
@@ -0,0 +1,181 @@
+//! Hands out provider clients for the accounts named in a configuration.
+
+use super::{AccountConfig, Config};
+use crate::{Error, Provider, Result};
+use std::collections::BTreeMap;
+
+/// The accounts in a configuration, by name.
+///
+/// Besides the entries under `accounts`, the `porkbun` and `name_com`
+/// sections become accounts named `porkbun` and `name-com`.
+///
+/// ```no_run
+/// use registrar::config::Config;
+///
+/// # async fn run() -> registrar::Result<()> {
+/// let registry = Config::load_default()?.registry()?;
+/// for name in registry.names() {
+///   let domains = registry.portfolio(name)?.list_domains().await?;
+///   println!("{}: {} domains", name, domains.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AccountRegistry {
+  accounts: BTreeMap<String, AccountConfig>,
+  default_account: Option<String>,
+  /// The configuration's preferred registrar.
+  provider: Option<Provider>,
+}
+
+impl AccountRegistry {
+  /// Collects the accounts in `config`.
+  pub fn new(config: &Config) -> Result<Self> {
+    let mut accounts = config.accounts.clone();
+    let sections = [
+      config.porkbun.clone().map(AccountConfig::Porkbun),
+      config.name_com.clone().map(AccountConfig::NameCom),
+    ];
+    for account in sections.into_iter().flatten() {
+      let name = account.provider().as_str().to_string();
+      if accounts.contains_key(&name) {
+        return Err(Error::InvalidInput(format!(
+          "The account '{}' is defined both under `accounts` and as a `{}` section",
+          name,
+          account.provider()
+        )));
+      }
+      accounts.insert(name, account);
+    }
+    if let Some(name) = &config.default_account
+      && !accounts.contains_key(name)
+    {
+      return Err(Error::InvalidInput(format!("The default account '{}' is not configured", name)));
+    }
+    Ok(Self { accounts, default_account: config.default_account.clone(), provider: config.provider })
+  }
+
+  /// The account names, in alphabetical order.
+  pub fn names(&self) -> impl Iterator<Item = &str> {
+    self.accounts.keys().map(String::as_str)
+  }
+
+  /// Whether no account is configured.
+  pub fn is_empty(&self) -> bool {
+    self.accounts.is_empty()
+  }
+
+  /// The credentials of the account `name`.
+  pub fn get(&self, name: &str) -> Option<&AccountConfig> {
+    self.accounts.get(name)
+  }
+
+  /// The registrar of the account `name`.
+  pub fn provider(&self, name: &str) -> Result<Provider> {
+    Ok(self.account(name)?.provider())
+  }
+
+  /// The names of the accounts at `provider`.
+  pub fn accounts_at(&self, provider: Provider) -> impl Iterator<Item = &str> {
+    self.accounts.iter().filter(move |(_, account)| account.provider() == provider).map(|(name, _)| name.as_str())
+  }
+
+  /// Chooses an account: `requested` if given, otherwise the default account if
+  /// it is at `provider`, otherwise the only account at `provider`. Without a
+  /// `provider`, the configuration's `provider` applies, if it has one.
+  ///
+  /// # Arguments
+  /// * `requested` - An account named by the user.
+  /// * `provider` - Limits the choice to one registrar.
+  pub fn select(&self, requested: Option<&str>, provider: Option<Provider>) -> Result<&str> {
+    if let Some(requested) = requested {
+      let (name, account) = self.accounts.get_key_value(requested).ok_or_else(|| unknown(requested))?;
+      return match provider {
+        Some(provider) if account.provider() != provider => Err(wrong_provider(name, account.provider(), provider)),
+        _ => Ok(name),
+      };
+    }
+    if let Some(name) = &self.default_account
+      && provider.is_none_or(|provider| self.accounts[name].provider() == provider)
+    {
+      return Ok(name);
+    }
+    let provider = provider.or(self.provider);
+    let candidates: Vec<&str> = match provider {
+      Some(provider) => self.accounts_at(provider).collect(),
+      None => self.names().collect(),
+    };
+    let scope = provider.map(|provider| format!(" for {}", provider)).unwrap_or_default();
+    match candidates.as_slice() {
+      [name] => Ok(name),
+      [] => Err(Error::InvalidInput(format!("No registrar credentials{} are configured", scope))),
+      _ => Err(Error::InvalidInput(format!(
+        "Several accounts{} are configured ({}); choose one by name or set `default_account`",
+        scope,
+        candidates.join(", ")
+      ))),
+    }
+  }
+
+  /// Builds a Porkbun client for the account `name`.
+  #[cfg(feature = "porkbun")]
+  pub fn porkbun(&self, name: &str) -> Result<crate::porkbun::Porkbun> {
+    match self.account(name)? {
+      AccountConfig::Porkbun(credentials) => Ok(credentials.client()),
+      other => Err(wrong_provider(name, other.provider(), Provider::Porkbun)),
+    }
+  }
+
+  /// Builds a Name.com client for the account `name`.
+  #[cfg(feature = "name-com")]
+  pub fn name_com(&self, name: &str) -> Result<crate::name_com::NameDotCom> {
+    match self.account(name)? {
+      AccountConfig::NameCom(credentials) => Ok(credentials.client()),
+      other => Err(wrong_provider(name, other.provider(), Provider::NameCom)),
+    }
+  }
+
+  /// Builds the DNS records client for the account `name`.
+  #[cfg(any(feature = "porkbun", feature = "name-com"))]
+  pub fn dns_records(&self, name: &str) -> Result<Box<dyn crate::records::DnsRecords>> {
+    match self.account(name)? {
+      #[cfg(feature = "porkbun")]
+      AccountConfig::Porkbun(credentials) => Ok(Box::new(credentials.client())),
+      #[cfg(feature = "name-com")]
+      AccountConfig::NameCom(credentials) => Ok(Box::new(credentials.client())),
+      #[allow(unreachable_patterns)]
+      other => Err(not_compiled(other.provider())),
+    }
+  }
+
+  /// Builds the domain portfolio client for the account `name`.
+  #[cfg(any(feature = "porkbun", feature = "name-com"))]
+  pub fn portfolio(&self, name: &str) -> Result<Box<dyn crate::portfolio::DomainPortfolio>> {
+    match self.account(name)? {
+      #[cfg(feature = "porkbun")]
+      AccountConfig::Porkbun(credentials) => Ok(Box::new(credentials.client())),
+      #[cfg(feature = "name-com")]
+      AccountConfig::NameCom(credentials) => Ok(Box::new(credentials.client())),
+      #[allow(unreachable_patterns)]
+      other => Err(not_compiled(other.provider())),
+    }
+  }
+
+  fn account(&self, name: &str) -> Result<&AccountConfig> {
+    self.accounts.get(name).ok_or_else(|| unknown(name))
+  }
+}
+
+fn unknown(name: &str) -> Error {
+  Error::InvalidInput(format!("No account named '{}' is configured", name))
+}
+
+fn wrong_provider(name: &str, actual: Provider, expected: Provider) -> Error {
+  Error::InvalidInput(format!("The account '{}' is at {}, not {}", name, actual, expected))
+}
+
+#[cfg(any(feature = "porkbun", feature = "name-com"))]
+fn not_compiled(provider: Provider) -> Error {
+  Error::Unsupported(format!("Support for {} is not compiled in", provider))
+}
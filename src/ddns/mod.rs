@@ -0,0 +1,298 @@
+//! # Dynamic DNS
+//!
+//! Keeps A and AAAA records pointed at a host whose public address changes.
+//!
+//! A [`DdnsUpdater`] discovers the current public IPv4 and IPv6 addresses
+//! through an [`IpSource`], compares them with the records at the registrar,
+//! and only changes records that differ. The last published addresses can be
+//! kept in a state file, so that runs where nothing changed make no registrar
+//! API calls at all.
+//!
+//! To use this module, you must enable the `ddns` feature in your `Cargo.toml`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use registrar::ddns::{DdnsUpdater, LocalAddress};
+//! use registrar::porkbun::Porkbun;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # async fn run(client: Porkbun) -> registrar::Result<()> {
+//! let client = Arc::new(client);
+//! let mut updater = DdnsUpdater::new(client.clone(), "example.com")
+//!   .with_host("home")
+//!   .with_ipv4_source(client)
+//!   .with_ipv6_source(Arc::new(LocalAddress))
+//!   .with_state_file("/var/lib/registrar/ddns.json")?;
+//!
+//! updater
+//!   .run(Duration::from_secs(300), |result| match result {
+//!     Ok(report) if report.changed() => println!("updated: {:?}", report.hosts),
+//!     Ok(_) => {}
+//!     Err(e) => eprintln!("update failed: {}", e),
+//!   })
+//!   .await;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::records::{DnsRecords, Record};
+use crate::Result;
+use chrono::Utc;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod source;
+pub mod types;
+
+pub use source::{IpSource, LocalAddress};
+pub use types::{AddressChange, DdnsState, HostUpdate, IpFamily, UpdateReport};
+
+/// The first delay before retrying a failed update.
+const MIN_BACKOFF: Duration = Duration::from_secs(30);
+
+/// The longest delay between retries of a failing update.
+const MAX_BACKOFF: Duration = Duration::from_secs(3600);
+
+/// How often records are checked at the registrar even if the address has not changed.
+const DEFAULT_RESYNC: Duration = Duration::from_secs(24 * 3600);
+
+/// Points the `host` record of `domain` at `ip`, creating, updating or de-duplicating as needed.
+///
+/// The record type (`A` or `AAAA`) follows the address family. Records of the
+/// other family are left alone.
+///
+/// # Arguments
+/// * `records` - The registrar hosting the zone.
+/// * `domain` - The registered domain (e.g., "example.com").
+/// * `host` - The name relative to the domain. Use an empty string for the apex.
+/// * `ip` - The address to publish.
+/// * `ttl` - The TTL for new or changed records. `None` uses the registrar's default.
+pub async fn set_address(
+  records: &dyn DnsRecords,
+  domain: &str,
+  host: &str,
+  ip: IpAddr,
+  ttl: Option<u32>,
+) -> Result<AddressChange> {
+  let existing = records.list_records(domain).await?;
+  reconcile(records, domain, host, ip, ttl, &existing).await
+}
+
+/// Like [`set_address`], against an already fetched list of the zone's records.
+async fn reconcile(
+  records: &dyn DnsRecords,
+  domain: &str,
+  host: &str,
+  ip: IpAddr,
+  ttl: Option<u32>,
+  zone: &[Record],
+) -> Result<AddressChange> {
+  let record_type = IpFamily::of(&ip).record_type();
+  let points_at_ip = |record: &Record| record.content.trim().parse::<IpAddr>().ok() == Some(ip);
+  let mut matching: Vec<&Record> = zone
+    .iter()
+    .filter(|record| record.record_type == record_type && record.host.eq_ignore_ascii_case(host))
+    .collect();
+
+  // Keep the record already pointing at the address, if any, and remove the rest.
+  matching.sort_by_key(|record| !points_at_ip(record));
+  let Some((keep, duplicates)) = matching.split_first() else {
+    let mut record = Record::new(host, record_type, ip.to_string());
+    record.ttl = ttl;
+    records.create_record(domain, &record).await?;
+    return Ok(AddressChange::Created);
+  };
+
+  let change = if points_at_ip(keep) {
+    if duplicates.is_empty() {
+      return Ok(AddressChange::Unchanged);
+    }
+    AddressChange::Updated {
+      previous: duplicates[0].content.clone(),
+    }
+  } else {
+    let mut record = (*keep).clone();
+    record.content = ip.to_string();
+    record.ttl = ttl.or(record.ttl);
+    records.update_record(domain, &record).await?;
+    AddressChange::Updated {
+      previous: keep.content.clone(),
+    }
+  };
+  for duplicate in duplicates {
+    records.delete_record(domain, duplicate.require_id()?).await?;
+  }
+  Ok(change)
+}
+
+/// Keeps a set of hosts in one domain pointed at the machine's public addresses.
+pub struct DdnsUpdater {
+  records: Arc<dyn DnsRecords>,
+  domain: String,
+  hosts: Vec<String>,
+  ipv4_source: Option<Arc<dyn IpSource>>,
+  ipv6_source: Option<Arc<dyn IpSource>>,
+  ttl: Option<u32>,
+  resync: Duration,
+  state: DdnsState,
+  state_file: Option<PathBuf>,
+}
+
+impl DdnsUpdater {
+  /// Creates an updater with no hosts and no address sources.
+  ///
+  /// # Arguments
+  /// * `records` - The registrar hosting the domain's DNS.
+  /// * `domain` - The registered domain (e.g., "example.com").
+  pub fn new(records: Arc<dyn DnsRecords>, domain: &str) -> Self {
+    Self {
+      records,
+      domain: domain.trim_end_matches('.').to_ascii_lowercase(),
+      hosts: Vec::new(),
+      ipv4_source: None,
+      ipv6_source: None,
+      ttl: None,
+      resync: DEFAULT_RESYNC,
+      state: DdnsState::default(),
+      state_file: None,
+    }
+  }
+
+  /// Adds a host to keep updated, relative to the domain. Use an empty string or `@` for the apex.
+  pub fn with_host(mut self, host: &str) -> Self {
+    let host = if host == "@" { "" } else { host.trim_end_matches('.') };
+    self.hosts.push(host.to_ascii_lowercase());
+    self
+  }
+
+  /// Publishes A records with the address reported by `source`.
+  pub fn with_ipv4_source(mut self, source: Arc<dyn IpSource>) -> Self {
+    self.ipv4_source = Some(source);
+    self
+  }
+
+  /// Publishes AAAA records with the address reported by `source`.
+  pub fn with_ipv6_source(mut self, source: Arc<dyn IpSource>) -> Self {
+    self.ipv6_source = Some(source);
+    self
+  }
+
+  /// Sets the TTL of created and updated records.
+  pub fn with_ttl(mut self, ttl: u32) -> Self {
+    self.ttl = Some(ttl);
+    self
+  }
+
+  /// Sets how often the records are checked at the registrar even though the
+  /// addresses have not changed, to repair edits made elsewhere. Defaults to one day.
+  pub fn with_resync(mut self, resync: Duration) -> Self {
+    self.resync = resync;
+    self
+  }
+
+  /// Persists the last published addresses to `path`, loading them if the file exists.
+  pub fn with_state_file(mut self, path: impl Into<PathBuf>) -> Result<Self> {
+    let path = path.into();
+    if path.exists() {
+      self.state = DdnsState::load(&path)?;
+    }
+    self.state_file = Some(path);
+    Ok(self)
+  }
+
+  /// The last published addresses.
+  pub fn state(&self) -> &DdnsState {
+    &self.state
+  }
+
+  /// Discovers the current addresses and updates any records that differ.
+  pub async fn update(&mut self) -> Result<UpdateReport> {
+    let ipv4 = discover(&self.ipv4_source, IpFamily::V4).await?;
+    let ipv6 = discover(&self.ipv6_source, IpFamily::V6).await?;
+    let fqdns: Vec<String> = self.hosts.iter().map(|host| self.fqdn(host)).collect();
+
+    let fresh = self.state.synced_at.is_some_and(|synced_at| {
+      (Utc::now() - synced_at).to_std().is_ok_and(|age| age < self.resync)
+    });
+    if fresh && self.state.ipv4 == ipv4 && self.state.ipv6 == ipv6 && self.state.hosts == fqdns {
+      return Ok(UpdateReport {
+        ipv4,
+        ipv6,
+        skipped: true,
+        hosts: Vec::new(),
+      });
+    }
+
+    let zone = self.records.list_records(&self.domain).await?;
+    let mut hosts = Vec::new();
+    for host in &self.hosts {
+      for ip in [ipv4, ipv6].into_iter().flatten() {
+        let change = reconcile(self.records.as_ref(), &self.domain, host, ip, self.ttl, &zone).await?;
+        hosts.push(HostUpdate {
+          fqdn: self.fqdn(host),
+          ip,
+          change,
+        });
+      }
+    }
+
+    self.state = DdnsState {
+      hosts: fqdns,
+      ipv4,
+      ipv6,
+      synced_at: Some(Utc::now()),
+    };
+    if let Some(path) = &self.state_file {
+      self.state.save(path)?;
+    }
+    Ok(UpdateReport {
+      ipv4,
+      ipv6,
+      skipped: false,
+      hosts,
+    })
+  }
+
+  /// Runs [`DdnsUpdater::update`] every `interval` until the future is dropped.
+  ///
+  /// After a failure the next attempt is made sooner, backing off exponentially
+  /// from 30 seconds up to an hour. `on_result` is called with every outcome.
+  pub async fn run(&mut self, interval: Duration, mut on_result: impl FnMut(&Result<UpdateReport>)) {
+    let mut backoff = MIN_BACKOFF;
+    loop {
+      let result = self.update().await;
+      on_result(&result);
+      let delay = match result {
+        Ok(_) => {
+          backoff = MIN_BACKOFF;
+          interval
+        }
+        Err(_) => {
+          let delay = backoff;
+          backoff = (backoff * 2).min(MAX_BACKOFF);
+          delay
+        }
+      };
+      tokio::time::sleep(delay).await;
+    }
+  }
+
+  fn fqdn(&self, host: &str) -> String {
+    if host.is_empty() {
+      self.domain.clone()
+    } else {
+      format!("{}.{}", host, self.domain)
+    }
+  }
+}
+
+async fn discover(source: &Option<Arc<dyn IpSource>>, family: IpFamily) -> Result<Option<IpAddr>> {
+  match source {
+    Some(source) => source.public_ip(family).await,
+    None => Ok(None),
+  }
+}
@@ -0,0 +1,77 @@
+//! Discovery of the host's public IP addresses.
+
+use super::types::IpFamily;
+use crate::Result;
+use async_trait::async_trait;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+
+/// A way of finding the public address the DNS records should point to.
+#[async_trait]
+pub trait IpSource: Send + Sync {
+  /// Returns the current public address of `family`, or `Ok(None)` if the host has none.
+  async fn public_ip(&self, family: IpFamily) -> Result<Option<IpAddr>>;
+}
+
+/// Uses Porkbun's `ping` endpoint, which echoes the caller's address.
+///
+/// IPv4 is queried through Porkbun's IPv4-only host. IPv6 is queried through
+/// the regular host and is only reported when the request actually used IPv6.
+#[cfg(feature = "porkbun")]
+#[async_trait]
+impl IpSource for crate::porkbun::Porkbun {
+  async fn public_ip(&self, family: IpFamily) -> Result<Option<IpAddr>> {
+    let response = match family {
+      IpFamily::V4 => self.ping_ipv4().await?,
+      IpFamily::V6 => self.ping().await?,
+    };
+    let ip = response.your_ip.trim().parse::<IpAddr>().map_err(|_| {
+      crate::Error::Api(format!("Porkbun returned an invalid IP address '{}'", response.your_ip))
+    })?;
+    Ok(Some(ip).filter(|ip| family.matches(ip)))
+  }
+}
+
+/// Reads the source address the operating system would use to reach the internet.
+///
+/// No packets are sent: a UDP socket is connected to a public resolver address
+/// and its local address inspected. Only globally routable addresses are
+/// reported, so this suits IPv6 and hosts with a public IPv4 address, but not
+/// IPv4 behind NAT.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalAddress;
+
+#[async_trait]
+impl IpSource for LocalAddress {
+  async fn public_ip(&self, family: IpFamily) -> Result<Option<IpAddr>> {
+    let (bind, target): (SocketAddr, SocketAddr) = match family {
+      IpFamily::V4 => (
+        (Ipv4Addr::UNSPECIFIED, 0).into(),
+        (Ipv4Addr::new(1, 1, 1, 1), 53).into(),
+      ),
+      IpFamily::V6 => (
+        (Ipv6Addr::UNSPECIFIED, 0).into(),
+        (Ipv6Addr::new(0x2606, 0x4700, 0x4700, 0, 0, 0, 0, 0x1111), 53).into(),
+      ),
+    };
+    let socket = UdpSocket::bind(bind)?;
+    // Without a route for the family, connecting fails: the host has no such address.
+    if socket.connect(target).is_err() {
+      return Ok(None);
+    }
+    let ip = socket.local_addr()?.ip();
+    Ok(Some(ip).filter(is_global))
+  }
+}
+
+/// Whether an address is publicly routable, as opposed to private, loopback or link-local.
+fn is_global(ip: &IpAddr) -> bool {
+  match ip {
+    IpAddr::V4(ip) => {
+      let shared = ip.octets()[0] == 100 && (ip.octets()[1] & 0xc0) == 64;
+      !(ip.is_private() || ip.is_loopback() || ip.is_link_local() || ip.is_unspecified() || shared)
+    }
+    IpAddr::V6(ip) => {
+      !(ip.is_loopback() || ip.is_unspecified() || ip.is_unique_local() || ip.is_unicast_link_local())
+    }
+  }
+}
@@ -0,0 +1,109 @@
+//! Contains the types reported and persisted by the dynamic DNS updater.
+
+use crate::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::path::Path;
+
+/// An IP address family.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum IpFamily {
+  V4,
+  V6,
+}
+
+impl IpFamily {
+  /// The DNS record type holding addresses of this family (`A` or `AAAA`).
+  pub fn record_type(&self) -> &'static str {
+    match self {
+      IpFamily::V4 => "A",
+      IpFamily::V6 => "AAAA",
+    }
+  }
+
+  /// The family of an address.
+  pub fn of(ip: &IpAddr) -> Self {
+    if ip.is_ipv4() { IpFamily::V4 } else { IpFamily::V6 }
+  }
+
+  /// Whether `ip` belongs to this family.
+  pub fn matches(&self, ip: &IpAddr) -> bool {
+    IpFamily::of(ip) == *self
+  }
+}
+
+/// What happened to a host's address record.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum AddressChange {
+  /// The record already pointed at the address.
+  Unchanged,
+  /// No record existed, so one was created.
+  Created,
+  /// The record pointed elsewhere and was changed. Duplicate records were removed.
+  Updated { previous: String },
+}
+
+/// The outcome for one host and address family.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HostUpdate {
+  pub fqdn: String,
+  pub ip: IpAddr,
+  #[serde(flatten)]
+  pub change: AddressChange,
+}
+
+/// The result of one [`super::DdnsUpdater::update`] run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdateReport {
+  /// The discovered public IPv4 address, if any.
+  pub ipv4: Option<IpAddr>,
+  /// The discovered public IPv6 address, if any.
+  pub ipv6: Option<IpAddr>,
+  /// `true` if the addresses matched the state file and the registrar was not contacted.
+  pub skipped: bool,
+  /// The per-host outcomes. Empty when `skipped`.
+  pub hosts: Vec<HostUpdate>,
+}
+
+impl UpdateReport {
+  /// Returns `true` if any record was created or changed.
+  pub fn changed(&self) -> bool {
+    self.hosts.iter().any(|host| host.change != AddressChange::Unchanged)
+  }
+}
+
+/// The addresses last published, persisted between runs to avoid needless API calls.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DdnsState {
+  /// The fully qualified names the addresses were published for.
+  #[serde(default)]
+  pub hosts: Vec<String>,
+  #[serde(default)]
+  pub ipv4: Option<IpAddr>,
+  #[serde(default)]
+  pub ipv6: Option<IpAddr>,
+  /// When the records were last checked at the registrar.
+  #[serde(default)]
+  pub synced_at: Option<DateTime<Utc>>,
+}
+
+impl DdnsState {
+  /// Loads a persisted state from a JSON file.
+  pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+    let contents = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+  }
+
+  /// Writes the state to a JSON file, replacing it atomically.
+  pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    std::fs::write(&temp_path, serde_json::to_vec_pretty(self)?)?;
+    std::fs::rename(&temp_path, path)?;
+    Ok(())
+  }
+}
@@ -0,0 +1,138 @@
+//! Keeps the registrar's DS records in sync with a zone's CDS/CDNSKEY records (RFC 7344, RFC 8078).
+//!
+//! The child zone publishes CDS and/or CDNSKEY records at its apex describing
+//! the DS set it wants in the parent. [`CdsSync`] reads them from every
+//! authoritative server, requires the servers to agree, and reconciles the
+//! registrar's DS set through [`DsRegistry`]. The RFC 8078 delete signal
+//! (CDS `0 0 0 00` or CDNSKEY `0 3 0 AA==`) removes every DS record, turning
+//! DNSSEC off for the delegation.
+//!
+//! The CDS/CDNSKEY answers are trusted as served: their DNSSEC signatures are
+//! not validated. Point the scanner only at authoritative servers you operate.
+//!
+//! Requires the `lookup` feature.
+
+use super::{
+  DsRegistry, check_removals,
+  types::{DigestType, DsRecord},
+};
+use crate::lookup::DnsLookup;
+use crate::{Error, Result};
+
+/// The changes needed to bring the registrar's DS set in line with the zone.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CdsPlan {
+  /// DS records to publish at the registrar.
+  pub add: Vec<DsRecord>,
+  /// DS records to remove from the registrar.
+  pub remove: Vec<DsRecord>,
+}
+
+impl CdsPlan {
+  /// Returns `true` if the registrar is already in sync.
+  pub fn is_empty(&self) -> bool {
+    self.add.is_empty() && self.remove.is_empty()
+  }
+}
+
+/// Reconciles a registrar's DS records with the CDS/CDNSKEY records of a zone.
+pub struct CdsSync<'a> {
+  registry: &'a dyn DsRegistry,
+  authoritative: DnsLookup,
+  digest_type: DigestType,
+}
+
+impl<'a> CdsSync<'a> {
+  /// Creates a scanner.
+  ///
+  /// # Arguments
+  /// * `registry` - The registrar holding the domain's DS records.
+  /// * `authoritative` - A lookup targeting the zone's authoritative servers
+  ///   (see [`DnsLookup::authoritative_for`]).
+  pub fn new(registry: &'a dyn DsRegistry, authoritative: DnsLookup) -> Self {
+    Self {
+      registry,
+      authoritative,
+      digest_type: DigestType::Sha256,
+    }
+  }
+
+  /// Sets the digest type used when deriving DS records from CDNSKEY records. Defaults to SHA-256.
+  pub fn with_digest_type(mut self, digest_type: DigestType) -> Self {
+    self.digest_type = digest_type;
+    self
+  }
+
+  /// Reads the zone's desired DS set.
+  ///
+  /// Returns `Ok(None)` when the zone publishes neither CDS nor CDNSKEY
+  /// records, meaning no change is requested, and `Ok(Some(vec![]))` for the
+  /// delete signal.
+  pub async fn desired_ds(&self, domain: &str) -> Result<Option<Vec<DsRecord>>> {
+    let mut agreed: Option<Option<Vec<DsRecord>>> = None;
+    for server in self.authoritative.per_server() {
+      let desired = self.desired_ds_from(&server, domain).await?;
+      match &agreed {
+        None => agreed = Some(desired),
+        Some(previous) if *previous == desired => {}
+        Some(_) => {
+          return Err(Error::Dns(format!(
+            "Authoritative servers for '{}' disagree on CDS/CDNSKEY; refusing to change DS records",
+            domain
+          )));
+        }
+      }
+    }
+    agreed.ok_or_else(|| Error::Dns("No authoritative servers configured".to_string()))
+  }
+
+  /// Compares the zone's desired DS set with the registrar's and returns the changes, without applying them.
+  ///
+  /// Fails with [`Error::Unsupported`] when the registrar removes DS records
+  /// by key tag and a record to remove shares its key tag with a desired one.
+  pub async fn plan(&self, domain: &str) -> Result<CdsPlan> {
+    let Some(desired) = self.desired_ds(domain).await? else {
+      return Ok(CdsPlan::default());
+    };
+    let current = self.registry.list_ds(domain).await?;
+    let remove: Vec<DsRecord> = current.iter().filter(|ds| !desired.contains(ds)).cloned().collect();
+    check_removals(self.registry, domain, &remove, &desired)?;
+    Ok(CdsPlan {
+      add: desired.iter().filter(|ds| !current.contains(ds)).cloned().collect(),
+      remove,
+    })
+  }
+
+  /// Applies the changes from [`CdsSync::plan`], adding new records before removing old ones.
+  pub async fn sync(&self, domain: &str) -> Result<CdsPlan> {
+    let plan = self.plan(domain).await?;
+    for ds in &plan.add {
+      self.registry.add_ds(domain, ds).await?;
+    }
+    for ds in &plan.remove {
+      self.registry.remove_ds(domain, ds).await?;
+    }
+    Ok(plan)
+  }
+
+  async fn desired_ds_from(&self, server: &DnsLookup, domain: &str) -> Result<Option<Vec<DsRecord>>> {
+    let mut cds = server.cds(domain).await?;
+    let cdnskey = server.cdnskey(domain).await?;
+    if cds.is_empty() && cdnskey.is_empty() {
+      return Ok(None);
+    }
+    if cds.iter().any(DsRecord::is_delete_signal) || cdnskey.iter().any(|key| key.is_delete_signal()) {
+      return Ok(Some(Vec::new()));
+    }
+    // CDS takes precedence; CDNSKEY is only used to derive DS records when no CDS is published.
+    if cds.is_empty() {
+      cds = cdnskey
+        .iter()
+        .map(|key| key.to_ds(self.digest_type))
+        .collect::<Result<Vec<_>>>()?;
+    }
+    cds.sort_by(|a, b| (a.key_tag, &a.digest).cmp(&(b.key_tag, &b.digest)));
+    cds.dedup();
+    Ok(Some(cds))
+  }
+}
@@ -0,0 +1,88 @@
+//! # DNSSEC
+//!
+//! A provider-agnostic model of the DS records a registrar publishes for a
+//! domain, and helpers for deriving them from a signer's DNSKEY.
+//!
+//! Porkbun's `DnssecRecord` stores every field as a string while Name.com's
+//! uses integers. [`DsRecord`] is typed, and converts to and from both
+//! providers' payloads.
+//!
+//! ## Example
+//!
+//! ```
+//! use registrar::dnssec::{DigestType, Dnskey};
+//!
+//! let dnskey: Dnskey = "example.net. 3600 IN DNSKEY 257 3 13 (
+//!   GojIhhXUN/u4v54ZQqGSnyhWJwaubCvTmeexv7bR6edb
+//!   krSqQpF64cYbcB7wNcP+e+MAnLr+Wi9xMWyQLc8NAA== )"
+//!   .parse()?;
+//!
+//! let ds = dnskey.to_ds(DigestType::Sha256)?;
+//! assert_eq!(ds.key_tag, 55648);
+//! # Ok::<(), registrar::Error>(())
+//! ```
+
+use crate::{Error, Result};
+use async_trait::async_trait;
+
+#[cfg(feature = "lookup")]
+pub mod cds;
+#[cfg(feature = "name-com")]
+mod name_com;
+#[cfg(feature = "porkbun")]
+mod porkbun;
+pub mod rollover;
+pub mod types;
+
+#[cfg(feature = "lookup")]
+pub use cds::{CdsPlan, CdsSync};
+pub use rollover::{Rollover, RolloverAction, RolloverState, RolloverStep};
+pub use types::{DigestType, Dnskey, DnssecAlgorithm, DsRecord};
+
+/// DS record operations at the registry, shared by every supported registrar.
+#[async_trait]
+pub trait DsRegistry: Send + Sync {
+  /// Lists the DS records currently published for the domain.
+  async fn list_ds(&self, domain: &str) -> Result<Vec<DsRecord>>;
+
+  /// Publishes a DS record for the domain.
+  async fn add_ds(&self, domain: &str, ds: &DsRecord) -> Result<()>;
+
+  /// Removes a DS record from the domain.
+  ///
+  /// Porkbun identifies DS records by key tag only, so every record sharing
+  /// `ds.key_tag` is removed there; see [`DsRegistry::removes_by_key_tag`].
+  async fn remove_ds(&self, domain: &str, ds: &DsRecord) -> Result<()>;
+
+  /// Whether [`DsRegistry::remove_ds`] removes every record sharing the key
+  /// tag rather than only the given record.
+  fn removes_by_key_tag(&self) -> bool {
+    false
+  }
+}
+
+/// Refuses to remove `remove` when that would also remove a record in `keep`.
+///
+/// On a registry that removes by key tag, an old DS sharing its key tag with
+/// a kept one, as after changing the digest type of the same key, cannot be
+/// removed alone. Removing it would leave the domain without a DS for its
+/// current key, which breaks DNSSEC validation.
+pub(crate) fn check_removals(
+  registry: &dyn DsRegistry,
+  domain: &str,
+  remove: &[DsRecord],
+  keep: &[DsRecord],
+) -> Result<()> {
+  if !registry.removes_by_key_tag() {
+    return Ok(());
+  }
+  for ds in remove {
+    if let Some(kept) = keep.iter().find(|kept| kept.key_tag == ds.key_tag && *kept != ds) {
+      return Err(Error::Unsupported(format!(
+        "The registrar removes DS records by key tag, so DS {} of {} cannot be removed without also removing DS {}",
+        ds, domain, kept
+      )));
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,62 @@
+//! Conversions between `DsRecord` and Name.com's DNSSEC payloads.
+
+use super::{
+  DsRegistry,
+  types::{DigestType, DnssecAlgorithm, DsRecord},
+};
+use crate::name_com::{
+  NameDotCom,
+  dns::types::{DnssecCreatePayload, DnssecRecord},
+};
+use crate::{DomainName, Error, Result};
+use async_trait::async_trait;
+
+impl<'a> From<&'a DsRecord> for DnssecCreatePayload<'a> {
+  fn from(ds: &'a DsRecord) -> Self {
+    DnssecCreatePayload {
+      digest: &ds.digest,
+      digest_type: u8::from(ds.digest_type) as i32,
+      key_tag: ds.key_tag as i32,
+      algorithm: u8::from(ds.algorithm) as i32,
+    }
+  }
+}
+
+impl TryFrom<&DnssecRecord> for DsRecord {
+  type Error = Error;
+
+  fn try_from(record: &DnssecRecord) -> Result<Self> {
+    let out_of_range = |field: &str, value: i32| {
+      Error::Api(format!("Name.com returned an out-of-range DNSSEC {}: {}", field, value))
+    };
+    let key_tag = u16::try_from(record.key_tag).map_err(|_| out_of_range("key tag", record.key_tag))?;
+    let algorithm = u8::try_from(record.algorithm).map_err(|_| out_of_range("algorithm", record.algorithm))?;
+    let digest_type = u8::try_from(record.digest_type).map_err(|_| out_of_range("digest type", record.digest_type))?;
+    DsRecord::new(
+      key_tag,
+      DnssecAlgorithm::from(algorithm),
+      DigestType::from(digest_type),
+      &record.digest,
+    )
+  }
+}
+
+#[async_trait]
+impl DsRegistry for NameDotCom {
+  async fn list_ds(&self, domain: &str) -> Result<Vec<DsRecord>> {
+    let domain = DomainName::new(domain)?;
+    let records = self.dns(&domain).list_dnssec().await?;
+    records.iter().map(DsRecord::try_from).collect()
+  }
+
+  async fn add_ds(&self, domain: &str, ds: &DsRecord) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    self.dns(&domain).create_dnssec(DnssecCreatePayload::from(ds)).await?;
+    Ok(())
+  }
+
+  async fn remove_ds(&self, domain: &str, ds: &DsRecord) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    self.dns(&domain).delete_dnssec(&ds.digest).await
+  }
+}
@@ -0,0 +1,78 @@
+//! Conversions between `DsRecord` and Porkbun's DNSSEC payloads.
+
+use super::{
+  DsRegistry,
+  types::{DigestType, DnssecAlgorithm, DsRecord},
+};
+use crate::porkbun::{Porkbun, dns::types::DnssecRecord};
+use crate::{DomainName, Error, Result};
+use async_trait::async_trait;
+
+impl From<&DsRecord> for DnssecRecord {
+  fn from(ds: &DsRecord) -> Self {
+    DnssecRecord {
+      key_tag: ds.key_tag.to_string(),
+      alg: u8::from(ds.algorithm).to_string(),
+      digest_type: u8::from(ds.digest_type).to_string(),
+      digest: ds.digest.clone(),
+      max_sig_life: None,
+      key_data_flags: None,
+      key_data_protocol: None,
+      key_data_algo: None,
+      key_data_pub_key: None,
+    }
+  }
+}
+
+impl TryFrom<&DnssecRecord> for DsRecord {
+  type Error = Error;
+
+  fn try_from(record: &DnssecRecord) -> Result<Self> {
+    let number = |field: &str, value: &str| {
+      value
+        .trim()
+        .parse::<u16>()
+        .map_err(|_| Error::Api(format!("Porkbun returned a non-numeric DNSSEC {}: '{}'", field, value)))
+    };
+    let algorithm = u8::try_from(number("algorithm", &record.alg)?)
+      .map_err(|_| Error::Api(format!("Porkbun returned an out-of-range DNSSEC algorithm: '{}'", record.alg)))?;
+    let digest_type = u8::try_from(number("digest type", &record.digest_type)?).map_err(|_| {
+      Error::Api(format!(
+        "Porkbun returned an out-of-range DNSSEC digest type: '{}'",
+        record.digest_type
+      ))
+    })?;
+    DsRecord::new(
+      number("key tag", &record.key_tag)?,
+      DnssecAlgorithm::from(algorithm),
+      DigestType::from(digest_type),
+      &record.digest,
+    )
+  }
+}
+
+#[async_trait]
+impl DsRegistry for Porkbun {
+  async fn list_ds(&self, domain: &str) -> Result<Vec<DsRecord>> {
+    let domain = DomainName::new(domain)?;
+    let records = self.dns(&domain).get_dnssec_records().await?;
+    records.values().map(DsRecord::try_from).collect()
+  }
+
+  async fn add_ds(&self, domain: &str, ds: &DsRecord) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let record = DnssecRecord::from(ds);
+    self.dns(&domain).create_dnssec_record(&record).await?;
+    Ok(())
+  }
+
+  async fn remove_ds(&self, domain: &str, ds: &DsRecord) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    self.dns(&domain).delete_dnssec_record(&ds.key_tag.to_string()).await?;
+    Ok(())
+  }
+
+  fn removes_by_key_tag(&self) -> bool {
+    true
+  }
+}
@@ -0,0 +1,292 @@
+//! Orchestrates a KSK rollover at the registrar using the double-DS method.
+//!
+//! A rollover publishes the new DS record, waits for the parent zone's DS TTL
+//! to expire so resolvers have seen it, verifies the record is published, and
+//! finally removes the old DS records. The progress is kept in a
+//! [`RolloverState`] which can be persisted to a JSON file after every step so
+//! that an interrupted rollover resumes where it left off.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use registrar::dnssec::{DigestType, Dnskey, Rollover, RolloverState};
+//! use registrar::porkbun::Porkbun;
+//! use std::time::Duration;
+//!
+//! # async fn run(client: Porkbun, new_key: Dnskey) -> registrar::Result<()> {
+//! let state_file = "example.com.rollover.json";
+//! let mut rollover = match Rollover::resume(&client, state_file)? {
+//!   Some(rollover) => rollover,
+//!   None => {
+//!     let state = RolloverState::new("example.com", new_key.to_ds(DigestType::Sha256)?, Duration::from_secs(86400));
+//!     Rollover::new(&client, state).with_state_file(state_file)
+//!   }
+//! };
+//!
+//! // Show what will happen, then do it.
+//! for action in rollover.plan().await? {
+//!   println!("{}", action);
+//! }
+//! rollover.run().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{DsRegistry, check_removals, types::DsRecord};
+use crate::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The next step a rollover will perform.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum RolloverStep {
+  /// Publish the new DS record and record the existing ones as old.
+  PublishNew,
+  /// Wait until the parent zone's DS TTL has expired.
+  WaitParentTtl { until: DateTime<Utc> },
+  /// Confirm the new DS record is published at the registry.
+  Verify,
+  /// Remove the old DS records.
+  RemoveOld,
+  /// The rollover has completed.
+  Done,
+}
+
+/// The persisted progress of a rollover.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RolloverState {
+  pub domain: String,
+  /// The DS record being introduced.
+  pub new_ds: DsRecord,
+  /// The DS records to retire. Captured from the registry when the new record is published.
+  #[serde(default)]
+  pub old_ds: Vec<DsRecord>,
+  /// How long to wait after publishing before removing the old records.
+  #[serde(with = "duration_secs")]
+  pub parent_ttl: Duration,
+  pub step: RolloverStep,
+}
+
+impl RolloverState {
+  /// Creates the state for a rollover that has not started yet.
+  ///
+  /// # Arguments
+  /// * `domain` - The domain whose DS records are being rolled.
+  /// * `new_ds` - The DS record of the incoming key.
+  /// * `parent_ttl` - The TTL of the DS RRset in the parent zone (often one day).
+  pub fn new(domain: impl Into<String>, new_ds: DsRecord, parent_ttl: Duration) -> Self {
+    Self {
+      domain: domain.into(),
+      new_ds,
+      old_ds: Vec::new(),
+      parent_ttl,
+      step: RolloverStep::PublishNew,
+    }
+  }
+
+  /// Loads a persisted state from a JSON file.
+  pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+    let contents = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+  }
+
+  /// Writes the state to a JSON file, replacing it atomically.
+  pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    std::fs::write(&temp_path, serde_json::to_vec_pretty(self)?)?;
+    std::fs::rename(&temp_path, path)?;
+    Ok(())
+  }
+}
+
+/// A single operation in a rollover, as reported by [`Rollover::plan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RolloverAction {
+  Publish(DsRecord),
+  Wait { until: Option<DateTime<Utc>>, duration: Duration },
+  Verify(DsRecord),
+  Remove(DsRecord),
+}
+
+impl fmt::Display for RolloverAction {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      RolloverAction::Publish(ds) => write!(f, "publish DS {}", ds),
+      RolloverAction::Wait { until: Some(until), .. } => write!(f, "wait for the parent TTL until {}", until),
+      RolloverAction::Wait { duration, .. } => {
+        write!(f, "wait {}s for the parent TTL to expire", duration.as_secs())
+      }
+      RolloverAction::Verify(ds) => write!(f, "verify DS {} is published", ds),
+      RolloverAction::Remove(ds) => write!(f, "remove DS {}", ds),
+    }
+  }
+}
+
+/// Drives a [`RolloverState`] to completion against a registrar.
+pub struct Rollover<'a> {
+  registry: &'a dyn DsRegistry,
+  state: RolloverState,
+  state_file: Option<PathBuf>,
+}
+
+impl<'a> Rollover<'a> {
+  /// Creates a rollover from an initial or previously loaded state.
+  pub fn new(registry: &'a dyn DsRegistry, state: RolloverState) -> Self {
+    Self {
+      registry,
+      state,
+      state_file: None,
+    }
+  }
+
+  /// Resumes a rollover from a state file, returning `Ok(None)` if the file does not exist.
+  pub fn resume(registry: &'a dyn DsRegistry, path: impl AsRef<Path>) -> Result<Option<Self>> {
+    let path = path.as_ref();
+    if !path.exists() {
+      return Ok(None);
+    }
+    let state = RolloverState::load(path)?;
+    Ok(Some(Self::new(registry, state).with_state_file(path)))
+  }
+
+  /// Persists the state to `path` after every completed step.
+  pub fn with_state_file(mut self, path: impl Into<PathBuf>) -> Self {
+    self.state_file = Some(path.into());
+    self
+  }
+
+  /// The current state of the rollover.
+  pub fn state(&self) -> &RolloverState {
+    &self.state
+  }
+
+  /// Returns the remaining actions without performing them.
+  ///
+  /// Before the new DS record is published, the old records are looked up at
+  /// the registry so the plan shows exactly what will be removed.
+  pub async fn plan(&self) -> Result<Vec<RolloverAction>> {
+    let state = &self.state;
+    let old_ds = match state.step {
+      RolloverStep::PublishNew => self.current_old_ds().await?,
+      _ => state.old_ds.clone(),
+    };
+    if state.step != RolloverStep::Done {
+      check_removals(self.registry, &state.domain, &old_ds, std::slice::from_ref(&state.new_ds))?;
+    }
+    let mut actions = Vec::new();
+    if state.step == RolloverStep::PublishNew {
+      actions.push(RolloverAction::Publish(state.new_ds.clone()));
+    }
+    match &state.step {
+      RolloverStep::PublishNew => actions.push(RolloverAction::Wait {
+        until: None,
+        duration: state.parent_ttl,
+      }),
+      RolloverStep::WaitParentTtl { until } => actions.push(RolloverAction::Wait {
+        until: Some(*until),
+        duration: remaining(*until),
+      }),
+      _ => {}
+    }
+    if matches!(
+      state.step,
+      RolloverStep::PublishNew | RolloverStep::WaitParentTtl { .. } | RolloverStep::Verify
+    ) {
+      actions.push(RolloverAction::Verify(state.new_ds.clone()));
+    }
+    if state.step != RolloverStep::Done {
+      actions.extend(old_ds.into_iter().map(RolloverAction::Remove));
+    }
+    Ok(actions)
+  }
+
+  /// Runs the remaining steps until the rollover is done.
+  pub async fn run(&mut self) -> Result<()> {
+    while self.state.step != RolloverStep::Done {
+      self.step().await?;
+    }
+    Ok(())
+  }
+
+  /// Performs the next step and persists the resulting state.
+  pub async fn step(&mut self) -> Result<&RolloverStep> {
+    let domain = self.state.domain.clone();
+    let next = match &self.state.step {
+      RolloverStep::PublishNew => {
+        let existing = self.registry.list_ds(&domain).await?;
+        let old_ds: Vec<DsRecord> = existing.iter().filter(|ds| *ds != &self.state.new_ds).cloned().collect();
+        // Refuse before publishing anything if the old records could not be removed later.
+        check_removals(self.registry, &domain, &old_ds, std::slice::from_ref(&self.state.new_ds))?;
+        // A retry after a crash may find the record already published.
+        if !existing.contains(&self.state.new_ds) {
+          self.registry.add_ds(&domain, &self.state.new_ds).await?;
+        }
+        self.state.old_ds = old_ds;
+        let parent_ttl = chrono::Duration::from_std(self.state.parent_ttl)
+          .map_err(|_| Error::InvalidInput("Parent TTL is too large".to_string()))?;
+        RolloverStep::WaitParentTtl {
+          until: Utc::now() + parent_ttl,
+        }
+      }
+      RolloverStep::WaitParentTtl { until } => {
+        tokio::time::sleep(remaining(*until)).await;
+        RolloverStep::Verify
+      }
+      RolloverStep::Verify => {
+        let published = self.registry.list_ds(&domain).await?;
+        if !published.contains(&self.state.new_ds) {
+          return Err(Error::Api(format!(
+            "New DS record {} is not published for {}; not removing old records",
+            self.state.new_ds, domain
+          )));
+        }
+        RolloverStep::RemoveOld
+      }
+      RolloverStep::RemoveOld => {
+        let published = self.registry.list_ds(&domain).await?;
+        // Skip records already removed by an earlier, interrupted run.
+        let old_ds: Vec<DsRecord> = self.state.old_ds.iter().filter(|ds| published.contains(ds)).cloned().collect();
+        check_removals(self.registry, &domain, &old_ds, std::slice::from_ref(&self.state.new_ds))?;
+        for ds in &old_ds {
+          self.registry.remove_ds(&domain, ds).await?;
+        }
+        RolloverStep::Done
+      }
+      RolloverStep::Done => RolloverStep::Done,
+    };
+    self.state.step = next;
+    if let Some(path) = &self.state_file {
+      self.state.save(path)?;
+    }
+    Ok(&self.state.step)
+  }
+
+  async fn current_old_ds(&self) -> Result<Vec<DsRecord>> {
+    let existing = self.registry.list_ds(&self.state.domain).await?;
+    Ok(existing.into_iter().filter(|ds| ds != &self.state.new_ds).collect())
+  }
+}
+
+/// The time left until `until`, or zero if it has passed.
+fn remaining(until: DateTime<Utc>) -> Duration {
+  (until - Utc::now()).to_std().unwrap_or(Duration::ZERO)
+}
+
+mod duration_secs {
+  use serde::{Deserialize, Deserializer, Serializer};
+  use std::time::Duration;
+
+  pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_secs())
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    u64::deserialize(deserializer).map(Duration::from_secs)
+  }
+}
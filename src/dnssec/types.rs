@@ -0,0 +1,398 @@
+//! Contains the provider-agnostic DNSSEC types and DS digest computation.
+
+use crate::{Error, Result};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha384};
+use std::fmt;
+use std::str::FromStr;
+
+// --- Algorithm Enums ---
+
+/// A DNSSEC signing algorithm, as registered with IANA.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "u8", into = "u8")]
+pub enum DnssecAlgorithm {
+  RsaMd5,
+  Dsa,
+  RsaSha1,
+  DsaNsec3Sha1,
+  RsaSha1Nsec3Sha1,
+  RsaSha256,
+  RsaSha512,
+  EccGost,
+  EcdsaP256Sha256,
+  EcdsaP384Sha384,
+  Ed25519,
+  Ed448,
+  /// Any algorithm number without a dedicated variant (including 0, the
+  /// RFC 8078 "delete" signal).
+  Other(u8),
+}
+
+impl From<u8> for DnssecAlgorithm {
+  fn from(value: u8) -> Self {
+    match value {
+      1 => Self::RsaMd5,
+      3 => Self::Dsa,
+      5 => Self::RsaSha1,
+      6 => Self::DsaNsec3Sha1,
+      7 => Self::RsaSha1Nsec3Sha1,
+      8 => Self::RsaSha256,
+      10 => Self::RsaSha512,
+      12 => Self::EccGost,
+      13 => Self::EcdsaP256Sha256,
+      14 => Self::EcdsaP384Sha384,
+      15 => Self::Ed25519,
+      16 => Self::Ed448,
+      other => Self::Other(other),
+    }
+  }
+}
+
+impl From<DnssecAlgorithm> for u8 {
+  fn from(value: DnssecAlgorithm) -> Self {
+    match value {
+      DnssecAlgorithm::RsaMd5 => 1,
+      DnssecAlgorithm::Dsa => 3,
+      DnssecAlgorithm::RsaSha1 => 5,
+      DnssecAlgorithm::DsaNsec3Sha1 => 6,
+      DnssecAlgorithm::RsaSha1Nsec3Sha1 => 7,
+      DnssecAlgorithm::RsaSha256 => 8,
+      DnssecAlgorithm::RsaSha512 => 10,
+      DnssecAlgorithm::EccGost => 12,
+      DnssecAlgorithm::EcdsaP256Sha256 => 13,
+      DnssecAlgorithm::EcdsaP384Sha384 => 14,
+      DnssecAlgorithm::Ed25519 => 15,
+      DnssecAlgorithm::Ed448 => 16,
+      DnssecAlgorithm::Other(other) => other,
+    }
+  }
+}
+
+impl FromStr for DnssecAlgorithm {
+  type Err = Error;
+
+  /// Accepts either the algorithm number or its IANA mnemonic (e.g., "ECDSAP256SHA256").
+  fn from_str(s: &str) -> Result<Self> {
+    if let Ok(number) = s.parse::<u8>() {
+      return Ok(Self::from(number));
+    }
+    match s.to_ascii_uppercase().as_str() {
+      "RSAMD5" => Ok(Self::RsaMd5),
+      "DSA" => Ok(Self::Dsa),
+      "RSASHA1" => Ok(Self::RsaSha1),
+      "DSA-NSEC3-SHA1" => Ok(Self::DsaNsec3Sha1),
+      "RSASHA1-NSEC3-SHA1" => Ok(Self::RsaSha1Nsec3Sha1),
+      "RSASHA256" => Ok(Self::RsaSha256),
+      "RSASHA512" => Ok(Self::RsaSha512),
+      "ECC-GOST" => Ok(Self::EccGost),
+      "ECDSAP256SHA256" => Ok(Self::EcdsaP256Sha256),
+      "ECDSAP384SHA384" => Ok(Self::EcdsaP384Sha384),
+      "ED25519" => Ok(Self::Ed25519),
+      "ED448" => Ok(Self::Ed448),
+      _ => Err(Error::InvalidInput(format!("'{}' is not a DNSSEC algorithm", s))),
+    }
+  }
+}
+
+/// The digest algorithm used by a DS record.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "u8", into = "u8")]
+pub enum DigestType {
+  Sha1,
+  Sha256,
+  Gost,
+  Sha384,
+  /// Any digest type number without a dedicated variant.
+  Other(u8),
+}
+
+impl From<u8> for DigestType {
+  fn from(value: u8) -> Self {
+    match value {
+      1 => Self::Sha1,
+      2 => Self::Sha256,
+      3 => Self::Gost,
+      4 => Self::Sha384,
+      other => Self::Other(other),
+    }
+  }
+}
+
+impl From<DigestType> for u8 {
+  fn from(value: DigestType) -> Self {
+    match value {
+      DigestType::Sha1 => 1,
+      DigestType::Sha256 => 2,
+      DigestType::Gost => 3,
+      DigestType::Sha384 => 4,
+      DigestType::Other(other) => other,
+    }
+  }
+}
+
+// --- DS Record ---
+
+/// A Delegation Signer record as published at the parent zone.
+///
+/// The digest is stored as upper-case hexadecimal, which is how both
+/// supported registrars accept it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DsRecord {
+  pub key_tag: u16,
+  pub algorithm: DnssecAlgorithm,
+  pub digest_type: DigestType,
+  pub digest: String,
+}
+
+impl DsRecord {
+  /// Creates a DS record, validating and normalizing the hexadecimal digest.
+  pub fn new(key_tag: u16, algorithm: DnssecAlgorithm, digest_type: DigestType, digest: &str) -> Result<Self> {
+    let digest: String = digest.split_whitespace().collect::<String>().to_ascii_uppercase();
+    if digest.is_empty() || !digest.len().is_multiple_of(2) || !digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+      return Err(Error::InvalidInput(format!("'{}' is not a hexadecimal digest", digest)));
+    }
+    Ok(Self {
+      key_tag,
+      algorithm,
+      digest_type,
+      digest,
+    })
+  }
+
+  /// Parses DS (or CDS) RDATA in wire format.
+  pub fn from_rdata(rdata: &[u8]) -> Result<Self> {
+    if rdata.len() < 5 {
+      return Err(Error::InvalidInput("DS RDATA is too short".to_string()));
+    }
+    Ok(Self {
+      key_tag: u16::from_be_bytes([rdata[0], rdata[1]]),
+      algorithm: DnssecAlgorithm::from(rdata[2]),
+      digest_type: DigestType::from(rdata[3]),
+      digest: to_hex(&rdata[4..]),
+    })
+  }
+
+  /// Returns `true` if this is the RFC 8078 "delete DS" signal (`0 0 0 00`).
+  pub fn is_delete_signal(&self) -> bool {
+    self.key_tag == 0
+      && u8::from(self.algorithm) == 0
+      && u8::from(self.digest_type) == 0
+      && self.digest.bytes().all(|b| b == b'0')
+  }
+}
+
+impl fmt::Display for DsRecord {
+  /// Formats the record's RDATA in presentation format (e.g., "60485 5 2 D4B7...").
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} {} {} {}",
+      self.key_tag,
+      u8::from(self.algorithm),
+      u8::from(self.digest_type),
+      self.digest
+    )
+  }
+}
+
+impl FromStr for DsRecord {
+  type Err = Error;
+
+  /// Parses DS RDATA in presentation format, optionally preceded by the owner,
+  /// TTL, class and `DS` type (e.g., "example.com. 3600 IN DS 60485 5 2 D4B7...").
+  fn from_str(s: &str) -> Result<Self> {
+    let tokens = tokenize_rr(s);
+    let rdata = match tokens.iter().position(|t| t.eq_ignore_ascii_case("DS") || t.eq_ignore_ascii_case("CDS")) {
+      Some(index) => &tokens[index + 1..],
+      None => &tokens[..],
+    };
+    if rdata.len() < 4 {
+      return Err(Error::InvalidInput(format!("'{}' is not a DS record", s)));
+    }
+    let key_tag = rdata[0]
+      .parse::<u16>()
+      .map_err(|_| Error::InvalidInput(format!("'{}' is not a valid key tag", rdata[0])))?;
+    let algorithm = rdata[1].parse::<DnssecAlgorithm>()?;
+    let digest_type = rdata[2]
+      .parse::<u8>()
+      .map_err(|_| Error::InvalidInput(format!("'{}' is not a valid digest type", rdata[2])))?;
+    Self::new(key_tag, algorithm, DigestType::from(digest_type), &rdata[3..].concat())
+  }
+}
+
+// --- DNSKEY ---
+
+/// A DNSKEY resource record, used to derive the DS record for the parent zone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dnskey {
+  /// The zone the key belongs to (e.g., "example.com").
+  pub owner: String,
+  pub flags: u16,
+  pub protocol: u8,
+  pub algorithm: DnssecAlgorithm,
+  pub public_key: Vec<u8>,
+}
+
+impl Dnskey {
+  /// The Secure Entry Point flag, set on key-signing keys.
+  pub const FLAG_SEP: u16 = 0x0001;
+  /// The Zone Key flag, set on every key used to sign zone data.
+  pub const FLAG_ZONE: u16 = 0x0100;
+
+  /// Parses DNSKEY (or CDNSKEY) RDATA in wire format.
+  ///
+  /// # Arguments
+  /// * `owner` - The zone the key belongs to.
+  /// * `rdata` - The record's RDATA.
+  pub fn from_rdata(owner: &str, rdata: &[u8]) -> Result<Self> {
+    if rdata.len() < 4 {
+      return Err(Error::InvalidInput("DNSKEY RDATA is too short".to_string()));
+    }
+    Ok(Self {
+      owner: owner.to_string(),
+      flags: u16::from_be_bytes([rdata[0], rdata[1]]),
+      protocol: rdata[2],
+      algorithm: DnssecAlgorithm::from(rdata[3]),
+      public_key: rdata[4..].to_vec(),
+    })
+  }
+
+  /// Returns `true` if this is the RFC 8078 "delete DS" signal (`0 3 0 AA==`).
+  pub fn is_delete_signal(&self) -> bool {
+    self.flags == 0 && self.protocol == 3 && u8::from(self.algorithm) == 0 && self.public_key == [0]
+  }
+
+  /// Returns `true` if the key has the Secure Entry Point flag (i.e., it is a KSK).
+  pub fn is_sep(&self) -> bool {
+    self.flags & Self::FLAG_SEP != 0
+  }
+
+  /// The key's RDATA in wire format.
+  pub fn rdata(&self) -> Vec<u8> {
+    let mut rdata = Vec::with_capacity(4 + self.public_key.len());
+    rdata.extend_from_slice(&self.flags.to_be_bytes());
+    rdata.push(self.protocol);
+    rdata.push(self.algorithm.into());
+    rdata.extend_from_slice(&self.public_key);
+    rdata
+  }
+
+  /// Computes the key tag as defined in RFC 4034, Appendix B.
+  pub fn key_tag(&self) -> u16 {
+    if self.algorithm == DnssecAlgorithm::RsaMd5 {
+      // RSA/MD5 uses the most significant 16 bits of the least significant 24 bits of the modulus.
+      let key = &self.public_key;
+      return match key.len() {
+        len if len >= 3 => u16::from_be_bytes([key[len - 3], key[len - 2]]),
+        _ => 0,
+      };
+    }
+    let mut accumulator: u32 = 0;
+    for (index, byte) in self.rdata().iter().enumerate() {
+      accumulator += if index & 1 == 1 { *byte as u32 } else { (*byte as u32) << 8 };
+    }
+    accumulator += (accumulator >> 16) & 0xFFFF;
+    (accumulator & 0xFFFF) as u16
+  }
+
+  /// Computes the DS record for this key using the given digest type.
+  ///
+  /// SHA-256 and SHA-384 are supported; other digest types return
+  /// `Error::Unsupported`.
+  pub fn to_ds(&self, digest_type: DigestType) -> Result<DsRecord> {
+    let mut input = owner_to_wire(&self.owner)?;
+    input.extend_from_slice(&self.rdata());
+    let digest = match digest_type {
+      DigestType::Sha256 => to_hex(&Sha256::digest(&input)),
+      DigestType::Sha384 => to_hex(&Sha384::digest(&input)),
+      other => {
+        return Err(Error::Unsupported(format!(
+          "Computing DS digests of type {} is not supported",
+          u8::from(other)
+        )));
+      }
+    };
+    Ok(DsRecord {
+      key_tag: self.key_tag(),
+      algorithm: self.algorithm,
+      digest_type,
+      digest,
+    })
+  }
+}
+
+impl FromStr for Dnskey {
+  type Err = Error;
+
+  /// Parses a DNSKEY (or CDNSKEY) record in zone-file presentation format, as
+  /// produced by signers such as `dnssec-keygen` or `ldns-keygen`.
+  ///
+  /// The owner, optional TTL and class must precede the type. Comments and
+  /// parentheses spanning multiple lines are accepted.
+  fn from_str(s: &str) -> Result<Self> {
+    let tokens = tokenize_rr(s);
+    let type_index = tokens
+      .iter()
+      .position(|t| t.eq_ignore_ascii_case("DNSKEY") || t.eq_ignore_ascii_case("CDNSKEY"))
+      .ok_or_else(|| Error::InvalidInput("Expected a DNSKEY record".to_string()))?;
+    if type_index == 0 {
+      return Err(Error::InvalidInput("DNSKEY record is missing its owner name".to_string()));
+    }
+    let rdata = &tokens[type_index + 1..];
+    if rdata.len() < 4 {
+      return Err(Error::InvalidInput("DNSKEY record is missing RDATA fields".to_string()));
+    }
+    let flags = rdata[0]
+      .parse::<u16>()
+      .map_err(|_| Error::InvalidInput(format!("'{}' is not valid DNSKEY flags", rdata[0])))?;
+    let protocol = rdata[1]
+      .parse::<u8>()
+      .map_err(|_| Error::InvalidInput(format!("'{}' is not a valid DNSKEY protocol", rdata[1])))?;
+    let algorithm = rdata[2].parse::<DnssecAlgorithm>()?;
+    let public_key = BASE64
+      .decode(rdata[3..].concat())
+      .map_err(|e| Error::InvalidInput(format!("DNSKEY public key is not valid base64: {}", e)))?;
+    Ok(Self {
+      owner: tokens[0].clone(),
+      flags,
+      protocol,
+      algorithm,
+      public_key,
+    })
+  }
+}
+
+// --- Helpers ---
+
+/// Splits a presentation-format record into tokens, dropping comments and parentheses.
+fn tokenize_rr(s: &str) -> Vec<String> {
+  s.lines()
+    .map(|line| line.split(';').next().unwrap_or_default())
+    .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == '(' || c == ')'))
+    .filter(|token| !token.is_empty())
+    .map(str::to_string)
+    .collect()
+}
+
+/// Encodes a domain name in canonical (lower-case) DNS wire format.
+fn owner_to_wire(owner: &str) -> Result<Vec<u8>> {
+  let owner = owner.trim_end_matches('.').to_ascii_lowercase();
+  let mut wire = Vec::with_capacity(owner.len() + 2);
+  if !owner.is_empty() {
+    for label in owner.split('.') {
+      if label.is_empty() || label.len() > 63 {
+        return Err(Error::InvalidInput(format!("'{}' is not a valid owner name", owner)));
+      }
+      wire.push(label.len() as u8);
+      wire.extend_from_slice(label.as_bytes());
+    }
+  }
+  wire.push(0);
+  Ok(wire)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+  bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
@@ -0,0 +1,253 @@
+//! # Domain Names
+//!
+//! A validated, normalised domain name.
+//!
+//! Registrar APIs expect lower-case ASCII names without a trailing dot, while
+//! names arrive from users and configuration files as `Bücher.Example.`.
+//! [`DomainName`] converts internationalised names to their ASCII (punycode)
+//! form, lower-cases them, drops a trailing dot and checks the length and label
+//! rules. It also splits a name into its public suffix, registrable domain and
+//! subdomain using the ICANN section of the Public Suffix List, which is
+//! embedded in the crate.
+//!
+//! Scoped client constructors, such as `Porkbun::dns` or `NameDotCom::dns`,
+//! take a `&DomainName`, so every request is made with the normalised name.
+//! `DomainName` also dereferences to `&str`.
+//!
+//! ## Example
+//!
+//! ```
+//! use registrar::domain_name::DomainName;
+//!
+//! let name = DomainName::new("WWW.Bücher.co.uk.")?;
+//! assert_eq!(name.as_str(), "www.xn--bcher-kva.co.uk");
+//! assert_eq!(name.public_suffix(), "co.uk");
+//! assert_eq!(name.registrable(), Some("xn--bcher-kva.co.uk"));
+//! assert_eq!(name.subdomain(), Some("www"));
+//! assert_eq!(name.to_unicode(), "www.bücher.co.uk");
+//!
+//! # #[cfg(feature = "porkbun")]
+//! # {
+//! let client = registrar::porkbun::Porkbun::new("pk1_...".to_string(), "sk1_...".to_string());
+//! let dns = client.dns(&name);
+//! # }
+//! # Ok::<(), registrar::Error>(())
+//! ```
+
+use crate::{Error, Result};
+use publicsuffix::{IcannList, Psl};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// The longest name allowed, in ASCII characters without the trailing dot.
+pub const MAX_LENGTH: usize = 253;
+
+/// The longest label allowed.
+pub const MAX_LABEL_LENGTH: usize = 63;
+
+/// The ICANN section of the Public Suffix List. Private suffixes such as
+/// `github.io` are left out, since registrars sell names under ICANN suffixes.
+static PUBLIC_SUFFIXES: LazyLock<IcannList> = LazyLock::new(|| {
+  include_str!("public_suffix_list.dat").parse().expect("the embedded public suffix list is valid")
+});
+
+/// A domain name in lower-case ASCII form, without a trailing dot.
+///
+/// Labels may contain letters, digits, hyphens (not at either end) and
+/// underscores, so service names such as `_acme-challenge.example.com` are
+/// accepted.
+#[derive(Clone)]
+pub struct DomainName {
+  name: String,
+  /// Where the public suffix starts.
+  suffix: usize,
+  /// Where the registrable domain starts, if the name is below a public suffix.
+  registrable: Option<usize>,
+}
+
+impl DomainName {
+  /// Validates and normalises a domain name.
+  ///
+  /// # Arguments
+  /// * `name` - The name, in Unicode or ASCII form, optionally ending with a dot.
+  pub fn new(name: &str) -> Result<Self> {
+    let invalid = |reason: String| Error::InvalidInput(format!("'{}' is not a valid domain name: {}", name, reason));
+    let trimmed = name.strip_suffix('.').unwrap_or(name);
+    if trimmed.is_empty() {
+      return Err(invalid("it is empty".to_string()));
+    }
+    let ascii = idna::domain_to_ascii(trimmed).map_err(|_| invalid("it cannot be converted to ASCII".to_string()))?;
+    if ascii.len() > MAX_LENGTH {
+      return Err(invalid(format!("it is longer than {} characters", MAX_LENGTH)));
+    }
+    for label in ascii.split('.') {
+      if label.is_empty() {
+        return Err(invalid("it has an empty label".to_string()));
+      }
+      if label.len() > MAX_LABEL_LENGTH {
+        return Err(invalid(format!("the label '{}' is longer than {} characters", label, MAX_LABEL_LENGTH)));
+      }
+      if label.starts_with('-') || label.ends_with('-') {
+        return Err(invalid(format!("the label '{}' starts or ends with a hyphen", label)));
+      }
+      if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        let reason = format!("the label '{}' contains a character other than a letter, digit, '-' or '_'", label);
+        return Err(invalid(reason));
+      }
+    }
+    Ok(Self::split(ascii))
+  }
+
+  /// Locates the public suffix and registrable domain of a valid ASCII name.
+  fn split(name: String) -> Self {
+    let (suffix, registrable) = match PUBLIC_SUFFIXES.domain(name.as_bytes()) {
+      Some(domain) => (domain.suffix().as_bytes().len(), Some(domain.as_bytes().len())),
+      None => (PUBLIC_SUFFIXES.suffix(name.as_bytes()).map_or(name.len(), |suffix| suffix.as_bytes().len()), None),
+    };
+    Self { suffix: name.len() - suffix, registrable: registrable.map(|length| name.len() - length), name }
+  }
+
+  /// The name in lower-case ASCII form.
+  pub fn as_str(&self) -> &str {
+    &self.name
+  }
+
+  /// The name with internationalised labels converted back to Unicode.
+  pub fn to_unicode(&self) -> String {
+    idna::domain_to_unicode(&self.name).0
+  }
+
+  /// The public suffix, such as `com` or `co.uk`. Names under a top-level
+  /// domain missing from the list use the top-level domain.
+  pub fn public_suffix(&self) -> &str {
+    &self.name[self.suffix..]
+  }
+
+  /// The domain that can be registered: the public suffix and one more label,
+  /// such as `example.co.uk`. `None` when the name is itself a public suffix.
+  pub fn registrable(&self) -> Option<&str> {
+    self.registrable.map(|start| &self.name[start..])
+  }
+
+  /// The registrable domain as a `DomainName`.
+  pub fn registrable_domain(&self) -> Option<DomainName> {
+    let start = self.registrable?;
+    Some(Self { name: self.name[start..].to_string(), suffix: self.suffix - start, registrable: Some(0) })
+  }
+
+  /// The labels in front of the registrable domain, such as `www` in
+  /// `www.example.com`. `None` for a registrable domain or a public suffix.
+  pub fn subdomain(&self) -> Option<&str> {
+    self.registrable.filter(|start| *start > 0).map(|start| &self.name[..start - 1])
+  }
+
+  /// Whether the name is a registrable domain, with no subdomain.
+  pub fn is_registrable(&self) -> bool {
+    self.registrable == Some(0)
+  }
+
+  /// Whether this name equals `zone` or lies below it.
+  pub fn is_within(&self, zone: &DomainName) -> bool {
+    self.name == zone.name || self.name.strip_suffix(zone.as_str()).is_some_and(|prefix| prefix.ends_with('.'))
+  }
+}
+
+impl Deref for DomainName {
+  type Target = str;
+
+  fn deref(&self) -> &str {
+    &self.name
+  }
+}
+
+impl AsRef<str> for DomainName {
+  fn as_ref(&self) -> &str {
+    &self.name
+  }
+}
+
+impl fmt::Display for DomainName {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.name)
+  }
+}
+
+impl fmt::Debug for DomainName {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("DomainName").field(&self.name).finish()
+  }
+}
+
+impl PartialEq for DomainName {
+  fn eq(&self, other: &Self) -> bool {
+    self.name == other.name
+  }
+}
+
+impl Eq for DomainName {}
+
+impl Hash for DomainName {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.name.hash(state)
+  }
+}
+
+impl PartialOrd for DomainName {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for DomainName {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.name.cmp(&other.name)
+  }
+}
+
+impl FromStr for DomainName {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    Self::new(s)
+  }
+}
+
+impl TryFrom<&str> for DomainName {
+  type Error = Error;
+
+  fn try_from(value: &str) -> Result<Self> {
+    Self::new(value)
+  }
+}
+
+impl TryFrom<String> for DomainName {
+  type Error = Error;
+
+  fn try_from(value: String) -> Result<Self> {
+    Self::new(&value)
+  }
+}
+
+impl From<DomainName> for String {
+  fn from(name: DomainName) -> String {
+    name.name
+  }
+}
+
+impl Serialize for DomainName {
+  fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&self.name)
+  }
+}
+
+impl<'de> Deserialize<'de> for DomainName {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    Self::new(&name).map_err(serde::de::Error::custom)
+  }
+}
@@ -0,0 +1,267 @@
+//! # dyndns2 Server
+//!
+//! An HTTP server speaking the dyndns2 update protocol used by routers, NAS
+//! boxes and clients such as ddclient and inadyn.
+//!
+//! Devices send `GET /nic/update?hostname=home.example.com&myip=198.51.100.7`
+//! with HTTP Basic credentials. Each account may only update the hostnames
+//! listed for it in the [`Dyndns2Config`], so devices never hold the
+//! registrar's API keys. Updates are applied through any [`DnsRecords`]
+//! implementation and answered with the standard `good`, `nochg`, `badauth`,
+//! `nohost`, `notfqdn`, `numhost` and `dnserr` codes, one line per hostname.
+//!
+//! When `myip` is omitted, the address the request came from is used. Both
+//! an IPv4 and an IPv6 address may be given, separated by a comma or through
+//! the `myipv6` parameter.
+//!
+//! To use this module, you must enable the `dyndns2` feature in your `Cargo.toml`.
+
+use crate::ddns::{AddressChange, set_address};
+use crate::records::DnsRecords;
+use crate::{Error, Result};
+use axum::Router;
+use axum::extract::{ConnectInfo, Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+pub mod types;
+
+pub use types::{Dyndns2Config, Dyndns2User, UpdateStatus};
+
+/// The most hostnames accepted in a single request.
+const MAX_HOSTNAMES: usize = 20;
+
+/// A callback receiving the hostname and error of every failed registrar update.
+pub type ErrorLog = Arc<dyn Fn(&str, &Error) + Send + Sync>;
+
+/// The query parameters of an update request.
+#[derive(Deserialize, Debug, Default)]
+pub struct UpdateQuery {
+  /// One or more comma-separated hostnames.
+  #[serde(default)]
+  pub hostname: Option<String>,
+  /// One or two comma-separated addresses. Defaults to the client's address.
+  #[serde(default)]
+  pub myip: Option<String>,
+  /// An IPv6 address, as sent by some clients in addition to `myip`.
+  #[serde(default)]
+  pub myipv6: Option<String>,
+}
+
+/// A dyndns2 server backed by a registrar's DNS records.
+#[derive(Clone)]
+pub struct Dyndns2Server {
+  records: Arc<dyn DnsRecords>,
+  config: Arc<Dyndns2Config>,
+  users: Arc<HashMap<String, Dyndns2User>>,
+  error_log: Option<ErrorLog>,
+}
+
+impl Dyndns2Server {
+  /// Creates a server, validating that every configured hostname lies within a configured zone.
+  ///
+  /// # Arguments
+  /// * `records` - The registrar hosting the zones.
+  /// * `config` - The zones and accounts to serve.
+  pub fn new(records: Arc<dyn DnsRecords>, mut config: Dyndns2Config) -> Result<Self> {
+    for zone in &mut config.zones {
+      *zone = zone.trim_end_matches('.').to_ascii_lowercase();
+    }
+    let mut users = HashMap::new();
+    for user in &config.users {
+      if user.password.is_none() && user.password_sha256.is_none() {
+        return Err(Error::InvalidInput(format!("dyndns2 user '{}' has no password", user.username)));
+      }
+      for host in &user.hosts {
+        if zone_for(&config.zones, host).is_none() {
+          return Err(Error::InvalidInput(format!(
+            "dyndns2 host '{}' of user '{}' is not within a configured zone",
+            host, user.username
+          )));
+        }
+      }
+      if users.insert(user.username.clone(), user.clone()).is_some() {
+        return Err(Error::InvalidInput(format!("dyndns2 user '{}' is listed twice", user.username)));
+      }
+    }
+    Ok(Self {
+      records,
+      config: Arc::new(config),
+      users: Arc::new(users),
+      error_log: None,
+    })
+  }
+
+  /// Reports registrar errors, which clients only see as `dnserr`, to `log`.
+  pub fn with_error_log(mut self, log: ErrorLog) -> Self {
+    self.error_log = Some(log);
+    self
+  }
+
+  /// Applies an update request and returns one status per hostname.
+  ///
+  /// # Arguments
+  /// * `credentials` - The username and password from the request, if any.
+  /// * `query` - The request's query parameters.
+  /// * `client_ip` - The address the request came from, used when `myip` is absent.
+  pub async fn update(
+    &self,
+    credentials: Option<(&str, &str)>,
+    query: &UpdateQuery,
+    client_ip: IpAddr,
+  ) -> Vec<UpdateStatus> {
+    let user = credentials.and_then(|(username, password)| {
+      self.users.get(username).filter(|user| user.verify(password))
+    });
+    let Some(user) = user else {
+      return vec![UpdateStatus::BadAuth];
+    };
+    let hostnames: Vec<String> = query
+      .hostname
+      .as_deref()
+      .unwrap_or_default()
+      .split(',')
+      .map(|hostname| hostname.trim().trim_end_matches('.').to_ascii_lowercase())
+      .filter(|hostname| !hostname.is_empty())
+      .collect();
+    if hostnames.is_empty() {
+      return vec![UpdateStatus::NotFqdn];
+    }
+    if hostnames.len() > MAX_HOSTNAMES {
+      return vec![UpdateStatus::NumHost];
+    }
+    let Some(ips) = requested_ips(query, client_ip) else {
+      // An unparsable address is reported like the other malformed-request codes.
+      return vec![UpdateStatus::NotFqdn; hostnames.len()];
+    };
+
+    let mut statuses = Vec::with_capacity(hostnames.len());
+    for hostname in &hostnames {
+      statuses.push(self.update_host(user, hostname, &ips).await);
+    }
+    statuses
+  }
+
+  async fn update_host(&self, user: &Dyndns2User, hostname: &str, ips: &[IpAddr]) -> UpdateStatus {
+    if !hostname.contains('.') {
+      return UpdateStatus::NotFqdn;
+    }
+    if !user.may_update(hostname) {
+      return UpdateStatus::NoHost;
+    }
+    let Some(zone) = zone_for(&self.config.zones, hostname) else {
+      return UpdateStatus::NoHost;
+    };
+    let host = hostname.strip_suffix(zone).unwrap_or_default().trim_end_matches('.');
+    let mut changed = false;
+    for ip in ips {
+      match set_address(self.records.as_ref(), zone, host, *ip, self.config.ttl).await {
+        Ok(change) => changed |= change != AddressChange::Unchanged,
+        Err(e) => {
+          if let Some(log) = &self.error_log {
+            log(hostname, &e);
+          }
+          return UpdateStatus::DnsErr;
+        }
+      }
+    }
+    if changed {
+      UpdateStatus::Good(ips.to_vec())
+    } else {
+      UpdateStatus::NoChange(ips.to_vec())
+    }
+  }
+
+  /// Builds the HTTP routes: `/nic/update` and its alias `/update`.
+  pub fn router(self) -> Router {
+    Router::new()
+      .route("/nic/update", get(handle_update))
+      .route("/update", get(handle_update))
+      .with_state(self)
+  }
+
+  /// Serves requests on `listener` until the future is dropped.
+  pub async fn serve(self, listener: TcpListener) -> Result<()> {
+    let app = self.router().into_make_service_with_connect_info::<SocketAddr>();
+    axum::serve(listener, app).await?;
+    Ok(())
+  }
+}
+
+async fn handle_update(
+  State(server): State<Dyndns2Server>,
+  ConnectInfo(peer): ConnectInfo<SocketAddr>,
+  Query(query): Query<UpdateQuery>,
+  headers: HeaderMap,
+) -> Response {
+  let mut client_ip = peer.ip().to_canonical();
+  if server.config.trust_forwarded_for {
+    let forwarded = headers
+      .get("x-forwarded-for")
+      .and_then(|value| value.to_str().ok())
+      .and_then(|value| value.split(',').next())
+      .and_then(|value| value.trim().parse::<IpAddr>().ok());
+    client_ip = forwarded.unwrap_or(client_ip);
+  }
+  let credentials = basic_auth(&headers);
+  let statuses = server
+    .update(credentials.as_ref().map(|(u, p)| (u.as_str(), p.as_str())), &query, client_ip)
+    .await;
+
+  let body = statuses.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n");
+  if statuses == [UpdateStatus::BadAuth] {
+    let challenge = HeaderValue::from_static("Basic realm=\"dyndns2\"");
+    return (StatusCode::UNAUTHORIZED, [(header::WWW_AUTHENTICATE, challenge)], body).into_response();
+  }
+  body.into_response()
+}
+
+/// Extracts HTTP Basic credentials.
+fn basic_auth(headers: &HeaderMap) -> Option<(String, String)> {
+  let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+  let (scheme, encoded) = value.split_once(' ')?;
+  if !scheme.eq_ignore_ascii_case("basic") {
+    return None;
+  }
+  let decoded = String::from_utf8(STANDARD.decode(encoded.trim()).ok()?).ok()?;
+  let (username, password) = decoded.split_once(':')?;
+  Some((username.to_string(), password.to_string()))
+}
+
+/// The addresses to publish: at most one per family, defaulting to the client's address.
+fn requested_ips(query: &UpdateQuery, client_ip: IpAddr) -> Option<Vec<IpAddr>> {
+  let mut ips: Vec<IpAddr> = Vec::new();
+  let values = [query.myip.as_deref(), query.myipv6.as_deref()];
+  for value in values.into_iter().flatten().flat_map(|value| value.split(',')) {
+    let value = value.trim();
+    if value.is_empty() {
+      continue;
+    }
+    let ip = value.parse::<IpAddr>().ok()?.to_canonical();
+    if !ips.iter().any(|existing| existing.is_ipv4() == ip.is_ipv4()) {
+      ips.push(ip);
+    }
+  }
+  if ips.is_empty() {
+    ips.push(client_ip);
+  }
+  Some(ips)
+}
+
+/// The longest zone containing `hostname`.
+fn zone_for<'a>(zones: &'a [String], hostname: &str) -> Option<&'a str> {
+  let hostname = hostname.trim_end_matches('.').to_ascii_lowercase();
+  zones
+    .iter()
+    .filter(|zone| hostname == **zone || hostname.ends_with(&format!(".{}", zone)))
+    .max_by_key(|zone| zone.len())
+    .map(String::as_str)
+}
@@ -0,0 +1,116 @@
+//! Contains the dyndns2 server configuration and response types.
+
+use crate::secret::constant_time_eq;
+use crate::{Result, config};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::net::IpAddr;
+use std::path::Path;
+
+/// The configuration of a dyndns2 server.
+///
+/// ```yaml
+/// zones:
+///   - example.com
+/// ttl: 300
+/// users:
+///   - username: router
+///     password_sha256: "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8"
+///     hosts:
+///       - home.example.com
+///       - vpn.example.com
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct Dyndns2Config {
+  /// The registered domains hosted at the registrar. Each hostname is updated in the longest zone containing it.
+  pub zones: Vec<String>,
+  /// The accounts allowed to update hostnames.
+  pub users: Vec<Dyndns2User>,
+  /// The TTL of updated records. `None` uses the registrar's default.
+  #[serde(default)]
+  pub ttl: Option<u32>,
+  /// Takes the client address from the first `X-Forwarded-For` entry. Enable only behind a reverse proxy.
+  #[serde(default)]
+  pub trust_forwarded_for: bool,
+}
+
+impl Dyndns2Config {
+  /// Parses a configuration from YAML text.
+  pub fn from_yaml(yaml: &str) -> Result<Self> {
+    config::parse_yaml(yaml, "dyndns2")
+  }
+
+  /// Loads a configuration file. A file that cannot be read is an `Error::Io`.
+  pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+    config::load_yaml(path.as_ref(), "dyndns2")
+  }
+}
+
+/// An account allowed to update a fixed set of hostnames.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Dyndns2User {
+  pub username: String,
+  /// The password in plain text. Prefer `password_sha256`.
+  #[serde(default)]
+  pub password: Option<String>,
+  /// The hexadecimal SHA-256 digest of the password.
+  #[serde(default)]
+  pub password_sha256: Option<String>,
+  /// The fully qualified hostnames this account may update.
+  pub hosts: Vec<String>,
+}
+
+impl Dyndns2User {
+  /// Checks a password against the configured one.
+  pub(crate) fn verify(&self, password: &str) -> bool {
+    match (&self.password, &self.password_sha256) {
+      (Some(expected), _) => constant_time_eq(expected.as_bytes(), password.as_bytes()),
+      (None, Some(expected)) => {
+        let digest = Sha256::digest(password.as_bytes());
+        let digest: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+        constant_time_eq(expected.to_ascii_lowercase().as_bytes(), digest.as_bytes())
+      }
+      (None, None) => false,
+    }
+  }
+
+  /// Whether the account may update `hostname`.
+  pub(crate) fn may_update(&self, hostname: &str) -> bool {
+    self.hosts.iter().any(|host| host.trim_end_matches('.').eq_ignore_ascii_case(hostname))
+  }
+}
+
+/// A dyndns2 protocol response code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateStatus {
+  /// The update succeeded and at least one record changed.
+  Good(Vec<IpAddr>),
+  /// The records already held the addresses.
+  NoChange(Vec<IpAddr>),
+  /// The credentials were missing or wrong.
+  BadAuth,
+  /// The hostname is unknown or the account may not update it.
+  NoHost,
+  /// The hostname is missing or not fully qualified.
+  NotFqdn,
+  /// Too many hostnames were given in one request.
+  NumHost,
+  /// The registrar rejected the update.
+  DnsErr,
+}
+
+impl fmt::Display for UpdateStatus {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let join = |ips: &[IpAddr]| ips.iter().map(IpAddr::to_string).collect::<Vec<_>>().join(",");
+    match self {
+      UpdateStatus::Good(ips) => write!(f, "good {}", join(ips)),
+      UpdateStatus::NoChange(ips) => write!(f, "nochg {}", join(ips)),
+      UpdateStatus::BadAuth => f.write_str("badauth"),
+      UpdateStatus::NoHost => f.write_str("nohost"),
+      UpdateStatus::NotFqdn => f.write_str("notfqdn"),
+      UpdateStatus::NumHost => f.write_str("numhost"),
+      UpdateStatus::DnsErr => f.write_str("dnserr"),
+    }
+  }
+}
@@ -0,0 +1,387 @@
+//! # external-dns Webhook Provider
+//!
+//! Implements the HTTP protocol of external-dns's `webhook` provider, so
+//! that Kubernetes external-dns can manage zones hosted at a registrar.
+//!
+//! external-dns runs the provider as a sidecar and calls it on
+//! `http://localhost:8888`:
+//! - `GET /` negotiates the protocol and returns the managed domains.
+//! - `GET /records` lists the current records, grouped into endpoints.
+//! - `POST /records` applies a set of changes.
+//! - `POST /adjustendpoints` normalizes desired endpoints the way the
+//!   registrar will store them, so that plans converge.
+//!
+//! external-dns's TXT registry (`--registry=txt`) works unchanged: its
+//! ownership records are stored without the surrounding quotes, which some
+//! registrars would otherwise escape, and quoted again when listed, so the
+//! owner check always sees the value external-dns wrote.
+//!
+//! To use this module, you must enable the `external-dns` feature in your `Cargo.toml`.
+
+use crate::records::{DnsRecords, Record};
+use crate::{Error, Result};
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+pub mod types;
+
+pub use types::{Changes, DomainFilter, Endpoint, ProviderSpecificProperty};
+
+/// The media type of every webhook request and response.
+pub const MEDIA_TYPE: &str = "application/external.dns.webhook+json;version=1";
+
+/// The record types exchanged with external-dns.
+const SUPPORTED_TYPES: [&str; 8] = ["A", "AAAA", "CAA", "CNAME", "MX", "NS", "SRV", "TXT"];
+
+/// How a change treats the targets of an endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+  /// Adds the targets that do not exist yet.
+  Create,
+  /// Makes the targets the complete set of values.
+  Update,
+  /// Removes the targets.
+  Delete,
+}
+
+/// An external-dns webhook provider backed by a registrar's DNS records.
+#[derive(Clone)]
+pub struct ExternalDnsProvider {
+  records: Arc<dyn DnsRecords>,
+  zones: Vec<String>,
+  exclude: Vec<String>,
+  min_ttl: Option<u32>,
+}
+
+impl ExternalDnsProvider {
+  /// Creates a provider managing no zones.
+  ///
+  /// # Arguments
+  /// * `records` - The registrar hosting the zones.
+  pub fn new(records: Arc<dyn DnsRecords>) -> Self {
+    Self {
+      records,
+      zones: Vec::new(),
+      exclude: Vec::new(),
+      min_ttl: None,
+    }
+  }
+
+  /// Adds a registered domain to manage.
+  pub fn with_zone(mut self, zone: &str) -> Self {
+    self.zones.push(normalize_name(zone));
+    self
+  }
+
+  /// Leaves names at or below `domain` alone, even within a managed zone.
+  pub fn with_exclude(mut self, domain: &str) -> Self {
+    self.exclude.push(normalize_name(domain));
+    self
+  }
+
+  /// Raises endpoint TTLs below `min_ttl` to it, both when applying changes and
+  /// in [`ExternalDnsProvider::adjust_endpoints`], so external-dns plans with
+  /// the TTL that ends up stored. TTLs set through the
+  /// `external-dns.alpha.kubernetes.io/ttl` annotation may be below what the
+  /// registrar accepts.
+  pub fn with_min_ttl(mut self, min_ttl: u32) -> Self {
+    self.min_ttl = Some(min_ttl);
+    self
+  }
+
+  /// The managed domains, as returned during negotiation.
+  pub fn domain_filter(&self) -> DomainFilter {
+    DomainFilter {
+      include: self.zones.clone(),
+      exclude: self.exclude.clone(),
+    }
+  }
+
+  /// Lists the records of every managed zone, one endpoint per name and type.
+  ///
+  /// The SOA and apex NS records, which belong to the registrar, are left out.
+  pub async fn records(&self) -> Result<Vec<Endpoint>> {
+    let mut endpoints: BTreeMap<(String, String), Endpoint> = BTreeMap::new();
+    for zone in &self.zones {
+      for record in self.records.list_records(zone).await? {
+        let record_type = record.record_type.to_ascii_uppercase();
+        let fqdn = normalize_name(&record.fqdn(zone));
+        let protected = is_protected(&record.host, &record_type);
+        if !SUPPORTED_TYPES.contains(&record_type.as_str()) || protected || self.zone_for(&fqdn) != Some(zone) {
+          continue;
+        }
+        let endpoint = endpoints
+          .entry((fqdn.clone(), record_type.clone()))
+          .or_insert_with(|| Endpoint::new(&fqdn, &record_type, Vec::new()));
+        endpoint.targets.push(to_target(&record));
+        if let Some(ttl) = record.ttl {
+          endpoint.record_ttl = Some(endpoint.record_ttl.map_or(ttl, |current| current.min(ttl)));
+        }
+      }
+    }
+    Ok(endpoints.into_values().collect())
+  }
+
+  /// Applies deletions, then updates, then creations.
+  ///
+  /// Updates are applied against the current records, so `changes.update_old`
+  /// is not needed and stale values are removed even if external-dns did not know them.
+  /// Changes to the apex NS records are ignored, as they are never listed.
+  pub async fn apply_changes(&self, changes: &Changes) -> Result<()> {
+    let mut listings = HashMap::new();
+    let batches = [
+      (&changes.delete, Change::Delete),
+      (&changes.update_new, Change::Update),
+      (&changes.create, Change::Create),
+    ];
+    for (endpoints, change) in batches {
+      for endpoint in endpoints {
+        self.apply(&mut listings, endpoint, change).await?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Normalizes endpoints the way the registrar will store them.
+  ///
+  /// Names lose their trailing dot and are lowercased, TTLs are raised to the
+  /// minimum, and endpoints of unsupported record types are dropped.
+  pub fn adjust_endpoints(&self, endpoints: Vec<Endpoint>) -> Vec<Endpoint> {
+    endpoints
+      .into_iter()
+      .filter(|endpoint| SUPPORTED_TYPES.contains(&endpoint.record_type.to_ascii_uppercase().as_str()))
+      .map(|mut endpoint| {
+        endpoint.dns_name = normalize_name(&endpoint.dns_name);
+        endpoint.record_type = endpoint.record_type.to_ascii_uppercase();
+        endpoint.record_ttl = self.ttl(&endpoint);
+        if is_name_type(&endpoint.record_type) {
+          for target in &mut endpoint.targets {
+            *target = target.trim_end_matches('.').to_ascii_lowercase();
+          }
+        }
+        endpoint
+      })
+      .collect()
+  }
+
+  async fn apply(
+    &self,
+    listings: &mut HashMap<String, Vec<Record>>,
+    endpoint: &Endpoint,
+    change: Change,
+  ) -> Result<()> {
+    let name = normalize_name(&endpoint.dns_name);
+    let zone = self
+      .zone_for(&name)
+      .ok_or_else(|| Error::InvalidInput(format!("'{}' is not within a managed zone", name)))?
+      .to_string();
+    let record_type = endpoint.record_type.to_ascii_uppercase();
+    if !SUPPORTED_TYPES.contains(&record_type.as_str()) {
+      return Err(Error::Unsupported(format!("{} records cannot be managed through external-dns", record_type)));
+    }
+    let host = if name == zone {
+      String::new()
+    } else {
+      name[..name.len() - zone.len() - 1].to_string()
+    };
+    if is_protected(&host, &record_type) {
+      return Ok(());
+    }
+    if !listings.contains_key(&zone) {
+      listings.insert(zone.clone(), self.records.list_records(&zone).await?);
+    }
+    let existing = listings.get_mut(&zone).expect("listing was just inserted");
+    let current: Vec<Record> = existing
+      .iter()
+      .filter(|record| record.host.eq_ignore_ascii_case(&host) && record.record_type.eq_ignore_ascii_case(&record_type))
+      .cloned()
+      .collect();
+    let targeted = |record: &Record| {
+      let target = to_target(record);
+      endpoint.targets.iter().any(|wanted| same_target(&record_type, &target, wanted))
+    };
+
+    let doomed = current.iter().filter(|record| match change {
+      Change::Create => false,
+      Change::Update => !targeted(record),
+      Change::Delete => targeted(record),
+    });
+    for record in doomed {
+      self.records.delete_record(&zone, record.require_id()?).await?;
+      existing.retain(|other| other.id != record.id);
+    }
+    if change == Change::Delete {
+      return Ok(());
+    }
+
+    let ttl = self.ttl(endpoint);
+    for target in &endpoint.targets {
+      let found = current.iter().find(|record| same_target(&record_type, &to_target(record), target));
+      match found {
+        Some(record) if change == Change::Update && ttl.is_some() && record.ttl != ttl => {
+          let mut record = record.clone();
+          record.ttl = ttl;
+          self.records.update_record(&zone, &record).await?;
+          existing.retain(|other| other.id != record.id);
+          existing.push(record);
+        }
+        Some(_) => {}
+        None => {
+          let mut record = from_target(&host, &record_type, target)?;
+          record.ttl = ttl;
+          record.id = Some(self.records.create_record(&zone, &record).await?);
+          existing.push(record);
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// The TTL to publish for an endpoint. Zero means the registrar's default.
+  fn ttl(&self, endpoint: &Endpoint) -> Option<u32> {
+    let ttl = endpoint.record_ttl.filter(|ttl| *ttl > 0)?;
+    Some(self.min_ttl.map_or(ttl, |min_ttl| ttl.max(min_ttl)))
+  }
+
+  /// The longest managed zone containing `name`, unless the name is excluded.
+  fn zone_for(&self, name: &str) -> Option<&str> {
+    let within = |domain: &String| name == domain || name.ends_with(&format!(".{}", domain));
+    if self.exclude.iter().any(within) {
+      return None;
+    }
+    self
+      .zones
+      .iter()
+      .filter(|zone| within(zone))
+      .max_by_key(|zone| zone.len())
+      .map(String::as_str)
+  }
+
+  /// Builds the HTTP routes of the webhook protocol, plus `/healthz`.
+  pub fn router(self) -> Router {
+    Router::new()
+      .route("/", get(handle_negotiate))
+      .route("/records", get(handle_records).post(handle_apply_changes))
+      .route("/adjustendpoints", post(handle_adjust_endpoints))
+      .route("/healthz", get(|| async { "ok" }))
+      .with_state(self)
+  }
+
+  /// Serves requests on `listener` until the future is dropped.
+  pub async fn serve(self, listener: TcpListener) -> Result<()> {
+    axum::serve(listener, self.router()).await?;
+    Ok(())
+  }
+}
+
+async fn handle_negotiate(State(provider): State<ExternalDnsProvider>) -> Response {
+  webhook_json(&provider.domain_filter())
+}
+
+async fn handle_records(State(provider): State<ExternalDnsProvider>) -> Response {
+  match provider.records().await {
+    Ok(endpoints) => webhook_json(&endpoints),
+    Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+  }
+}
+
+async fn handle_apply_changes(State(provider): State<ExternalDnsProvider>, body: Bytes) -> Response {
+  let changes: Changes = match serde_json::from_slice(&body) {
+    Ok(changes) => changes,
+    Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+  };
+  match provider.apply_changes(&changes).await {
+    Ok(()) => StatusCode::NO_CONTENT.into_response(),
+    Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+  }
+}
+
+async fn handle_adjust_endpoints(State(provider): State<ExternalDnsProvider>, body: Bytes) -> Response {
+  match serde_json::from_slice::<Vec<Endpoint>>(&body) {
+    Ok(endpoints) => webhook_json(&provider.adjust_endpoints(endpoints)),
+    Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+  }
+}
+
+/// A JSON response with the webhook media type.
+fn webhook_json<T: Serialize>(value: &T) -> Response {
+  match serde_json::to_vec(value) {
+    Ok(body) => ([(header::CONTENT_TYPE, MEDIA_TYPE), (header::VARY, "Content-Type")], body).into_response(),
+    Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+  }
+}
+
+/// A record's value as external-dns expects it: MX and SRV targets begin
+/// with the priority, and TXT registry records are quoted.
+fn to_target(record: &Record) -> String {
+  let content = record.content.trim();
+  match record.record_type.to_ascii_uppercase().as_str() {
+    "MX" | "SRV" => format!("{} {}", record.priority.unwrap_or(0), content.trim_end_matches('.')),
+    "CNAME" | "NS" => content.trim_end_matches('.').to_string(),
+    "TXT" if unquote(content).starts_with("heritage=") => format!("\"{}\"", unquote(content)),
+    _ => content.to_string(),
+  }
+}
+
+/// The record for an external-dns target; the inverse of [`to_target`].
+fn from_target(host: &str, record_type: &str, target: &str) -> Result<Record> {
+  let target = target.trim();
+  let record = match record_type {
+    "MX" | "SRV" => {
+      let invalid =
+        || Error::InvalidInput(format!("{} target '{}' does not start with a priority", record_type, target));
+      let (priority, rest) = target.split_once(' ').ok_or_else(invalid)?;
+      let priority = priority.parse::<u32>().map_err(|_| invalid())?;
+      Record::new(host, record_type, rest.trim().trim_end_matches('.')).with_priority(priority)
+    }
+    "CNAME" | "NS" => Record::new(host, record_type, target.trim_end_matches('.')),
+    "TXT" if unquote(target).starts_with("heritage=") => Record::new(host, record_type, unquote(target)),
+    _ => Record::new(host, record_type, target),
+  };
+  Ok(record)
+}
+
+/// Whether a record is left to the registrar: the apex NS records, which
+/// external-dns is never shown and must not delete or replace.
+fn is_protected(host: &str, record_type: &str) -> bool {
+  host.is_empty() && record_type.eq_ignore_ascii_case("NS")
+}
+
+/// Whether two targets of `record_type` denote the same value.
+fn same_target(record_type: &str, a: &str, b: &str) -> bool {
+  let normalize = |target: &str| {
+    let target = target.trim();
+    match record_type {
+      "A" | "AAAA" => target.parse::<IpAddr>().map_or(target.to_string(), |ip| ip.to_string()),
+      "TXT" => unquote(target).to_string(),
+      _ if is_name_type(record_type) => target.trim_end_matches('.').to_ascii_lowercase(),
+      _ => target.to_string(),
+    }
+  };
+  normalize(a) == normalize(b)
+}
+
+/// Whether the values of `record_type` end in a domain name.
+fn is_name_type(record_type: &str) -> bool {
+  matches!(record_type, "CNAME" | "MX" | "NS" | "SRV")
+}
+
+fn unquote(value: &str) -> &str {
+  value
+    .strip_prefix('"')
+    .and_then(|value| value.strip_suffix('"'))
+    .unwrap_or(value)
+}
+
+fn normalize_name(name: &str) -> String {
+  name.trim().trim_end_matches('.').to_ascii_lowercase()
+}
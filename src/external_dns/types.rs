@@ -0,0 +1,80 @@
+//! Contains the external-dns webhook protocol types.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+
+/// A DNS name with its targets, as exchanged with external-dns.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Endpoint {
+  #[serde(rename = "dnsName")]
+  pub dns_name: String,
+  #[serde(default, deserialize_with = "nullable")]
+  pub targets: Vec<String>,
+  #[serde(rename = "recordType")]
+  pub record_type: String,
+  #[serde(rename = "setIdentifier", default, skip_serializing_if = "String::is_empty")]
+  pub set_identifier: String,
+  /// The TTL in seconds. `None` or `0` uses the registrar's default.
+  #[serde(rename = "recordTTL", default, skip_serializing_if = "Option::is_none")]
+  pub record_ttl: Option<u32>,
+  #[serde(default, deserialize_with = "nullable", skip_serializing_if = "BTreeMap::is_empty")]
+  pub labels: BTreeMap<String, String>,
+  #[serde(
+    rename = "providerSpecific",
+    default,
+    deserialize_with = "nullable",
+    skip_serializing_if = "Vec::is_empty"
+  )]
+  pub provider_specific: Vec<ProviderSpecificProperty>,
+}
+
+impl Endpoint {
+  /// Creates an endpoint without a TTL.
+  ///
+  /// # Arguments
+  /// * `dns_name` - The fully qualified name (e.g., "www.example.com").
+  /// * `record_type` - The record type (e.g., "A").
+  /// * `targets` - The record values.
+  pub fn new(dns_name: &str, record_type: &str, targets: Vec<String>) -> Self {
+    Self {
+      dns_name: dns_name.to_string(),
+      targets,
+      record_type: record_type.to_string(),
+      ..Self::default()
+    }
+  }
+}
+
+/// A provider-specific setting attached to an endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProviderSpecificProperty {
+  pub name: String,
+  pub value: String,
+}
+
+/// The changes external-dns asks the provider to apply.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Changes {
+  #[serde(rename = "Create", default, deserialize_with = "nullable")]
+  pub create: Vec<Endpoint>,
+  #[serde(rename = "UpdateOld", default, deserialize_with = "nullable")]
+  pub update_old: Vec<Endpoint>,
+  #[serde(rename = "UpdateNew", default, deserialize_with = "nullable")]
+  pub update_new: Vec<Endpoint>,
+  #[serde(rename = "Delete", default, deserialize_with = "nullable")]
+  pub delete: Vec<Endpoint>,
+}
+
+/// The domains a provider manages, returned during negotiation.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DomainFilter {
+  #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Vec::is_empty")]
+  pub include: Vec<String>,
+  #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Vec::is_empty")]
+  pub exclude: Vec<String>,
+}
+
+/// Deserializes `null`, as Go encodes empty slices and maps, to the default value.
+fn nullable<'de, D: Deserializer<'de>, T: Deserialize<'de> + Default>(deserializer: D) -> Result<T, D::Error> {
+  Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
@@ -0,0 +1,521 @@
+//! # Multi-Tenant Gateway
+//!
+//! A REST server that lets teams change their own DNS records without
+//! holding the registrar's account-wide credentials.
+//!
+//! The gateway issues local bearer tokens, each scoped in a [`TokenScope`]
+//! to a set of zones, host patterns and record types. Every request is
+//! checked against the token's scope before the registrar is called, and
+//! every mutation attempt, whether applied, denied or failed, is written to
+//! an audit log. Attempts without a valid token are logged under
+//! [`ANONYMOUS_TOKEN`].
+//!
+//! | Method and path                         | Operation                                     |
+//! |-----------------------------------------|-----------------------------------------------|
+//! | `GET /v1/zones`                         | The zones the token may access                |
+//! | `GET /v1/zones/{zone}`                  | The domain's registration details             |
+//! | `GET /v1/zones/{zone}/records`          | The records within the token's scope          |
+//! | `POST /v1/zones/{zone}/records`         | Creates a record; returns its ID              |
+//! | `PUT /v1/zones/{zone}/records/{id}`     | Replaces a record                             |
+//! | `DELETE /v1/zones/{zone}/records/{id}`  | Deletes a record                              |
+//! | `PUT /v1/zones/{zone}/nameservers`      | `{"nameservers": [...]}`                      |
+//! | `PUT /v1/zones/{zone}/auto-renew`       | `{"enabled": true}`                           |
+//! | `PUT /v1/zones/{zone}/lock`             | `{"locked": true}`                            |
+//!
+//! The last three require the token's `domain_settings` permission. Errors
+//! are returned as `{"error": "..."}`.
+//!
+//! To use this module, you must enable the `gateway` feature in your `Cargo.toml`.
+
+use crate::portfolio::{DomainPortfolio, DomainSummary};
+use crate::records::{DnsRecords, Record};
+use crate::{Error, Result};
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, put};
+use axum::{Json, Router};
+use chrono::Utc;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+
+pub mod types;
+
+pub use types::{AuditEntry, AuditOutcome, GatewayConfig, GatewayError, TokenScope};
+
+/// The token name audited for mutation attempts without a valid bearer token.
+pub const ANONYMOUS_TOKEN: &str = "(anonymous)";
+
+/// A callback receiving every audit log entry.
+pub type AuditSink = Arc<dyn Fn(&AuditEntry) + Send + Sync>;
+
+type GatewayResult<T> = std::result::Result<T, GatewayError>;
+
+/// Returns a sink appending entries as JSON lines to `path`.
+pub fn file_audit_sink(path: impl AsRef<std::path::Path>) -> Result<AuditSink> {
+  let file = OpenOptions::new().create(true).append(true).open(path)?;
+  let file = Mutex::new(file);
+  Ok(Arc::new(move |entry: &AuditEntry| {
+    if let (Ok(line), Ok(mut file)) = (serde_json::to_string(entry), file.lock()) {
+      let _ = writeln!(file, "{}", line);
+    }
+  }))
+}
+
+/// A REST gateway enforcing token scopes in front of a registrar.
+#[derive(Clone)]
+pub struct Gateway {
+  records: Arc<dyn DnsRecords>,
+  portfolio: Option<Arc<dyn DomainPortfolio>>,
+  tokens: Arc<Vec<TokenScope>>,
+  audit: AuditSink,
+}
+
+impl Gateway {
+  /// Creates a gateway that logs mutations to standard error.
+  ///
+  /// # Arguments
+  /// * `records` - The registrar hosting the zones.
+  /// * `config` - The tokens and their scopes.
+  pub fn new(records: Arc<dyn DnsRecords>, config: GatewayConfig) -> Result<Self> {
+    for (index, scope) in config.tokens.iter().enumerate() {
+      if scope.token.is_none() && scope.token_sha256.is_none() {
+        return Err(Error::InvalidInput(format!("Gateway token '{}' has no token", scope.name)));
+      }
+      if config.tokens[..index].iter().any(|other| other.name == scope.name) {
+        return Err(Error::InvalidInput(format!("Gateway token '{}' is listed twice", scope.name)));
+      }
+    }
+    Ok(Self {
+      records,
+      portfolio: None,
+      tokens: Arc::new(config.tokens),
+      audit: Arc::new(|entry: &AuditEntry| {
+        if let Ok(line) = serde_json::to_string(entry) {
+          eprintln!("{}", line);
+        }
+      }),
+    })
+  }
+
+  /// Enables the domain endpoints, served through `portfolio`.
+  pub fn with_portfolio(mut self, portfolio: Arc<dyn DomainPortfolio>) -> Self {
+    self.portfolio = Some(portfolio);
+    self
+  }
+
+  /// Sends audit log entries to `sink` instead of standard error.
+  pub fn with_audit_sink(mut self, sink: AuditSink) -> Self {
+    self.audit = sink;
+    self
+  }
+
+  /// The scope of a bearer token.
+  pub fn authenticate(&self, token: &str) -> GatewayResult<&TokenScope> {
+    self
+      .tokens
+      .iter()
+      .find(|scope| scope.verify(token))
+      .ok_or(GatewayError::Unauthorized)
+  }
+
+  /// The zones `scope` may access.
+  pub fn zones(&self, scope: &TokenScope) -> Vec<String> {
+    scope.zones.iter().map(|zone| normalize_zone(zone)).collect()
+  }
+
+  /// The domain's registration details.
+  pub async fn domain(&self, scope: &TokenScope, zone: &str) -> GatewayResult<DomainSummary> {
+    let zone = check_zone(scope, zone)?;
+    Ok(self.portfolio()?.get_domain(&zone).await?)
+  }
+
+  /// The records of `zone` within `scope`.
+  pub async fn list_records(&self, scope: &TokenScope, zone: &str) -> GatewayResult<Vec<Record>> {
+    let zone = check_zone(scope, zone)?;
+    let records = self.records.list_records(&zone).await?;
+    Ok(records.into_iter().filter(|record| scope.allows_record(record)).collect())
+  }
+
+  /// Creates a record and returns its ID.
+  pub async fn create_record(&self, scope: &TokenScope, zone: &str, record: Record) -> GatewayResult<String> {
+    let mut entry = self.entry(&scope.name, "create_record", zone);
+    entry.record = Some(record.clone());
+    let result = async {
+      let zone = check_writable_zone(scope, zone)?;
+      check_record(scope, &record)?;
+      Ok(self.records.create_record(&zone, &record).await?)
+    }
+    .await;
+    if let Ok(id) = &result {
+      entry.record_id = Some(id.clone());
+    }
+    self.audit(entry, &result);
+    result
+  }
+
+  /// Replaces the record `id`. Both the current and the new record must be within `scope`.
+  pub async fn update_record(&self, scope: &TokenScope, zone: &str, id: &str, mut record: Record) -> GatewayResult<()> {
+    record.id = Some(id.to_string());
+    let mut entry = self.entry(&scope.name, "update_record", zone);
+    entry.record_id = Some(id.to_string());
+    entry.record = Some(record.clone());
+    let result = async {
+      let zone = check_writable_zone(scope, zone)?;
+      self.find_record(scope, &zone, id).await?;
+      check_record(scope, &record)?;
+      Ok(self.records.update_record(&zone, &record).await?)
+    }
+    .await;
+    self.audit(entry, &result);
+    result
+  }
+
+  /// Deletes the record `id`, which must be within `scope`.
+  pub async fn delete_record(&self, scope: &TokenScope, zone: &str, id: &str) -> GatewayResult<()> {
+    let mut entry = self.entry(&scope.name, "delete_record", zone);
+    entry.record_id = Some(id.to_string());
+    let result = async {
+      let zone = check_writable_zone(scope, zone)?;
+      self.find_record(scope, &zone, id).await?;
+      Ok(self.records.delete_record(&zone, id).await?)
+    }
+    .await;
+    self.audit(entry, &result);
+    result
+  }
+
+  /// Replaces the domain's nameservers.
+  pub async fn set_nameservers(&self, scope: &TokenScope, zone: &str, nameservers: &[String]) -> GatewayResult<()> {
+    let mut entry = self.entry(&scope.name, "set_nameservers", zone);
+    entry.detail = Some(serde_json::json!(nameservers));
+    let result = async {
+      let zone = check_domain_settings(scope, zone)?;
+      let nameservers: Vec<&str> = nameservers.iter().map(String::as_str).collect();
+      Ok(self.portfolio()?.set_nameservers(&zone, &nameservers).await?)
+    }
+    .await;
+    self.audit(entry, &result);
+    result
+  }
+
+  /// Turns automatic renewal on or off.
+  pub async fn set_auto_renew(&self, scope: &TokenScope, zone: &str, enabled: bool) -> GatewayResult<()> {
+    let mut entry = self.entry(&scope.name, "set_auto_renew", zone);
+    entry.detail = Some(serde_json::json!(enabled));
+    let result = async {
+      let zone = check_domain_settings(scope, zone)?;
+      Ok(self.portfolio()?.set_auto_renew(&zone, enabled).await?)
+    }
+    .await;
+    self.audit(entry, &result);
+    result
+  }
+
+  /// Turns the transfer lock on or off.
+  pub async fn set_lock(&self, scope: &TokenScope, zone: &str, locked: bool) -> GatewayResult<()> {
+    let mut entry = self.entry(&scope.name, "set_lock", zone);
+    entry.detail = Some(serde_json::json!(locked));
+    let result = async {
+      let zone = check_domain_settings(scope, zone)?;
+      Ok(self.portfolio()?.set_lock(&zone, locked).await?)
+    }
+    .await;
+    self.audit(entry, &result);
+    result
+  }
+
+  /// Builds the HTTP routes listed in the module documentation.
+  pub fn router(self) -> Router {
+    Router::new()
+      .route("/v1/zones", get(handle_zones))
+      .route("/v1/zones/{zone}", get(handle_domain))
+      .route("/v1/zones/{zone}/records", get(handle_list_records).post(handle_create_record))
+      .route(
+        "/v1/zones/{zone}/records/{id}",
+        put(handle_update_record).delete(handle_delete_record),
+      )
+      .route("/v1/zones/{zone}/nameservers", put(handle_set_nameservers))
+      .route("/v1/zones/{zone}/auto-renew", put(handle_set_auto_renew))
+      .route("/v1/zones/{zone}/lock", put(handle_set_lock))
+      .with_state(self)
+  }
+
+  /// Serves requests on `listener` until the future is dropped.
+  pub async fn serve(self, listener: TcpListener) -> Result<()> {
+    axum::serve(listener, self.router()).await?;
+    Ok(())
+  }
+
+  async fn find_record(&self, scope: &TokenScope, zone: &str, id: &str) -> GatewayResult<Record> {
+    let records = self.records.list_records(zone).await?;
+    records
+      .into_iter()
+      .find(|record| record.id.as_deref() == Some(id) && scope.allows_record(record))
+      .ok_or_else(|| GatewayError::NotFound(format!("No record '{}' within the token's scope", id)))
+  }
+
+  fn portfolio(&self) -> GatewayResult<&Arc<dyn DomainPortfolio>> {
+    self
+      .portfolio
+      .as_ref()
+      .ok_or_else(|| GatewayError::NotFound("Domain operations are not enabled on this gateway".to_string()))
+  }
+
+  fn entry(&self, token: &str, action: &str, zone: &str) -> AuditEntry {
+    AuditEntry {
+      time: Utc::now(),
+      token: token.to_string(),
+      action: action.to_string(),
+      zone: normalize_zone(zone),
+      record_id: None,
+      record: None,
+      detail: None,
+      outcome: AuditOutcome::Applied,
+    }
+  }
+
+  fn audit<T>(&self, mut entry: AuditEntry, result: &GatewayResult<T>) {
+    entry.outcome = match result {
+      Ok(_) => AuditOutcome::Applied,
+      Err(GatewayError::Upstream(e)) => AuditOutcome::Failed(e.clone()),
+      Err(e) => AuditOutcome::Denied(e.to_string()),
+    };
+    (self.audit)(&entry);
+  }
+}
+
+fn normalize_zone(zone: &str) -> String {
+  zone.trim_end_matches('.').to_ascii_lowercase()
+}
+
+fn check_zone(scope: &TokenScope, zone: &str) -> GatewayResult<String> {
+  let zone = normalize_zone(zone);
+  if !scope.allows_zone(&zone) {
+    return Err(GatewayError::Forbidden(format!("The token may not access {}", zone)));
+  }
+  Ok(zone)
+}
+
+fn check_writable_zone(scope: &TokenScope, zone: &str) -> GatewayResult<String> {
+  let zone = check_zone(scope, zone)?;
+  if scope.read_only {
+    return Err(GatewayError::Forbidden("The token is read-only".to_string()));
+  }
+  Ok(zone)
+}
+
+fn check_domain_settings(scope: &TokenScope, zone: &str) -> GatewayResult<String> {
+  let zone = check_writable_zone(scope, zone)?;
+  if !scope.domain_settings {
+    return Err(GatewayError::Forbidden("The token may not change domain settings".to_string()));
+  }
+  Ok(zone)
+}
+
+fn check_record(scope: &TokenScope, record: &Record) -> GatewayResult<()> {
+  if !scope.allows_record(record) {
+    let host = if record.host.is_empty() { "@" } else { record.host.as_str() };
+    return Err(GatewayError::Forbidden(format!(
+      "The token may not change {} records at '{}'",
+      record.record_type, host
+    )));
+  }
+  Ok(())
+}
+
+impl IntoResponse for GatewayError {
+  fn into_response(self) -> Response {
+    let status = match self {
+      GatewayError::Unauthorized => StatusCode::UNAUTHORIZED,
+      GatewayError::Forbidden(_) => StatusCode::FORBIDDEN,
+      GatewayError::NotFound(_) => StatusCode::NOT_FOUND,
+      GatewayError::BadRequest(_) => StatusCode::BAD_REQUEST,
+      GatewayError::Upstream(_) => StatusCode::BAD_GATEWAY,
+    };
+    (status, Json(serde_json::json!({ "error": self.to_string() }))).into_response()
+  }
+}
+
+/// A mutation request, so that one rejected before it reaches a [`Gateway`]
+/// method, for want of a valid token or a well-formed body, is still audited.
+struct Attempt<'a> {
+  gateway: &'a Gateway,
+  action: &'static str,
+  zone: &'a str,
+  record_id: Option<&'a str>,
+}
+
+impl<'a> Attempt<'a> {
+  fn new(gateway: &'a Gateway, action: &'static str, zone: &'a str) -> Self {
+    Self { gateway, action, zone, record_id: None }
+  }
+
+  fn with_record_id(mut self, id: &'a str) -> Self {
+    self.record_id = Some(id);
+    self
+  }
+
+  /// The scope of the request's bearer token.
+  fn scope(&self, headers: &HeaderMap) -> GatewayResult<&'a TokenScope> {
+    scope(self.gateway, headers).inspect_err(|e| self.reject(ANONYMOUS_TOKEN, e))
+  }
+
+  fn parse<T: DeserializeOwned>(&self, scope: &TokenScope, body: &Bytes) -> GatewayResult<T> {
+    parse(body).inspect_err(|e| self.reject(&scope.name, e))
+  }
+
+  fn reject(&self, token: &str, error: &GatewayError) {
+    let mut entry = self.gateway.entry(token, self.action, self.zone);
+    entry.record_id = self.record_id.map(str::to_string);
+    self.gateway.audit(entry, &Err::<(), _>(error.clone()));
+  }
+}
+
+/// The scope of the request's bearer token.
+fn scope<'a>(gateway: &'a Gateway, headers: &HeaderMap) -> GatewayResult<&'a TokenScope> {
+  let value = headers.get(header::AUTHORIZATION).and_then(|value| value.to_str().ok());
+  let token = value.and_then(|value| value.strip_prefix("Bearer ")).ok_or(GatewayError::Unauthorized)?;
+  gateway.authenticate(token.trim())
+}
+
+fn parse<T: DeserializeOwned>(body: &Bytes) -> GatewayResult<T> {
+  serde_json::from_slice(body).map_err(|e| GatewayError::BadRequest(format!("Invalid request body: {}", e)))
+}
+
+/// Converts a handler result into a response.
+fn respond<T: IntoResponse>(result: GatewayResult<T>) -> Response {
+  match result {
+    Ok(response) => response.into_response(),
+    Err(e) => e.into_response(),
+  }
+}
+
+async fn handle_zones(State(gateway): State<Gateway>, headers: HeaderMap) -> Response {
+  respond(scope(&gateway, &headers).map(|scope| Json(gateway.zones(scope))))
+}
+
+async fn handle_domain(State(gateway): State<Gateway>, Path(zone): Path<String>, headers: HeaderMap) -> Response {
+  let result = async { Ok(Json(gateway.domain(scope(&gateway, &headers)?, &zone).await?)) };
+  respond(result.await)
+}
+
+async fn handle_list_records(
+  State(gateway): State<Gateway>,
+  Path(zone): Path<String>,
+  headers: HeaderMap,
+) -> Response {
+  let result = async { Ok(Json(gateway.list_records(scope(&gateway, &headers)?, &zone).await?)) };
+  respond(result.await)
+}
+
+async fn handle_create_record(
+  State(gateway): State<Gateway>,
+  Path(zone): Path<String>,
+  headers: HeaderMap,
+  body: Bytes,
+) -> Response {
+  let result = async {
+    let attempt = Attempt::new(&gateway, "create_record", &zone);
+    let scope = attempt.scope(&headers)?;
+    let id = gateway.create_record(scope, &zone, attempt.parse(scope, &body)?).await?;
+    Ok((StatusCode::CREATED, Json(serde_json::json!({ "id": id }))))
+  };
+  respond(result.await)
+}
+
+async fn handle_update_record(
+  State(gateway): State<Gateway>,
+  Path((zone, id)): Path<(String, String)>,
+  headers: HeaderMap,
+  body: Bytes,
+) -> Response {
+  let result = async {
+    let attempt = Attempt::new(&gateway, "update_record", &zone).with_record_id(&id);
+    let scope = attempt.scope(&headers)?;
+    gateway.update_record(scope, &zone, &id, attempt.parse(scope, &body)?).await?;
+    Ok(StatusCode::NO_CONTENT)
+  };
+  respond(result.await)
+}
+
+async fn handle_delete_record(
+  State(gateway): State<Gateway>,
+  Path((zone, id)): Path<(String, String)>,
+  headers: HeaderMap,
+) -> Response {
+  let result = async {
+    let scope = Attempt::new(&gateway, "delete_record", &zone).with_record_id(&id).scope(&headers)?;
+    gateway.delete_record(scope, &zone, &id).await?;
+    Ok(StatusCode::NO_CONTENT)
+  };
+  respond(result.await)
+}
+
+#[derive(Deserialize)]
+struct NameserversBody {
+  nameservers: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct AutoRenewBody {
+  enabled: bool,
+}
+
+#[derive(Deserialize)]
+struct LockBody {
+  locked: bool,
+}
+
+async fn handle_set_nameservers(
+  State(gateway): State<Gateway>,
+  Path(zone): Path<String>,
+  headers: HeaderMap,
+  body: Bytes,
+) -> Response {
+  let result = async {
+    let attempt = Attempt::new(&gateway, "set_nameservers", &zone);
+    let scope = attempt.scope(&headers)?;
+    let body: NameserversBody = attempt.parse(scope, &body)?;
+    gateway.set_nameservers(scope, &zone, &body.nameservers).await?;
+    Ok(StatusCode::NO_CONTENT)
+  };
+  respond(result.await)
+}
+
+async fn handle_set_auto_renew(
+  State(gateway): State<Gateway>,
+  Path(zone): Path<String>,
+  headers: HeaderMap,
+  body: Bytes,
+) -> Response {
+  let result = async {
+    let attempt = Attempt::new(&gateway, "set_auto_renew", &zone);
+    let scope = attempt.scope(&headers)?;
+    let body: AutoRenewBody = attempt.parse(scope, &body)?;
+    gateway.set_auto_renew(scope, &zone, body.enabled).await?;
+    Ok(StatusCode::NO_CONTENT)
+  };
+  respond(result.await)
+}
+
+async fn handle_set_lock(
+  State(gateway): State<Gateway>,
+  Path(zone): Path<String>,
+  headers: HeaderMap,
+  body: Bytes,
+) -> Response {
+  let result = async {
+    let attempt = Attempt::new(&gateway, "set_lock", &zone);
+    let scope = attempt.scope(&headers)?;
+    let body: LockBody = attempt.parse(scope, &body)?;
+    gateway.set_lock(scope, &zone, body.locked).await?;
+    Ok(StatusCode::NO_CONTENT)
+  };
+  respond(result.await)
+}
@@ -0,0 +1,186 @@
+//! Contains the gateway configuration, token scopes and audit log types.
+
+use crate::records::Record;
+use crate::secret::constant_time_eq;
+use crate::{Error, Result, config};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The configuration of a gateway.
+///
+/// ```yaml
+/// audit_log: /var/log/registrar/gateway.jsonl
+/// tokens:
+///   - name: web-team
+///     token_sha256: "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+///     zones: [example.com]
+///     names: ["www", "*.web"]
+///     types: [A, AAAA, CNAME]
+///   - name: monitoring
+///     token: "read-only-token"
+///     zones: [example.com, example.org]
+///     read_only: true
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct GatewayConfig {
+  /// The tokens accepted, each with its own scope.
+  pub tokens: Vec<TokenScope>,
+  /// A file receiving one JSON line per mutation. Without it, mutations are logged to standard error.
+  #[serde(default)]
+  pub audit_log: Option<PathBuf>,
+}
+
+impl GatewayConfig {
+  /// Parses a configuration from YAML text.
+  pub fn from_yaml(yaml: &str) -> Result<Self> {
+    config::parse_yaml(yaml, "gateway")
+  }
+
+  /// Loads a configuration file. A file that cannot be read is an `Error::Io`.
+  pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+    config::load_yaml(path.as_ref(), "gateway")
+  }
+}
+
+/// A token and what it may do.
+#[derive(Deserialize, Debug, Clone)]
+pub struct TokenScope {
+  /// The name recorded in the audit log.
+  pub name: String,
+  /// The token in plain text. Prefer `token_sha256`.
+  #[serde(default)]
+  pub token: Option<String>,
+  /// The hexadecimal SHA-256 digest of the token.
+  #[serde(default)]
+  pub token_sha256: Option<String>,
+  /// The registered domains the token may access.
+  pub zones: Vec<String>,
+  /// Host patterns, relative to the zone, the token may access. `@` is the
+  /// apex and `*` matches any characters. Empty allows every name.
+  #[serde(default)]
+  pub names: Vec<String>,
+  /// The record types the token may access. Empty allows every type.
+  #[serde(default)]
+  pub types: Vec<String>,
+  /// Only allows listing.
+  #[serde(default)]
+  pub read_only: bool,
+  /// Also allows changing nameservers, auto-renewal and the transfer lock.
+  #[serde(default)]
+  pub domain_settings: bool,
+}
+
+impl TokenScope {
+  /// Checks a presented token against the configured one.
+  pub(crate) fn verify(&self, token: &str) -> bool {
+    match (&self.token, &self.token_sha256) {
+      (Some(expected), _) => constant_time_eq(expected.as_bytes(), token.as_bytes()),
+      (None, Some(expected)) => {
+        let digest: String = Sha256::digest(token.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect();
+        constant_time_eq(expected.to_ascii_lowercase().as_bytes(), digest.as_bytes())
+      }
+      (None, None) => false,
+    }
+  }
+
+  /// Whether the token may access `zone`.
+  pub fn allows_zone(&self, zone: &str) -> bool {
+    self.zones.iter().any(|allowed| allowed.trim_end_matches('.').eq_ignore_ascii_case(zone))
+  }
+
+  /// Whether the token may access `record`, by host and type.
+  pub fn allows_record(&self, record: &Record) -> bool {
+    let host = if record.host.is_empty() { "@" } else { record.host.as_str() };
+    let name_allowed = self.names.is_empty() || self.names.iter().any(|pattern| glob_match(pattern, host));
+    let type_allowed = self.types.is_empty() || self.types.iter().any(|t| t.eq_ignore_ascii_case(&record.record_type));
+    name_allowed && type_allowed
+  }
+}
+
+/// Matches `value` against a pattern where `*` stands for any characters, ignoring case.
+fn glob_match(pattern: &str, value: &str) -> bool {
+  let pattern = pattern.to_ascii_lowercase();
+  let value = value.to_ascii_lowercase();
+  let mut parts = pattern.split('*');
+  let first = parts.next().unwrap_or_default();
+  let Some(mut rest) = value.strip_prefix(first) else {
+    return false;
+  };
+  let parts: Vec<&str> = parts.collect();
+  let Some((last, middle)) = parts.split_last() else {
+    return rest.is_empty();
+  };
+  for part in middle {
+    match rest.find(part) {
+      Some(index) => rest = &rest[index + part.len()..],
+      None => return false,
+    }
+  }
+  rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// One mutation attempt, as written to the audit log.
+#[derive(Serialize, Debug, Clone)]
+pub struct AuditEntry {
+  pub time: DateTime<Utc>,
+  /// The name of the token's scope, or [`ANONYMOUS_TOKEN`](super::ANONYMOUS_TOKEN) without a valid token.
+  pub token: String,
+  /// The operation (e.g., "create_record", "set_nameservers").
+  pub action: String,
+  pub zone: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub record_id: Option<String>,
+  /// The record as requested, for record changes.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub record: Option<Record>,
+  /// The requested setting, for domain changes.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub detail: Option<serde_json::Value>,
+  #[serde(flatten)]
+  pub outcome: AuditOutcome,
+}
+
+/// Whether a mutation was carried out.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "outcome", content = "reason", rename_all = "lowercase")]
+pub enum AuditOutcome {
+  Applied,
+  Denied(String),
+  Failed(String),
+}
+
+/// Why a gateway request was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GatewayError {
+  /// No token, or an unknown one.
+  Unauthorized,
+  /// The token's scope does not cover the request.
+  Forbidden(String),
+  /// The record does not exist, or is outside the token's scope.
+  NotFound(String),
+  /// The request body is malformed.
+  BadRequest(String),
+  /// The registrar rejected the call or could not be reached.
+  Upstream(String),
+}
+
+impl fmt::Display for GatewayError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      GatewayError::Unauthorized => f.write_str("A valid bearer token is required"),
+      GatewayError::Forbidden(reason)
+      | GatewayError::NotFound(reason)
+      | GatewayError::BadRequest(reason)
+      | GatewayError::Upstream(reason) => f.write_str(reason),
+    }
+  }
+}
+
+impl From<Error> for GatewayError {
+  fn from(e: Error) -> Self {
+    GatewayError::Upstream(e.to_string())
+  }
+}
@@ -19,7 +19,11 @@ use thiserror::Error;
 /// This enum consolidates errors from the underlying HTTP client, JSON
 /// serialization/deserialization, and specific API error messages
 /// returned by the registrar.
+///
+/// New variants may be added in minor releases, so matches on this enum need
+/// a wildcard arm.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
   /// An error occurred during the HTTP request. This could be a network issue,
   /// a DNS problem, or an invalid certificate.
@@ -34,6 +38,24 @@ pub enum Error {
   /// The registrar's API returned a specific error message (e.g., "Invalid API Key").
   #[error("API Error: {0}")]
   Api(String),
+
+  /// The requested operation is not offered by the registrar (e.g., locking a
+  /// domain through the Porkbun API). The message names the missing capability.
+  #[error("Unsupported operation: {0}")]
+  Unsupported(String),
+
+  /// A value supplied by the caller failed validation before any request was
+  /// made (e.g., a malformed IP address or a hostname outside the domain).
+  #[error("Invalid input: {0}")]
+  InvalidInput(String),
+
+  /// A local file operation failed, such as reading or writing persisted state.
+  #[error("I/O error: {0}")]
+  Io(#[from] std::io::Error),
+
+  /// A DNS query failed or returned a malformed response.
+  #[error("DNS lookup failed: {0}")]
+  Dns(String),
 }
 
 /// A specialized `Result` type for registrar operations.
@@ -42,6 +64,43 @@ pub enum Error {
 /// by using `registrar::Error` as the default error type.
 pub type Result<T> = std::result::Result<T, Error>;
 
+#[cfg(feature = "acme")]
+pub mod acme;
+#[cfg(feature = "acme-dns")]
+pub mod acme_dns;
+#[cfg(all(feature = "blocking", any(feature = "porkbun", feature = "name-com")))]
+pub mod blocking;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "ddns")]
+pub mod ddns;
+pub mod dnssec;
+pub mod domain_name;
+#[cfg(feature = "dyndns2")]
+pub mod dyndns2;
+#[cfg(feature = "external-dns")]
+pub mod external_dns;
+#[cfg(feature = "gateway")]
+pub mod gateway;
+#[cfg(feature = "lookup")]
+pub mod lookup;
+pub mod nameserver;
+pub mod portfolio;
+pub mod provider;
+pub mod records;
+pub mod redirect;
+#[cfg(any(feature = "acme-dns", feature = "dyndns2", feature = "gateway", feature = "rfc2136"))]
+mod secret;
+#[cfg(any(feature = "porkbun", feature = "name-com"))]
+mod shared;
+#[cfg(feature = "rfc2136")]
+pub mod rfc2136;
+#[cfg(feature = "router")]
+pub mod router;
+
+pub use domain_name::DomainName;
+pub use provider::Provider;
+
 // Conditionally compile and expose the porkbun module.
 // This block of code will only be included if the "porkbun" feature
 // is enabled by the user of this crate.
@@ -0,0 +1,270 @@
+//! # DNS Lookups
+//!
+//! A small DNS client for checking what a zone's nameservers actually serve.
+//!
+//! Registrar APIs report what has been configured, but automation such as
+//! ACME challenge solving or CDS scanning needs to know what the
+//! authoritative servers answer. [`DnsLookup`] sends queries directly to a
+//! fixed set of servers over UDP, retrying over TCP when a response is
+//! truncated, and returns the crate's own types.
+//!
+//! To use this module, you must enable the `lookup` feature in your `Cargo.toml`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use registrar::lookup::DnsLookup;
+//!
+//! # async fn run() -> registrar::Result<()> {
+//! // Find the authoritative servers via the system resolver, then ask each one.
+//! let authoritative = DnsLookup::system()?.authoritative_for("example.com").await?;
+//! for server in authoritative.per_server() {
+//!   println!("{:?}: {:?}", server.servers(), server.txt("_acme-challenge.example.com").await?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::dnssec::{Dnskey, DsRecord};
+use crate::{Error, Result};
+use hickory_proto::op::{Message, MessageType, OpCode, Query, ResponseCode};
+use hickory_proto::rr::{Name, RData, RecordType};
+use hickory_proto::serialize::binary::BinEncodable;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+
+/// The default time to wait for a single server to answer.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Sends DNS queries to a fixed list of servers.
+///
+/// Queries go to the servers in order; the first server to give a usable
+/// answer wins. An `NXDOMAIN` answer is treated as an empty result.
+#[derive(Clone, Debug)]
+pub struct DnsLookup {
+  servers: Vec<SocketAddr>,
+  timeout: Duration,
+  recursion_desired: bool,
+}
+
+impl DnsLookup {
+  /// Creates a lookup that queries the given servers.
+  pub fn new(servers: Vec<SocketAddr>) -> Self {
+    Self {
+      servers,
+      timeout: DEFAULT_TIMEOUT,
+      recursion_desired: true,
+    }
+  }
+
+  /// Creates a lookup using the recursive resolvers listed in `/etc/resolv.conf`.
+  pub fn system() -> Result<Self> {
+    let contents = std::fs::read_to_string("/etc/resolv.conf")?;
+    let servers: Vec<SocketAddr> = contents
+      .lines()
+      .filter_map(|line| line.trim().strip_prefix("nameserver"))
+      .filter_map(|addr| addr.trim().parse::<IpAddr>().ok())
+      .map(|ip| SocketAddr::new(ip, 53))
+      .collect();
+    if servers.is_empty() {
+      return Err(Error::Dns("No nameservers found in /etc/resolv.conf".to_string()));
+    }
+    Ok(Self::new(servers))
+  }
+
+  /// Sets how long to wait for each server before trying the next one.
+  pub fn with_timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = timeout;
+    self
+  }
+
+  /// The servers this lookup queries.
+  pub fn servers(&self) -> &[SocketAddr] {
+    &self.servers
+  }
+
+  /// Returns one lookup per server, for checking that every server agrees.
+  pub fn per_server(&self) -> impl Iterator<Item = DnsLookup> + '_ {
+    self.servers.iter().map(|server| DnsLookup {
+      servers: vec![*server],
+      ..self.clone()
+    })
+  }
+
+  /// Finds the authoritative nameservers for `zone` and returns a lookup that
+  /// queries them directly, without requesting recursion.
+  pub async fn authoritative_for(&self, zone: &str) -> Result<DnsLookup> {
+    let mut servers = Vec::new();
+    for nameserver in self.ns(zone).await? {
+      for ip in self.ip_addrs(&nameserver).await? {
+        servers.push(SocketAddr::new(ip, 53));
+      }
+    }
+    if servers.is_empty() {
+      return Err(Error::Dns(format!("No authoritative nameservers found for '{}'", zone)));
+    }
+    Ok(DnsLookup {
+      servers,
+      timeout: self.timeout,
+      recursion_desired: false,
+    })
+  }
+
+  /// Retrieves the TXT strings published at `name`. Multi-string records are concatenated.
+  pub async fn txt(&self, name: &str) -> Result<Vec<String>> {
+    let answers = self.query(name, RecordType::TXT).await?;
+    Ok(
+      answers
+        .iter()
+        .filter_map(|rdata| match rdata {
+          RData::TXT(txt) => Some(
+            txt
+              .iter()
+              .map(|part| String::from_utf8_lossy(part).into_owned())
+              .collect::<String>(),
+          ),
+          _ => None,
+        })
+        .collect(),
+    )
+  }
+
+  /// Retrieves the nameserver hostnames delegated for `name`, without trailing dots.
+  pub async fn ns(&self, name: &str) -> Result<Vec<String>> {
+    let answers = self.query(name, RecordType::NS).await?;
+    Ok(
+      answers
+        .iter()
+        .filter_map(|rdata| match rdata {
+          RData::NS(ns) => Some(ns.0.to_ascii().trim_end_matches('.').to_string()),
+          _ => None,
+        })
+        .collect(),
+    )
+  }
+
+  /// Retrieves the IPv4 and IPv6 addresses of `name`.
+  pub async fn ip_addrs(&self, name: &str) -> Result<Vec<IpAddr>> {
+    let mut addrs = Vec::new();
+    for record_type in [RecordType::A, RecordType::AAAA] {
+      for rdata in self.query(name, record_type).await? {
+        match rdata {
+          RData::A(a) => addrs.push(IpAddr::V4(a.0)),
+          RData::AAAA(aaaa) => addrs.push(IpAddr::V6(aaaa.0)),
+          _ => {}
+        }
+      }
+    }
+    Ok(addrs)
+  }
+
+  /// Retrieves the CDS records published at the apex of `zone`.
+  pub async fn cds(&self, zone: &str) -> Result<Vec<DsRecord>> {
+    let answers = self.query(zone, RecordType::CDS).await?;
+    answers.iter().map(|rdata| DsRecord::from_rdata(&rdata_bytes(rdata)?)).collect()
+  }
+
+  /// Retrieves the CDNSKEY records published at the apex of `zone`.
+  pub async fn cdnskey(&self, zone: &str) -> Result<Vec<Dnskey>> {
+    let owner = zone.trim_end_matches('.');
+    let answers = self.query(zone, RecordType::CDNSKEY).await?;
+    answers
+      .iter()
+      .map(|rdata| Dnskey::from_rdata(owner, &rdata_bytes(rdata)?))
+      .collect()
+  }
+
+  /// Queries each server in turn and returns the answer RDATA matching `record_type`.
+  async fn query(&self, name: &str, record_type: RecordType) -> Result<Vec<RData>> {
+    let name = Name::from_ascii(name).map_err(|e| Error::InvalidInput(format!("'{}': {}", name, e)))?;
+    let mut last_error = Error::Dns("No servers configured".to_string());
+    for server in &self.servers {
+      match self.exchange(*server, &name, record_type).await {
+        Ok(response) => match response.response_code() {
+          ResponseCode::NoError => {
+            return Ok(
+              response
+                .answers()
+                .iter()
+                .filter(|record| record.record_type() == record_type)
+                .filter_map(|record| record.data().cloned())
+                .collect(),
+            );
+          }
+          ResponseCode::NXDomain => return Ok(Vec::new()),
+          code => last_error = Error::Dns(format!("{} answered {} for {} {}", server, code, name, record_type)),
+        },
+        Err(e) => last_error = e,
+      }
+    }
+    Err(last_error)
+  }
+
+  /// Sends one query to one server, falling back to TCP if the UDP answer is truncated.
+  async fn exchange(&self, server: SocketAddr, name: &Name, record_type: RecordType) -> Result<Message> {
+    let mut request = Message::new();
+    request
+      .set_id(random_id())
+      .set_message_type(MessageType::Query)
+      .set_op_code(OpCode::Query)
+      .set_recursion_desired(self.recursion_desired)
+      .add_query(Query::query(name.clone(), record_type));
+    let bytes = request.to_vec().map_err(|e| Error::Dns(e.to_string()))?;
+
+    let response = tokio::time::timeout(self.timeout, udp_exchange(server, &bytes))
+      .await
+      .map_err(|_| Error::Dns(format!("Timed out waiting for {}", server)))??;
+    let response = if response.truncated() {
+      tokio::time::timeout(self.timeout, tcp_exchange(server, &bytes))
+        .await
+        .map_err(|_| Error::Dns(format!("Timed out waiting for {} over TCP", server)))??
+    } else {
+      response
+    };
+    if response.id() != request.id() {
+      return Err(Error::Dns(format!("{} answered with a mismatched query ID", server)));
+    }
+    Ok(response)
+  }
+}
+
+async fn udp_exchange(server: SocketAddr, request: &[u8]) -> Result<Message> {
+  let bind_addr: SocketAddr = if server.is_ipv4() {
+    ([0, 0, 0, 0], 0).into()
+  } else {
+    ([0u16; 8], 0).into()
+  };
+  let socket = UdpSocket::bind(bind_addr).await?;
+  socket.connect(server).await?;
+  socket.send(request).await?;
+  let mut buf = vec![0u8; 4096];
+  let len = socket.recv(&mut buf).await?;
+  Message::from_vec(&buf[..len]).map_err(|e| Error::Dns(e.to_string()))
+}
+
+async fn tcp_exchange(server: SocketAddr, request: &[u8]) -> Result<Message> {
+  let mut stream = TcpStream::connect(server).await?;
+  stream.write_all(&(request.len() as u16).to_be_bytes()).await?;
+  stream.write_all(request).await?;
+  let len = stream.read_u16().await? as usize;
+  let mut buf = vec![0u8; len];
+  stream.read_exact(&mut buf).await?;
+  Message::from_vec(&buf).map_err(|e| Error::Dns(e.to_string()))
+}
+
+/// The RDATA of a record in wire format, regardless of how it was decoded.
+fn rdata_bytes(rdata: &RData) -> Result<Vec<u8>> {
+  match rdata {
+    RData::Unknown { rdata, .. } => Ok(rdata.anything().to_vec()),
+    other => other.to_bytes().map_err(|e| Error::Dns(e.to_string())),
+  }
+}
+
+/// A query ID drawn from the randomly keyed hasher of the standard library.
+fn random_id() -> u16 {
+  RandomState::new().build_hasher().finish() as u16
+}
@@ -4,11 +4,14 @@ use super::{
   dns::DnsClient,
   domain::DomainsClient,
   endpoints,
-  types::{ErrorResponse, Hello},
+  types::{ErrorResponse, Hello, Page, PageCursor, Paginated},
   url_forwarding::UrlForwardingClient,
   vanity_ns::VanityNameserverClient,
 };
-use crate::{Error, Result};
+use crate::shared::Shared;
+use crate::{DomainName, Error, Result};
+use futures::future::{self, Either};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
 use reqwest::{Client as HttpClient, Response, StatusCode};
 use serde::{Serialize, de::DeserializeOwned};
 
@@ -22,6 +25,7 @@ pub struct NameDotCom {
   host: String,
   username: String,
   token: String,
+  page_concurrency: usize,
 }
 
 impl NameDotCom {
@@ -29,6 +33,8 @@ impl NameDotCom {
   pub const PRODUCTION_HOST: &'static str = "https://api.name.com";
   /// The development/testing API host.
   pub const DEVELOPMENT_HOST: &'static str = "https://api.dev.name.com";
+  /// How many pages of a listing are fetched at once unless set with `with_page_concurrency`.
+  pub const DEFAULT_PAGE_CONCURRENCY: usize = 4;
 
   /// Creates a new Name.com client for the production environment.
   pub fn new(username: String, token: String) -> Self {
@@ -46,9 +52,23 @@ impl NameDotCom {
       username,
       token,
       http_client: HttpClient::new(),
+      page_concurrency: Self::DEFAULT_PAGE_CONCURRENCY,
     }
   }
 
+  /// Sets how many pages of a listing are fetched at once.
+  ///
+  /// Listings fetch their first page alone, then the remaining pages up to
+  /// the `lastPage` it reports, `concurrency` at a time. Pages are still
+  /// yielded in order.
+  ///
+  /// # Arguments
+  /// * `concurrency` - The number of requests in flight; `1` fetches pages one after another.
+  pub fn with_page_concurrency(mut self, concurrency: usize) -> Self {
+    self.page_concurrency = concurrency.max(1);
+    self
+  }
+
   /// A simple endpoint to test connectivity to the Name.com API server.
   pub async fn hello(&self) -> Result<Hello> {
     let url = format!("{}{}", self.host, endpoints::HELLO);
@@ -68,15 +88,15 @@ impl NameDotCom {
     DomainsClient::new(self)
   }
 
-  pub fn dns<'a>(&'a self, domain_name: &'a str) -> DnsClient<'a> {
+  pub fn dns<'a>(&'a self, domain_name: &'a DomainName) -> DnsClient<'a> {
     DnsClient::new(self, domain_name)
   }
   
-  pub fn url_forwarding<'a>(&'a self, domain_name: &'a str) -> UrlForwardingClient<'a> {
+  pub fn url_forwarding<'a>(&'a self, domain_name: &'a DomainName) -> UrlForwardingClient<'a> {
     UrlForwardingClient::new(self, domain_name)
   }
 
-  pub fn vanity_ns<'a>(&'a self, domain_name: &'a str) -> VanityNameserverClient<'a> {
+  pub fn vanity_ns<'a>(&'a self, domain_name: &'a DomainName) -> VanityNameserverClient<'a> {
     VanityNameserverClient::new(self, domain_name)
   }
 
@@ -93,6 +113,91 @@ impl NameDotCom {
     Self::handle_response_with_body(response).await
   }
 
+  /// Streams the pages of a list endpoint, starting at `cursor`.
+  ///
+  /// The page at `cursor` is fetched first. When it reports the last page,
+  /// the pages after it are fetched `page_concurrency` at a time; otherwise
+  /// `nextPage` is followed one page at a time.
+  ///
+  /// # Arguments
+  /// * `path` - The endpoint path, without a query string.
+  /// * `cursor` - The first page to fetch.
+  pub(super) fn pages<'a, R>(
+    client: Shared<'a, Self>,
+    path: String,
+    cursor: PageCursor,
+  ) -> impl Stream<Item = Result<Page<R::Item>>> + Send + 'a
+  where
+    R: Paginated + Send + 'static,
+    R::Item: Send,
+  {
+    let url = format!("{}{}", path, cursor.query());
+    let first = client.clone();
+    stream::once(async move { first.get::<R>(&url).await })
+      .map_ok(move |response| {
+        let last_page = response.last_page().and_then(|page| u32::try_from(page).ok());
+        let (items, next_page) = response.into_parts();
+        let next = advance(cursor, next_page);
+        let rest = match (next, last_page) {
+          (Some(next), Some(last)) if last >= next.page => {
+            let path = path.clone();
+            let concurrency = client.page_concurrency;
+            let client = client.clone();
+            let pages = stream::iter(next.page..=last).map(move |page| {
+              let cursor = PageCursor { page, ..cursor };
+              let url = format!("{}{}", path, cursor.query());
+              let client = client.clone();
+              async move {
+                let (items, _) = client.get::<R>(&url).await?.into_parts();
+                let next = (page < last).then_some(PageCursor { page: page + 1, ..cursor });
+                Ok(Page { items, next })
+              }
+            });
+            Either::Left(pages.buffered(concurrency))
+          }
+          _ => Either::Right(Self::follow::<R>(client.clone(), path.clone(), next)),
+        };
+        stream::once(future::ready(Ok(Page { items, next }))).chain(rest)
+      })
+      .try_flatten()
+  }
+
+  /// Streams the pages of a list endpoint one at a time, following `nextPage`
+  /// from `cursor`.
+  fn follow<'a, R>(
+    client: Shared<'a, Self>,
+    path: String,
+    cursor: Option<PageCursor>,
+  ) -> impl Stream<Item = Result<Page<R::Item>>> + Send + 'a
+  where
+    R: Paginated + Send + 'static,
+    R::Item: Send,
+  {
+    stream::try_unfold(cursor, move |cursor| {
+      let (client, path) = (client.clone(), path.clone());
+      async move {
+        let Some(cursor) = cursor else { return Ok(None) };
+        let response: R = client.get(&format!("{}{}", path, cursor.query())).await?;
+        let (items, next_page) = response.into_parts();
+        let next = advance(cursor, next_page);
+        Ok(Some((Page { items, next }, next)))
+      }
+    })
+  }
+
+  /// Streams the items of a list endpoint, page by page.
+  pub(super) fn items<'a, R>(
+    client: Shared<'a, Self>,
+    path: String,
+    cursor: PageCursor,
+  ) -> impl Stream<Item = Result<R::Item>> + Send + 'a
+  where
+    R: Paginated + Send + 'static,
+    R::Item: Send,
+  {
+    Self::pages::<R>(client, path, cursor).map_ok(|page| stream::iter(page.items.into_iter().map(Ok))).try_flatten()
+  }
+
   pub(super) async fn post<T: DeserializeOwned, B: Serialize>(&self, path: &str, body: B) -> Result<T> {
     let url = format!("{}{}", self.host, path);
     let response = self
@@ -168,3 +273,13 @@ impl NameDotCom {
     }
   }
 }
+
+/// The cursor of the page after `cursor`, given the `nextPage` the API
+/// returned. A next page that does not advance would repeat forever, so it
+/// ends the listing.
+fn advance(cursor: PageCursor, next_page: Option<i32>) -> Option<PageCursor> {
+  next_page
+    .and_then(|page| u32::try_from(page).ok())
+    .filter(|page| *page > cursor.page)
+    .map(|page| PageCursor { page, ..cursor })
+}
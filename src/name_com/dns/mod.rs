@@ -3,51 +3,82 @@
 use self::types::{
   DnsRecord, DnsRecordPayload, DnssecCreatePayload, DnssecRecord, ListDnsRecordsResponse, ListDnssecResponse,
 };
-use super::{client::NameDotCom, endpoints};
-use crate::Result;
+use super::{
+  client::NameDotCom,
+  endpoints,
+  types::{Page, PageCursor},
+};
+use crate::shared::Shared;
+use crate::{DomainName, Result};
+use futures::{Stream, TryStreamExt};
+use std::borrow::Cow;
+use std::sync::Arc;
 
 // Re-export the public types for this module.
 pub mod types;
 
 /// Provides access to DNS and DNSSEC functionality for a specific domain.
 ///
-/// Created via `NameCom::dns("example.org")`.
+/// Created via `NameCom::dns(&name)`, or with `DnsClient::owned`
+/// for a `'static` client that owns its parent client and domain name.
+#[derive(Clone)]
 pub struct DnsClient<'a> {
-  client: &'a NameDotCom,
-  domain_name: &'a str,
+  client: Shared<'a, NameDotCom>,
+  domain_name: Cow<'a, str>,
 }
 
 impl<'a> DnsClient<'a> {
   /// Constructor is internal to the `name_com` module.
-  pub(super) fn new(client: &'a NameDotCom, domain_name: &'a str) -> Self {
-    Self { client, domain_name }
+  pub(super) fn new(client: &'a NameDotCom, domain_name: &'a DomainName) -> Self {
+    Self { client: Shared::Borrowed(client), domain_name: Cow::Borrowed(domain_name.as_str()) }
+  }
+}
+
+impl DnsClient<'static> {
+  /// Creates a client that owns its parent client and domain name, so it can
+  /// be kept in application state or moved into a spawned task.
+  ///
+  /// # Arguments
+  /// * `client` - The parent client, shared with other owned clients.
+  /// * `domain_name` - The domain name to operate on.
+  pub fn owned(client: Arc<NameDotCom>, domain_name: DomainName) -> Self {
+    Self { client: Shared::Owned(client), domain_name: Cow::Owned(domain_name.into()) }
   }
+}
 
+impl<'a> DnsClient<'a> {
   // --- Standard DNS Record Methods ---
 
   /// Retrieves a list of all DNS records for the domain.
   /// This method handles pagination internally.
   pub async fn list_records(&self) -> Result<Vec<DnsRecord>> {
-    let mut all_records = Vec::new();
-    let mut page = 1;
-    loop {
-      let path = format!(
-        "{}{}{}?page={}",
-        endpoints::CORE_V1_DOMAINS_PREFIX,
-        self.domain_name,
-        endpoints::CORE_V1_RECORDS_SUFFIX,
-        page
-      );
-      let response: ListDnsRecordsResponse = self.client.get(&path).await?;
-
-      all_records.extend(response.records);
-
-      if response.next_page.is_none() {
-        break;
-      }
-      page = response.next_page.unwrap();
-    }
-    Ok(all_records)
+    self.stream_records(PageCursor::first()).try_collect().await
+  }
+
+  /// Streams the DNS records of the domain as each page arrives.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn stream_records(&self, cursor: PageCursor) -> impl Stream<Item = Result<DnsRecord>> + Send + use<'a> {
+    NameDotCom::items::<ListDnsRecordsResponse>(self.client.clone(), self.records_path(), cursor)
+  }
+
+  /// Streams the DNS records of the domain a page at a time. Each page's
+  /// `next` cursor resumes the listing after it.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn record_pages(&self, cursor: PageCursor) -> impl Stream<Item = Result<Page<DnsRecord>>> + Send + use<'a> {
+    NameDotCom::pages::<ListDnsRecordsResponse>(self.client.clone(), self.records_path(), cursor)
+  }
+
+  fn records_path(&self) -> String {
+    format!(
+      "{}{}{}",
+      endpoints::CORE_V1_DOMAINS_PREFIX,
+      self.domain_name,
+      endpoints::CORE_V1_RECORDS_SUFFIX
+    )
   }
 
   /// Retrieves a single DNS record by its ID.
@@ -1,5 +1,6 @@
 //! Contains all serde structs for the Name.com Core API DNS endpoints.
 
+use crate::name_com::types::Paginated;
 use serde::{Deserialize, Serialize};
 
 // =================================================================================
@@ -33,6 +34,18 @@ pub(crate) struct ListDnsRecordsResponse {
   pub last_page: Option<i32>,
 }
 
+impl Paginated for ListDnsRecordsResponse {
+  type Item = DnsRecord;
+
+  fn into_parts(self) -> (Vec<DnsRecord>, Option<i32>) {
+    (self.records, self.next_page)
+  }
+
+  fn last_page(&self) -> Option<i32> {
+    self.last_page
+  }
+}
+
 /// The request body used for creating or updating a DNS record.
 #[derive(Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
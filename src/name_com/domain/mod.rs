@@ -4,40 +4,69 @@
 
 use self::types::{
   AvailabilityResult, CheckAvailabilityRequest, CheckAvailabilityResponse, CreateDomainRequest, CreateDomainResponse,
-  Domain, DomainCreatePayload, GetAuthCodeResponse, ListDomainsResponse, SetNameserversRequest, UpdateDomainPayload,
+  Domain, DomainActionResponse, DomainCreatePayload, GetAuthCodeResponse, ListDomainsResponse, PurchasePrivacyRequest,
+  PurchasePrivacyResponse, SetNameserversRequest, UpdateDomainPayload,
 };
-use super::{client::NameDotCom, endpoints};
-use crate::Result;
+use super::{
+  client::NameDotCom,
+  endpoints,
+  types::{Page, PageCursor},
+};
+use crate::shared::Shared;
+use crate::{DomainName, Result};
+use futures::{Stream, TryStreamExt};
+use std::borrow::Cow;
+use std::sync::Arc;
 
 // Re-export the public types for this module.
 pub mod types;
 
 /// Provides access to domain-related functionality not specific to one domain.
+#[derive(Clone)]
 pub struct DomainsClient<'a> {
-  client: &'a NameDotCom,
+  client: Shared<'a, NameDotCom>,
 }
 
 impl<'a> DomainsClient<'a> {
   pub(super) fn new(client: &'a NameDotCom) -> Self {
-    Self { client }
+    Self { client: Shared::Borrowed(client) }
   }
+}
 
+impl DomainsClient<'static> {
+  /// Creates a client that owns its parent client, so it can be kept in
+  /// application state or moved into a spawned task.
+  ///
+  /// # Arguments
+  /// * `client` - The parent client, shared with other owned clients.
+  pub fn owned(client: Arc<NameDotCom>) -> Self {
+    Self { client: Shared::Owned(client) }
+  }
+}
+
+impl<'a> DomainsClient<'a> {
   /// Retrieves a list of all domains in your account.
   pub async fn list(&self) -> Result<Vec<Domain>> {
-    let mut all_domains = Vec::new();
-    let mut page = 1;
-    loop {
-      let path = format!("{}?page={}", endpoints::CORE_V1_DOMAINS_PREFIX, page);
-      let response: ListDomainsResponse = self.client.get(&path).await?;
+    self.stream(PageCursor::first()).try_collect().await
+  }
 
-      all_domains.extend(response.domains);
+  /// Streams the domains in your account as each page arrives.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn stream(&self, cursor: PageCursor) -> impl Stream<Item = Result<Domain>> + Send + use<'a> {
+    let path = endpoints::CORE_V1_DOMAINS_PREFIX.to_string();
+    NameDotCom::items::<ListDomainsResponse>(self.client.clone(), path, cursor)
+  }
 
-      if response.next_page.is_none() {
-        break;
-      }
-      page = response.next_page.unwrap();
-    }
-    Ok(all_domains)
+  /// Streams the domains in your account a page at a time. Each page's `next`
+  /// cursor resumes the listing after it.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn pages(&self, cursor: PageCursor) -> impl Stream<Item = Result<Page<Domain>>> + Send + use<'a> {
+    let path = endpoints::CORE_V1_DOMAINS_PREFIX.to_string();
+    NameDotCom::pages::<ListDomainsResponse>(self.client.clone(), path, cursor)
   }
 
   /// Checks the availability of a list of domain names.
@@ -63,25 +92,31 @@ impl<'a> DomainsClient<'a> {
   }
 
   /// Returns a client for operating on a single, specific domain.
-  pub fn domain(&self, domain_name: &'a str) -> DomainClient<'a> {
-    DomainClient {
-      client: self.client,
-      domain_name,
-    }
+  pub fn domain(&self, domain_name: &'a DomainName) -> DomainClient<'a> {
+    DomainClient { client: self.client.clone(), domain_name: Cow::Borrowed(domain_name.as_str()) }
   }
 }
 
 /// Provides access to functionality for a specific domain.
+#[derive(Clone)]
 pub struct DomainClient<'a> {
-  client: &'a NameDotCom,
-  domain_name: &'a str,
+  client: Shared<'a, NameDotCom>,
+  domain_name: Cow<'a, str>,
 }
 
-impl<'a> DomainClient<'a> {
-  pub(super) fn new(client: &'a NameDotCom, domain_name: &'a str) -> Self {
-    Self { client, domain_name }
+impl DomainClient<'static> {
+  /// Creates a client that owns its parent client and domain name, so it can
+  /// be kept in application state or moved into a spawned task.
+  ///
+  /// # Arguments
+  /// * `client` - The parent client, shared with other owned clients.
+  /// * `domain_name` - The domain name to operate on.
+  pub fn owned(client: Arc<NameDotCom>, domain_name: DomainName) -> Self {
+    Self { client: Shared::Owned(client), domain_name: Cow::Owned(domain_name.into()) }
   }
+}
 
+impl<'a> DomainClient<'a> {
   /// Retrieves the details for this specific domain.
   pub async fn get(&self) -> Result<Domain> {
     let path = format!("{}{}", endpoints::CORE_V1_DOMAINS_PREFIX, self.domain_name);
@@ -119,4 +154,56 @@ impl<'a> DomainClient<'a> {
     };
     self.client.post(&path, body).await
   }
+
+  /// Locks the domain at the registry, preventing unauthorized transfers.
+  ///
+  /// Returns the updated `Domain`.
+  pub async fn lock(&self) -> Result<Domain> {
+    self.action(endpoints::CORE_V1_ACTION_LOCK).await
+  }
+
+  /// Unlocks the domain so that it can be transferred to another registrar.
+  ///
+  /// Returns the updated `Domain`.
+  pub async fn unlock(&self) -> Result<Domain> {
+    self.action(endpoints::CORE_V1_ACTION_UNLOCK).await
+  }
+
+  /// Enables automatic renewal for the domain.
+  ///
+  /// Returns the updated `Domain`.
+  pub async fn enable_autorenew(&self) -> Result<Domain> {
+    self.action(endpoints::CORE_V1_ACTION_ENABLE_AUTORENEW).await
+  }
+
+  /// Disables automatic renewal for the domain.
+  ///
+  /// Returns the updated `Domain`.
+  pub async fn disable_autorenew(&self) -> Result<Domain> {
+    self.action(endpoints::CORE_V1_ACTION_DISABLE_AUTORENEW).await
+  }
+
+  /// Purchases WHOIS privacy for the domain.
+  ///
+  /// This is a paid action. The returned `PurchasePrivacyResponse` contains the
+  /// updated domain as well as the order number and the total amount charged.
+  ///
+  /// # Arguments
+  /// * `request` - The quoted price and, optionally, the number of years and a promo code.
+  pub async fn purchase_privacy(&self, request: PurchasePrivacyRequest<'_>) -> Result<PurchasePrivacyResponse> {
+    let path = format!(
+      "{}{}{}",
+      endpoints::CORE_V1_DOMAINS_PREFIX,
+      self.domain_name,
+      endpoints::CORE_V1_ACTION_PURCHASE_PRIVACY
+    );
+    self.client.post(&path, request).await
+  }
+
+  /// Performs a body-less action (e.g. `:lock`) on the domain and returns the updated domain.
+  async fn action(&self, action: &str) -> Result<Domain> {
+    let path = format!("{}{}{}", endpoints::CORE_V1_DOMAINS_PREFIX, self.domain_name, action);
+    let response: DomainActionResponse = self.client.post(&path, serde_json::json!({})).await?;
+    Ok(response.into_domain())
+  }
 }
@@ -1,5 +1,6 @@
 //! Contains all serde structs for the Name.com Core API Domain endpoints.
 
+use crate::name_com::types::Paginated;
 use serde::{Deserialize, Serialize};
 use serde_json::Value; // For the complex/unspecified Contact struct
 
@@ -42,6 +43,18 @@ pub(crate) struct ListDomainsResponse {
   pub last_page: Option<i32>,
 }
 
+impl Paginated for ListDomainsResponse {
+  type Item = Domain;
+
+  fn into_parts(self) -> (Vec<Domain>, Option<i32>) {
+    (self.domains, self.next_page)
+  }
+
+  fn last_page(&self) -> Option<i32> {
+    self.last_page
+  }
+}
+
 /// The inner payload for creating a new domain.
 #[derive(Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -76,6 +89,50 @@ pub struct UpdateDomainPayload {
   pub privacy_enabled: Option<bool>,
 }
 
+/// The response from a single-purpose domain action such as `:lock` or `:enableAutorenew`.
+///
+/// The API wraps the updated domain in a `domain` field for these actions, but
+/// the bare form is accepted as well so callers always get the updated `Domain`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub(crate) enum DomainActionResponse {
+  Wrapped { domain: Domain },
+  Bare(Domain),
+}
+
+impl DomainActionResponse {
+  pub(crate) fn into_domain(self) -> Domain {
+    match self {
+      DomainActionResponse::Wrapped { domain } | DomainActionResponse::Bare(domain) => domain,
+    }
+  }
+}
+
+/// Request body for purchasing WHOIS privacy for a domain.
+///
+/// `purchase_price` must match the price quoted by Name.com, otherwise the
+/// order is rejected. This guards against being charged an unexpected amount.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchasePrivacyRequest<'a> {
+  pub purchase_price: f64,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub years: Option<i32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub promo_code: Option<&'a str>,
+}
+
+/// The response after successfully purchasing WHOIS privacy.
+///
+/// Contains the updated domain along with the order details for auditing.
+#[derive(Deserialize, Debug, Clone)]
+pub struct PurchasePrivacyResponse {
+  pub domain: Domain,
+  pub order: i32,
+  #[serde(rename = "totalPaid")]
+  pub total_paid: f64,
+}
+
 /// Request body for setting nameservers for a domain.
 #[derive(Serialize, Debug, Clone)]
 pub(crate) struct SetNameserversRequest {
@@ -16,3 +16,8 @@ pub const CORE_V1_VANITY_NS_SUFFIX: &str = "/vanity_nameservers"; // Appended to
 pub const CORE_V1_ACTION_GET_AUTH_CODE: &str = ":getAuthCode";
 pub const CORE_V1_ACTION_SET_NAMESERVERS: &str = ":setNameservers";
 pub const CORE_V1_ACTION_CHECK_AVAILABILITY: &str = ":checkAvailability";
+pub const CORE_V1_ACTION_LOCK: &str = ":lock";
+pub const CORE_V1_ACTION_UNLOCK: &str = ":unlock";
+pub const CORE_V1_ACTION_ENABLE_AUTORENEW: &str = ":enableAutorenew";
+pub const CORE_V1_ACTION_DISABLE_AUTORENEW: &str = ":disableAutorenew";
+pub const CORE_V1_ACTION_PURCHASE_PRIVACY: &str = ":purchasePrivacy";
@@ -1,6 +1,6 @@
 //! Contains top-level and shared data structures for the Name.com API.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
 /// A standard error response from the Name.com API.
 ///
@@ -10,6 +10,7 @@ use serde::Deserialize;
 pub(crate) struct ErrorResponse {
   pub message: String,
   #[serde(default)]
+  #[allow(dead_code)]
   pub details: String,
 }
 
@@ -24,3 +25,62 @@ pub struct Hello {
   pub server_time: String,
   pub username: String,
 }
+
+/// A position in a paginated listing: the page to fetch next, and the page size.
+///
+/// Cursors are serializable, so a listing interrupted part-way can be resumed
+/// later, even from another process, by passing the [`Page::next`] cursor of
+/// the last page processed.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PageCursor {
+  /// The page number, starting at 1.
+  pub page: u32,
+  /// Items per page. The API's default applies when `None`; Name.com allows up to 1000.
+  #[serde(default)]
+  pub per_page: Option<u32>,
+}
+
+impl Default for PageCursor {
+  fn default() -> Self {
+    Self::first()
+  }
+}
+
+impl PageCursor {
+  /// The first page, with the API's default page size.
+  pub fn first() -> Self {
+    Self { page: 1, per_page: None }
+  }
+
+  /// Sets the number of items per page.
+  pub fn with_per_page(mut self, per_page: u32) -> Self {
+    self.per_page = Some(per_page);
+    self
+  }
+
+  /// The query string selecting this page.
+  pub(crate) fn query(&self) -> String {
+    match self.per_page {
+      Some(per_page) => format!("?page={}&perPage={}", self.page, per_page),
+      None => format!("?page={}", self.page),
+    }
+  }
+}
+
+/// One page of a paginated listing.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+  pub items: Vec<T>,
+  /// Where the listing continues, or `None` after the last page.
+  pub next: Option<PageCursor>,
+}
+
+/// A list response: its items and the numbers of the next and last pages.
+pub(crate) trait Paginated: DeserializeOwned {
+  type Item;
+
+  fn into_parts(self) -> (Vec<Self::Item>, Option<i32>);
+
+  /// The number of the last page, when the response gives it.
+  fn last_page(&self) -> Option<i32>;
+}
@@ -1,5 +1,6 @@
 //! Contains all serde structs for the Name.com Core API URL Forwarding endpoints.
 
+use crate::name_com::types::Paginated;
 use serde::{Deserialize, Serialize};
 
 /// Represents a single URL forwarding record.
@@ -24,6 +25,19 @@ pub(crate) struct ListUrlForwardingResponse {
   #[serde(rename = "urlForwarding", default)]
   pub forwards: Vec<UrlForwardingRecord>,
   pub next_page: Option<i32>,
+  pub last_page: Option<i32>,
+}
+
+impl Paginated for ListUrlForwardingResponse {
+  type Item = UrlForwardingRecord;
+
+  fn into_parts(self) -> (Vec<UrlForwardingRecord>, Option<i32>) {
+    (self.forwards, self.next_page)
+  }
+
+  fn last_page(&self) -> Option<i32> {
+    self.last_page
+  }
 }
 
 /// The request body used for creating a new URL forwarding record.
@@ -1,7 +1,15 @@
 //! The VanityNs sub-client and its methods for the Name.com Core API.
 
-use super::{client::NameDotCom, endpoints};
-use crate::Result;
+use super::{
+  client::NameDotCom,
+  endpoints,
+  types::{Page, PageCursor},
+};
+use crate::shared::Shared;
+use crate::{DomainName, Result};
+use futures::{Stream, TryStreamExt};
+use std::borrow::Cow;
+use std::sync::Arc;
 use self::types::{
   ListVanityNsResponse, VanityNameserver, VanityNsCreatePayload, VanityNsUpdatePayload,
 };
@@ -11,41 +19,59 @@ pub mod types;
 
 /// Provides access to Vanity Nameserver functionality for a specific domain.
 ///
-/// Created via `NameCom::vanity_ns("example.org")`.
+/// Created via `NameCom::vanity_ns(&name)`, or with `VanityNameserverClient::owned`
+/// for a `'static` client that owns its parent client and domain name.
+#[derive(Clone)]
 pub struct VanityNameserverClient<'a> {
-  client: &'a NameDotCom,
-  domain_name: &'a str,
+  client: Shared<'a, NameDotCom>,
+  domain_name: Cow<'a, str>,
 }
 
 impl<'a> VanityNameserverClient<'a> {
   /// Constructor is internal to the `name_com` module.
-  pub(super) fn new(client: &'a NameDotCom, domain_name: &'a str) -> Self {
-    Self { client, domain_name }
+  pub(super) fn new(client: &'a NameDotCom, domain_name: &'a DomainName) -> Self {
+    Self { client: Shared::Borrowed(client), domain_name: Cow::Borrowed(domain_name.as_str()) }
+  }
+}
+
+impl VanityNameserverClient<'static> {
+  /// Creates a client that owns its parent client and domain name, so it can
+  /// be kept in application state or moved into a spawned task.
+  ///
+  /// # Arguments
+  /// * `client` - The parent client, shared with other owned clients.
+  /// * `domain_name` - The domain name to operate on.
+  pub fn owned(client: Arc<NameDotCom>, domain_name: DomainName) -> Self {
+    Self { client: Shared::Owned(client), domain_name: Cow::Owned(domain_name.into()) }
   }
+}
 
+impl<'a> VanityNameserverClient<'a> {
   /// Retrieves a list of all vanity nameservers for the domain.
   /// This method handles pagination internally.
   pub async fn list(&self) -> Result<Vec<VanityNameserver>> {
-    let mut all_records = Vec::new();
-    let mut page = 1;
-    loop {
-      let path = format!(
-        "{}{}{}?page={}",
-        endpoints::CORE_V1_DOMAINS_PREFIX,
-        self.domain_name,
-        endpoints::CORE_V1_VANITY_NS_SUFFIX,
-        page
-      );
-      let response: ListVanityNsResponse = self.client.get(&path).await?;
+    self.stream(PageCursor::first()).try_collect().await
+  }
+
+  /// Streams the vanity nameservers of the domain as each page arrives.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn stream(&self, cursor: PageCursor) -> impl Stream<Item = Result<VanityNameserver>> + Send + use<'a> {
+    NameDotCom::items::<ListVanityNsResponse>(self.client.clone(), self.list_path(), cursor)
+  }
 
-      all_records.extend(response.vanity_ns);
+  /// Streams the vanity nameservers of the domain a page at a time. Each
+  /// page's `next` cursor resumes the listing after it.
+  ///
+  /// # Arguments
+  /// * `cursor` - The page to start at, and the page size.
+  pub fn pages(&self, cursor: PageCursor) -> impl Stream<Item = Result<Page<VanityNameserver>>> + Send + use<'a> {
+    NameDotCom::pages::<ListVanityNsResponse>(self.client.clone(), self.list_path(), cursor)
+  }
 
-      if response.next_page.is_none() {
-        break;
-      }
-      page = response.next_page.unwrap();
-    }
-    Ok(all_records)
+  fn list_path(&self) -> String {
+    format!("{}{}{}", endpoints::CORE_V1_DOMAINS_PREFIX, self.domain_name, endpoints::CORE_V1_VANITY_NS_SUFFIX)
   }
 
   /// Retrieves a single vanity nameserver by its hostname.
@@ -1,5 +1,6 @@
 //! Contains all serde structs for the Name.com Core API Vanity Nameserver endpoints.
 
+use crate::name_com::types::Paginated;
 use serde::{Deserialize, Serialize};
 
 /// Represents a single vanity nameserver.
@@ -19,6 +20,19 @@ pub(crate) struct ListVanityNsResponse {
   #[serde(rename = "vanityNameservers", default)]
   pub vanity_ns: Vec<VanityNameserver>,
   pub next_page: Option<i32>,
+  pub last_page: Option<i32>,
+}
+
+impl Paginated for ListVanityNsResponse {
+  type Item = VanityNameserver;
+
+  fn into_parts(self) -> (Vec<VanityNameserver>, Option<i32>) {
+    (self.vanity_ns, self.next_page)
+  }
+
+  fn last_page(&self) -> Option<i32> {
+    self.last_page
+  }
 }
 
 /// The request body used for creating a new vanity nameserver.
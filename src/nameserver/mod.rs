@@ -0,0 +1,39 @@
+//! # Nameserver Hosts
+//!
+//! A provider-agnostic model of the nameserver hosts (glue records) that a
+//! domain publishes at its registry.
+//!
+//! Porkbun exposes these as glue records addressed by subdomain, while Name.com
+//! calls them vanity nameservers and stores their IPs as strings. The
+//! [`NameserverHosts`] trait presents both as a [`NameserverHost`] with a fully
+//! qualified hostname and parsed `IpAddr`s.
+
+use crate::Result;
+use async_trait::async_trait;
+
+#[cfg(feature = "name-com")]
+mod name_com;
+#[cfg(feature = "porkbun")]
+mod porkbun;
+pub mod types;
+
+pub use types::NameserverHost;
+
+/// Nameserver host operations shared by every supported registrar.
+///
+/// Hostnames are fully qualified (e.g., "ns1.example.com") and must be within
+/// `domain`.
+#[async_trait]
+pub trait NameserverHosts: Send + Sync {
+  /// Lists every nameserver host registered under the domain.
+  async fn list_hosts(&self, domain: &str) -> Result<Vec<NameserverHost>>;
+
+  /// Registers a new nameserver host.
+  async fn create_host(&self, domain: &str, host: &NameserverHost) -> Result<()>;
+
+  /// Replaces the IP addresses of an existing nameserver host.
+  async fn update_host(&self, domain: &str, host: &NameserverHost) -> Result<()>;
+
+  /// Removes a nameserver host.
+  async fn delete_host(&self, domain: &str, hostname: &str) -> Result<()>;
+}
@@ -0,0 +1,59 @@
+//! `NameserverHosts` implementation for the Name.com client.
+
+use super::{
+  NameserverHosts,
+  types::{NameserverHost, relative_hostname},
+};
+use crate::name_com::{
+  NameDotCom,
+  vanity_ns::types::{VanityNameserver, VanityNsCreatePayload, VanityNsUpdatePayload},
+};
+use crate::{DomainName, Error, Result};
+use async_trait::async_trait;
+
+impl TryFrom<VanityNameserver> for NameserverHost {
+  type Error = Error;
+
+  fn try_from(vanity_ns: VanityNameserver) -> Result<Self> {
+    NameserverHost::parse(&vanity_ns.hostname, &vanity_ns.ips)
+  }
+}
+
+#[async_trait]
+impl NameserverHosts for NameDotCom {
+  async fn list_hosts(&self, domain: &str) -> Result<Vec<NameserverHost>> {
+    let domain = DomainName::new(domain)?;
+    let records = self.vanity_ns(&domain).list().await?;
+    records.into_iter().map(NameserverHost::try_from).collect()
+  }
+
+  async fn create_host(&self, domain: &str, host: &NameserverHost) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    host.subdomain_of(&domain)?;
+    let ips: Vec<String> = host.ips().iter().map(|ip| ip.to_string()).collect();
+    let payload = VanityNsCreatePayload {
+      hostname: host.hostname(),
+      ips: ips.iter().map(String::as_str).collect(),
+    };
+    self.vanity_ns(&domain).create(payload).await?;
+    Ok(())
+  }
+
+  async fn update_host(&self, domain: &str, host: &NameserverHost) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    host.subdomain_of(&domain)?;
+    let ips: Vec<String> = host.ips().iter().map(|ip| ip.to_string()).collect();
+    let payload = VanityNsUpdatePayload {
+      ips: ips.iter().map(String::as_str).collect(),
+    };
+    self.vanity_ns(&domain).update(host.hostname(), payload).await?;
+    Ok(())
+  }
+
+  async fn delete_host(&self, domain: &str, hostname: &str) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let hostname = hostname.trim_end_matches('.').to_ascii_lowercase();
+    relative_hostname(&hostname, &domain)?;
+    self.vanity_ns(&domain).delete(&hostname).await
+  }
+}
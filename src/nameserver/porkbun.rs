@@ -0,0 +1,49 @@
+//! `NameserverHosts` implementation for the Porkbun client.
+
+use super::{
+  NameserverHosts,
+  types::{NameserverHost, relative_hostname},
+};
+use crate::porkbun::{Porkbun, domain::types::GlueRecordIps};
+use crate::{DomainName, Error, Result};
+use async_trait::async_trait;
+
+impl TryFrom<(String, GlueRecordIps)> for NameserverHost {
+  type Error = Error;
+
+  fn try_from((hostname, ips): (String, GlueRecordIps)) -> Result<Self> {
+    let ips = ips.v4.into_iter().chain(ips.v6).collect();
+    NameserverHost::new(&hostname, ips)
+  }
+}
+
+#[async_trait]
+impl NameserverHosts for Porkbun {
+  async fn list_hosts(&self, domain: &str) -> Result<Vec<NameserverHost>> {
+    let domain = DomainName::new(domain)?;
+    let records = self.domain(&domain).get_glue_records().await?;
+    records.into_iter().map(NameserverHost::try_from).collect()
+  }
+
+  async fn create_host(&self, domain: &str, host: &NameserverHost) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let subdomain = host.subdomain_of(&domain)?;
+    self.domain(&domain).create_glue_record(subdomain, host.ips()).await?;
+    Ok(())
+  }
+
+  async fn update_host(&self, domain: &str, host: &NameserverHost) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let subdomain = host.subdomain_of(&domain)?;
+    self.domain(&domain).update_glue_record(subdomain, host.ips()).await?;
+    Ok(())
+  }
+
+  async fn delete_host(&self, domain: &str, hostname: &str) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let hostname = hostname.to_ascii_lowercase();
+    let subdomain = relative_hostname(&hostname, &domain)?;
+    self.domain(&domain).delete_glue_record(subdomain).await?;
+    Ok(())
+  }
+}
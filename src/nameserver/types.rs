@@ -0,0 +1,99 @@
+//! Contains the provider-agnostic nameserver host type.
+
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// A nameserver hostname together with the glue addresses published for it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct NameserverHost {
+  hostname: String,
+  ips: Vec<IpAddr>,
+}
+
+impl NameserverHost {
+  /// Creates a validated nameserver host.
+  ///
+  /// The hostname is lower-cased and any trailing dot is removed. At least one
+  /// IP address is required.
+  ///
+  /// # Arguments
+  /// * `hostname` - The fully qualified hostname (e.g., "ns1.example.com").
+  /// * `ips` - The IPv4 and/or IPv6 addresses of the host.
+  pub fn new(hostname: &str, ips: Vec<IpAddr>) -> Result<Self> {
+    let hostname = hostname.trim_end_matches('.').to_ascii_lowercase();
+    if hostname.is_empty() || !hostname.contains('.') {
+      return Err(Error::InvalidInput(format!(
+        "'{}' is not a fully qualified nameserver hostname",
+        hostname
+      )));
+    }
+    if ips.is_empty() {
+      return Err(Error::InvalidInput(format!(
+        "Nameserver host '{}' requires at least one IP address",
+        hostname
+      )));
+    }
+    Ok(Self { hostname, ips })
+  }
+
+  /// Creates a nameserver host from textual IP addresses, validating each one.
+  ///
+  /// # Arguments
+  /// * `hostname` - The fully qualified hostname (e.g., "ns1.example.com").
+  /// * `ips` - The IP addresses as strings (e.g., `["192.0.2.1", "2001:db8::1"]`).
+  pub fn parse<S: AsRef<str>>(hostname: &str, ips: &[S]) -> Result<Self> {
+    let ips = ips
+      .iter()
+      .map(|ip| {
+        let ip = ip.as_ref().trim();
+        ip.parse::<IpAddr>()
+          .map_err(|_| Error::InvalidInput(format!("'{}' is not a valid IP address", ip)))
+      })
+      .collect::<Result<Vec<_>>>()?;
+    Self::new(hostname, ips)
+  }
+
+  /// The fully qualified, lower-case hostname.
+  pub fn hostname(&self) -> &str {
+    &self.hostname
+  }
+
+  /// The host's IP addresses.
+  pub fn ips(&self) -> &[IpAddr] {
+    &self.ips
+  }
+
+  /// The host's IPv4 addresses.
+  pub fn ipv4(&self) -> impl Iterator<Item = &IpAddr> {
+    self.ips.iter().filter(|ip| ip.is_ipv4())
+  }
+
+  /// The host's IPv6 addresses.
+  pub fn ipv6(&self) -> impl Iterator<Item = &IpAddr> {
+    self.ips.iter().filter(|ip| ip.is_ipv6())
+  }
+
+  /// Returns the label(s) of the hostname below `domain` (e.g., "ns1" for
+  /// "ns1.example.com" in "example.com").
+  ///
+  /// Returns `Error::InvalidInput` if the host is not a subdomain of `domain`.
+  pub fn subdomain_of(&self, domain: &str) -> Result<&str> {
+    relative_hostname(&self.hostname, domain)
+  }
+}
+
+/// Strips `.domain` from a fully qualified hostname, requiring a non-empty remainder.
+pub(crate) fn relative_hostname<'a>(hostname: &'a str, domain: &str) -> Result<&'a str> {
+  let suffix = format!(".{}", domain.trim_end_matches('.').to_ascii_lowercase());
+  hostname
+    .trim_end_matches('.')
+    .strip_suffix(&suffix)
+    .filter(|subdomain| !subdomain.is_empty())
+    .ok_or_else(|| {
+      Error::InvalidInput(format!(
+        "Nameserver host '{}' is not within domain '{}'",
+        hostname, domain
+      ))
+    })
+}
@@ -2,12 +2,16 @@
 
 use super::{
   dns::Dns,
-  domain::Domain,
+  domain::{
+    Domain,
+    types::{DomainInfo, DomainListRequest, DomainListResponse},
+  },
   endpoints,
   ssl::Ssl,
   types::{Auth, PingResponse, PricingResponse, StatusResponse},
 };
-use crate::{Error, Result};
+use crate::{DomainName, Error, Result};
+use futures::stream::{self, Stream, TryStreamExt};
 use reqwest::Client as HttpClient;
 use serde::{Serialize, de::DeserializeOwned};
 
@@ -43,6 +47,12 @@ impl Porkbun {
     self.post(endpoints::PING, &self.auth).await
   }
 
+  /// Pings the Porkbun API over IPv4 only, so the returned address is your public IPv4 address
+  /// even on a dual-stack network.
+  pub async fn ping_ipv4(&self) -> Result<PingResponse> {
+    self.post_to(endpoints::IPV4_BASE_URL, endpoints::PING, &self.auth).await
+  }
+
   /// Retrieves the pricing for all TLDs.
   ///
   /// This endpoint does not require authentication.
@@ -50,13 +60,51 @@ impl Porkbun {
     self.post_unauthenticated(endpoints::PRICING_GET).await
   }
 
+  /// Retrieves all domains in your account.
+  /// Note: The API returns domains in chunks of 1000. This method handles
+  /// pagination internally to return a complete list.
+  ///
+  /// # Arguments
+  /// * `include_labels` - If true, includes any labels assigned to domains.
+  pub async fn list_all(&self, include_labels: bool) -> Result<Vec<DomainInfo>> {
+    self.stream_all(include_labels, 0).try_collect().await
+  }
+
+  /// Streams the domains in your account as each chunk of up to 1000 arrives.
+  ///
+  /// To resume an interrupted listing, pass the number of domains already
+  /// received as `start`.
+  ///
+  /// # Arguments
+  /// * `include_labels` - If true, includes any labels assigned to domains.
+  /// * `start` - The number of domains to skip.
+  pub fn stream_all(&self, include_labels: bool, start: u64) -> impl Stream<Item = Result<DomainInfo>> + Send + use<> {
+    let client = self.clone();
+    let chunks = stream::try_unfold(Some(start), move |start| {
+      let client = client.clone();
+      async move {
+        let Some(start) = start else { return Result::Ok(None) };
+        let body = DomainListRequest {
+          auth: client.auth.clone(),
+          start: Some(start),
+          include_labels: if include_labels { Some("yes".to_string()) } else { None },
+        };
+        let response: DomainListResponse = client.post(endpoints::DOMAIN_LIST_ALL, &body).await?;
+        // The listing ends with an empty chunk.
+        let next = (!response.domains.is_empty()).then(|| start + response.domains.len() as u64);
+        Ok(Some((response.domains, next)))
+      }
+    });
+    chunks.map_ok(|domains| stream::iter(domains.into_iter().map(Ok))).try_flatten()
+  }
+
   // --- Sub-Client Constructors ---
 
   /// Access domain-specific functionality.
   ///
   /// # Arguments
   /// * `domain` - The domain name to operate on (e.g., "example.com").
-  pub fn domain<'a>(&'a self, domain: &'a str) -> Domain<'a> {
+  pub fn domain<'a>(&'a self, domain: &'a DomainName) -> Domain<'a> {
     Domain::new(self, domain)
   }
 
@@ -64,7 +112,7 @@ impl Porkbun {
   ///
   /// # Arguments
   /// * `domain` - The domain name whose DNS records you want to manage.
-  pub fn dns<'a>(&'a self, domain: &'a str) -> Dns<'a> {
+  pub fn dns<'a>(&'a self, domain: &'a DomainName) -> Dns<'a> {
     Dns::new(self, domain)
   }
 
@@ -72,7 +120,7 @@ impl Porkbun {
   ///
   /// # Arguments
   /// * `domain` - The domain name whose SSL bundle you want to retrieve.
-  pub fn ssl<'a>(&'a self, domain: &'a str) -> Ssl<'a> {
+  pub fn ssl<'a>(&'a self, domain: &'a DomainName) -> Ssl<'a> {
     Ssl::new(self, domain)
   }
 
@@ -87,7 +135,16 @@ impl Porkbun {
     T: DeserializeOwned,
     B: Serialize,
   {
-    let url = format!("{}{}", endpoints::BASE_URL, path);
+    self.post_to(endpoints::BASE_URL, path, body).await
+  }
+
+  /// Like `post`, against an alternative API host.
+  async fn post_to<T, B>(&self, base_url: &str, path: &str, body: &B) -> Result<T>
+  where
+    T: DeserializeOwned,
+    B: Serialize,
+  {
+    let url = format!("{}{}", base_url, path);
 
     let response_text = self
       .http_client
@@ -5,25 +5,44 @@ use self::types::{
   DnssecCreateRequest, DnssecRecord, DnssecRecordListResponse,
 };
 use super::{client::Porkbun, endpoints, types::StatusResponse};
-use crate::{porkbun::dns::types::{DnsRecordCreateOptions, DnsRecordEditOptions}, Result};
+use crate::shared::Shared;
+use crate::{porkbun::dns::types::{DnsRecordCreateOptions, DnsRecordEditOptions}, DomainName, Result};
+use std::borrow::Cow;
+use std::sync::Arc;
 
 // Re-export the public types for this module to be used in `porkbun/mod.rs`
 pub mod types;
 
 /// Provides access to the DNS functionality of the Porkbun API.
 ///
-/// Created via `Porkbun::dns("example.com")`.
+/// Created via `Porkbun::dns(&name)`, or with `Dns::owned`
+/// for a `'static` client that owns its parent client and domain name.
+#[derive(Clone)]
 pub struct Dns<'a> {
-  client: &'a Porkbun,
-  domain: &'a str,
+  client: Shared<'a, Porkbun>,
+  domain: Cow<'a, str>,
 }
 
 impl<'a> Dns<'a> {
   // Constructor is internal to the `porkbun` module.
-  pub(super) fn new(client: &'a Porkbun, domain: &'a str) -> Self {
-    Self { client, domain }
+  pub(super) fn new(client: &'a Porkbun, domain: &'a DomainName) -> Self {
+    Self { client: Shared::Borrowed(client), domain: Cow::Borrowed(domain.as_str()) }
   }
+}
 
+impl Dns<'static> {
+  /// Creates a client that owns its parent client and domain name, so it can
+  /// be kept in application state or moved into a spawned task.
+  ///
+  /// # Arguments
+  /// * `client` - The parent client, shared with other owned clients.
+  /// * `domain` - The domain name to operate on.
+  pub fn owned(client: Arc<Porkbun>, domain: DomainName) -> Self {
+    Self { client: Shared::Owned(client), domain: Cow::Owned(domain.into()) }
+  }
+}
+
+impl<'a> Dns<'a> {
   /// Creates a new DNS record.
   ///
   /// # Arguments
@@ -115,7 +134,7 @@ impl<'a> Dns<'a> {
   ///
   /// # Arguments
   /// * `record` - A reference to a `DnssecRecord` struct with the new record's data.
-  pub async fn create_dnssec_record(&self, record: &'a DnssecRecord) -> Result<StatusResponse> {
+  pub async fn create_dnssec_record(&self, record: &DnssecRecord) -> Result<StatusResponse> {
     let path = format!("{}{}", endpoints::DNSSEC_CREATE, self.domain);
     let body = DnssecCreateRequest {
       auth: self.client.auth.clone(),
@@ -1,16 +1,20 @@
 //! The Domain sub-client and its methods.
 
 use self::types::{
-  DomainCheckResponse, DomainInfo, DomainListRequest, DomainListResponse, GlueRecordIps, GlueRecordListResponse,
+  AutoRenewUpdateRequest, DomainCheckResponse, DomainInfo, GlueRecordIps, GlueRecordListResponse,
   GlueRecordRequest, NameserverListResponse, NameserverUpdateRequest, UrlForwardCreateRequest, UrlForwardListResponse,
   UrlForwardRecord,
 };
 use super::{
   client::Porkbun,
   endpoints,
-  types::{Auth, StatusResponse},
+  types::StatusResponse,
 };
-use crate::Result;
+use crate::shared::Shared;
+use crate::{DomainName, Result};
+use futures::stream::Stream;
+use std::borrow::Cow;
+use std::sync::Arc;
 use std::net::IpAddr;
 
 // Re-export the public types for this module to be used in `porkbun/mod.rs`
@@ -18,18 +22,34 @@ pub mod types;
 
 /// Provides access to the Domain functionality of the Porkbun API.
 ///
-/// Created via `Porkbun::domain("example.com")`.
+/// Created via `Porkbun::domain(&name)`, or with `Domain::owned`
+/// for a `'static` client that owns its parent client and domain name.
+#[derive(Clone)]
 pub struct Domain<'a> {
-  client: &'a Porkbun,
-  domain: &'a str,
+  client: Shared<'a, Porkbun>,
+  domain: Cow<'a, str>,
 }
 
 impl<'a> Domain<'a> {
   // Constructor is internal to the `porkbun` module.
-  pub(super) fn new(client: &'a Porkbun, domain: &'a str) -> Self {
-    Self { client, domain }
+  pub(super) fn new(client: &'a Porkbun, domain: &'a DomainName) -> Self {
+    Self { client: Shared::Borrowed(client), domain: Cow::Borrowed(domain.as_str()) }
   }
+}
+
+impl Domain<'static> {
+  /// Creates a client that owns its parent client and domain name, so it can
+  /// be kept in application state or moved into a spawned task.
+  ///
+  /// # Arguments
+  /// * `client` - The parent client, shared with other owned clients.
+  /// * `domain` - The domain name to operate on.
+  pub fn owned(client: Arc<Porkbun>, domain: DomainName) -> Self {
+    Self { client: Shared::Owned(client), domain: Cow::Owned(domain.into()) }
+  }
+}
 
+impl<'a> Domain<'a> {
   /// Updates the nameservers for the domain.
   ///
   /// # Arguments
@@ -49,30 +69,38 @@ impl<'a> Domain<'a> {
     self.client.post(&path, &self.client.auth).await
   }
 
-  /// Retrieves all domains in your account.
-  /// Note: The API returns domains in chunks of 1000. This method handles
-  /// pagination internally to return a complete list.
+  /// Turns auto-renew on or off for the domain.
+  ///
+  /// # Arguments
+  /// * `enabled` - `true` to enable auto-renew, `false` to disable it.
+  pub async fn update_auto_renew(&self, enabled: bool) -> Result<StatusResponse> {
+    let path = format!("{}{}", endpoints::DOMAIN_UPDATE_AUTO_RENEW, self.domain);
+    let body = AutoRenewUpdateRequest {
+      auth: self.client.auth.clone(),
+      status: if enabled { "on" } else { "off" },
+    };
+    self.client.post(&path, &body).await
+  }
+
+  /// Retrieves all domains in your account; the same as [`Porkbun::list_all`].
   ///
   /// # Arguments
   /// * `include_labels` - If true, includes any labels assigned to domains.
   pub async fn list_all(&self, include_labels: bool) -> Result<Vec<DomainInfo>> {
-    let mut all_domains = Vec::new();
-    let mut start = 0;
-    loop {
-      let body = DomainListRequest {
-        auth: self.client.auth.clone(),
-        start: Some(start),
-        include_labels: if include_labels { Some("yes".to_string()) } else { None },
-      };
-      let response: DomainListResponse = self.client.post(endpoints::DOMAIN_LIST_ALL, &body).await?;
-
-      if response.domains.is_empty() {
-        break;
-      }
-      start += response.domains.len() as u64;
-      all_domains.extend(response.domains);
-    }
-    Ok(all_domains)
+    self.client.list_all(include_labels).await
+  }
+
+  /// Streams the domains in your account; the same as [`Porkbun::stream_all`].
+  ///
+  /// # Arguments
+  /// * `include_labels` - If true, includes any labels assigned to domains.
+  /// * `start` - The number of domains to skip.
+  pub fn stream_all(
+    &self,
+    include_labels: bool,
+    start: u64,
+  ) -> impl Stream<Item = Result<DomainInfo>> + Send + use<'a> {
+    self.client.stream_all(include_labels, start)
   }
 
   /// Adds a URL forwarding record.
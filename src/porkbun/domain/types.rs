@@ -1,7 +1,6 @@
 //! Contains all serde structs for the Porkbun Domain API endpoints.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use std::net::IpAddr;
 
 // --- Nameserver Types ---
@@ -21,6 +20,16 @@ pub struct NameserverListResponse {
   pub ns: Vec<String>,
 }
 
+// --- Auto-Renew Types ---
+
+/// Request body for turning auto-renew on or off.
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct AutoRenewUpdateRequest {
+  #[serde(flatten)]
+  pub auth: super::super::types::Auth,
+  pub status: &'static str, // "on" or "off"
+}
+
 // --- Domain Listing Types ---
 
 #[derive(Serialize, Debug, Clone)]
@@ -2,6 +2,8 @@
 
 // The base URL for all API v3 calls.
 pub const BASE_URL: &str = "https://api.porkbun.com/api/json/v3";
+// The same API on a host that only has IPv4 addresses, so `ping` reports the IPv4 address.
+pub const IPV4_BASE_URL: &str = "https://api-ipv4.porkbun.com/api/json/v3";
 
 // --- General Endpoints ---
 pub const PING: &str = "/ping";
@@ -12,6 +14,7 @@ pub const PRICING_GET: &str = "/pricing/get";
 pub const DOMAIN_UPDATE_NS: &str = "/domain/updateNs/";
 pub const DOMAIN_GET_NS: &str = "/domain/getNs/";
 pub const DOMAIN_LIST_ALL: &str = "/domain/listAll";
+pub const DOMAIN_UPDATE_AUTO_RENEW: &str = "/domain/updateAutoRenew/";
 pub const DOMAIN_ADD_URL_FORWARD: &str = "/domain/addUrlForward/";
 pub const DOMAIN_GET_URL_FORWARDING: &str = "/domain/getUrlForwarding/";
 pub const DOMAIN_DELETE_URL_FORWARD: &str = "/domain/deleteUrlForward/";
@@ -23,7 +23,8 @@
 //!   println!("Successfully connected. Your IP is: {}", response.your_ip);
 //!
 //!   // Get all DNS records for a domain
-//!   let records = client.dns("example.com").retrieve_all_records().await?;
+//!   let domain = "example.com".parse()?;
+//!   let records = client.dns(&domain).retrieve_all_records().await?;
 //!   println!("Found {} DNS records for example.com", records.len());
 //!
 //!   Ok(())
@@ -2,25 +2,44 @@
 
 use self::types::SslBundleResponse;
 use super::{client::Porkbun, endpoints};
-use crate::Result;
+use crate::shared::Shared;
+use crate::{DomainName, Result};
+use std::borrow::Cow;
+use std::sync::Arc;
 
 // Re-export the public types for this module to be used in `porkbun/mod.rs`
 pub mod types;
 
 /// Provides access to the SSL functionality of the Porkbun API.
 ///
-/// Created via `Porkbun::ssl("example.com")`.
+/// Created via `Porkbun::ssl(&name)`, or with `Ssl::owned`
+/// for a `'static` client that owns its parent client and domain name.
+#[derive(Clone)]
 pub struct Ssl<'a> {
-  client: &'a Porkbun,
-  domain: &'a str,
+  client: Shared<'a, Porkbun>,
+  domain: Cow<'a, str>,
 }
 
 impl<'a> Ssl<'a> {
   // Constructor is internal to the `porkbun` module.
-  pub(super) fn new(client: &'a Porkbun, domain: &'a str) -> Self {
-    Self { client, domain }
+  pub(super) fn new(client: &'a Porkbun, domain: &'a DomainName) -> Self {
+    Self { client: Shared::Borrowed(client), domain: Cow::Borrowed(domain.as_str()) }
   }
+}
+
+impl Ssl<'static> {
+  /// Creates a client that owns its parent client and domain name, so it can
+  /// be kept in application state or moved into a spawned task.
+  ///
+  /// # Arguments
+  /// * `client` - The parent client, shared with other owned clients.
+  /// * `domain` - The domain name to operate on.
+  pub fn owned(client: Arc<Porkbun>, domain: DomainName) -> Self {
+    Self { client: Shared::Owned(client), domain: Cow::Owned(domain.into()) }
+  }
+}
 
+impl<'a> Ssl<'a> {
   /// Retrieves the SSL certificate bundle for the specified domain.
   ///
   /// The bundle includes the private key, the full certificate chain,
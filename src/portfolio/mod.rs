@@ -0,0 +1,62 @@
+//! # Domain Portfolio
+//!
+//! A provider-agnostic view of the domains held in a registrar account.
+//!
+//! Porkbun's `DomainInfo` and Name.com's `Domain` describe the same thing with
+//! different field names and types. The [`DomainPortfolio`] trait is implemented
+//! by each main client and returns a uniform [`DomainSummary`], so code that
+//! reports on or manages many domains does not need to special-case registrars.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use registrar::portfolio::DomainPortfolio;
+//!
+//! async fn report(accounts: &[Box<dyn DomainPortfolio>]) -> registrar::Result<()> {
+//!   for account in accounts {
+//!     for domain in account.list_domains().await? {
+//!       println!("[{}] {} expires {:?}", domain.provider, domain.name, domain.expires);
+//!     }
+//!   }
+//!   Ok(())
+//! }
+//! ```
+
+use crate::{Provider, Result};
+use async_trait::async_trait;
+
+#[cfg(feature = "name-com")]
+mod name_com;
+#[cfg(feature = "porkbun")]
+mod porkbun;
+pub mod types;
+
+pub use types::{DomainSummary, ProviderExtras};
+
+/// Account-level domain operations shared by every supported registrar.
+///
+/// Operations a registrar does not offer return [`crate::Error::Unsupported`].
+#[async_trait]
+pub trait DomainPortfolio: Send + Sync {
+  /// The registrar this client talks to.
+  fn provider(&self) -> Provider;
+
+  /// Lists every domain in the account.
+  ///
+  /// Depending on the registrar, `nameservers` may be `None` in the listing to
+  /// avoid one extra request per domain. Use [`DomainPortfolio::get_domain`] to
+  /// retrieve them.
+  async fn list_domains(&self) -> Result<Vec<DomainSummary>>;
+
+  /// Retrieves a single domain, including its nameservers.
+  async fn get_domain(&self, domain: &str) -> Result<DomainSummary>;
+
+  /// Replaces the authoritative nameservers for the domain.
+  async fn set_nameservers(&self, domain: &str, nameservers: &[&str]) -> Result<()>;
+
+  /// Turns automatic renewal on or off.
+  async fn set_auto_renew(&self, domain: &str, enabled: bool) -> Result<()>;
+
+  /// Locks or unlocks the domain against transfers.
+  async fn set_lock(&self, domain: &str, locked: bool) -> Result<()>;
+}
@@ -0,0 +1,73 @@
+//! `DomainPortfolio` implementation for the Name.com client.
+
+use super::{
+  DomainPortfolio,
+  types::{DomainSummary, ProviderExtras, parse_timestamp},
+};
+use crate::name_com::{NameDotCom, domain::types::Domain};
+use crate::{DomainName, Provider, Result};
+use async_trait::async_trait;
+
+impl From<Domain> for DomainSummary {
+  fn from(domain: Domain) -> Self {
+    DomainSummary {
+      provider: Provider::NameCom,
+      created: parse_timestamp(&domain.create_date),
+      expires: parse_timestamp(&domain.expire_date),
+      auto_renew: domain.autorenew_enabled,
+      locked: domain.locked,
+      privacy: domain.privacy_enabled,
+      nameservers: Some(domain.nameservers),
+      extras: ProviderExtras::NameCom {
+        renewal_price: domain.renewal_price,
+      },
+      name: domain.domain_name,
+    }
+  }
+}
+
+#[async_trait]
+impl DomainPortfolio for NameDotCom {
+  fn provider(&self) -> Provider {
+    Provider::NameCom
+  }
+
+  async fn list_domains(&self) -> Result<Vec<DomainSummary>> {
+    let domains = self.domains().list().await?;
+    Ok(domains.into_iter().map(DomainSummary::from).collect())
+  }
+
+  async fn get_domain(&self, domain: &str) -> Result<DomainSummary> {
+    let name = DomainName::new(domain)?;
+    let domain = self.domains().domain(&name).get().await?;
+    Ok(DomainSummary::from(domain))
+  }
+
+  async fn set_nameservers(&self, domain: &str, nameservers: &[&str]) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    self.domains().domain(&domain).set_nameservers(nameservers).await?;
+    Ok(())
+  }
+
+  async fn set_auto_renew(&self, domain: &str, enabled: bool) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let client = self.domains().domain(&domain);
+    if enabled {
+      client.enable_autorenew().await?;
+    } else {
+      client.disable_autorenew().await?;
+    }
+    Ok(())
+  }
+
+  async fn set_lock(&self, domain: &str, locked: bool) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let client = self.domains().domain(&domain);
+    if locked {
+      client.lock().await?;
+    } else {
+      client.unlock().await?;
+    }
+    Ok(())
+  }
+}
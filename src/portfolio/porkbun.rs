@@ -0,0 +1,76 @@
+//! `DomainPortfolio` implementation for the Porkbun client.
+
+use super::{
+  DomainPortfolio,
+  types::{DomainSummary, ProviderExtras, parse_timestamp},
+};
+use crate::porkbun::{Porkbun, domain::types::DomainInfo};
+use crate::{DomainName, Error, Provider, Result};
+use async_trait::async_trait;
+
+impl From<DomainInfo> for DomainSummary {
+  fn from(info: DomainInfo) -> Self {
+    DomainSummary {
+      provider: Provider::Porkbun,
+      created: parse_timestamp(&info.create_date),
+      expires: parse_timestamp(&info.expire_date),
+      auto_renew: info.auto_renew == 1,
+      locked: info.security_lock == "1",
+      privacy: info.whois_privacy == "1",
+      nameservers: None,
+      extras: ProviderExtras::Porkbun {
+        status: info.status,
+        tld: info.tld,
+        not_local: info.not_local == 1,
+        labels: info.labels.into_iter().map(|label| label.title).collect(),
+      },
+      name: info.domain,
+    }
+  }
+}
+
+#[async_trait]
+impl DomainPortfolio for Porkbun {
+  fn provider(&self) -> Provider {
+    Provider::Porkbun
+  }
+
+  async fn list_domains(&self) -> Result<Vec<DomainSummary>> {
+    let domains = self.list_all(true).await?;
+    Ok(domains.into_iter().map(DomainSummary::from).collect())
+  }
+
+  async fn get_domain(&self, domain: &str) -> Result<DomainSummary> {
+    let domain = DomainName::new(domain)?;
+    // Porkbun has no single-domain lookup, so find it in the full listing.
+    let info = self
+      .list_all(true)
+      .await?
+      .into_iter()
+      .find(|info| info.domain.eq_ignore_ascii_case(&domain))
+      .ok_or_else(|| Error::Api(format!("Domain '{}' not found in account", domain)))?;
+    let nameservers = self.domain(&domain).get_nameservers().await?.ns;
+
+    let mut summary = DomainSummary::from(info);
+    summary.nameservers = Some(nameservers);
+    Ok(summary)
+  }
+
+  async fn set_nameservers(&self, domain: &str, nameservers: &[&str]) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    self.domain(&domain).update_nameservers(nameservers).await?;
+    Ok(())
+  }
+
+  async fn set_auto_renew(&self, domain: &str, enabled: bool) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    self.domain(&domain).update_auto_renew(enabled).await?;
+    Ok(())
+  }
+
+  async fn set_lock(&self, _domain: &str, _locked: bool) -> Result<()> {
+    Err(Error::Unsupported(
+      "Porkbun does not expose domain locking through its API".to_string(),
+    ))
+  }
+}
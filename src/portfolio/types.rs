@@ -0,0 +1,49 @@
+//! Contains the provider-agnostic domain summary types.
+
+use crate::Provider;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// A registrar-independent description of a domain in an account.
+#[derive(Serialize, Debug, Clone)]
+pub struct DomainSummary {
+  pub provider: Provider,
+  pub name: String,
+  pub created: Option<DateTime<Utc>>,
+  pub expires: Option<DateTime<Utc>>,
+  pub auto_renew: bool,
+  pub locked: bool,
+  pub privacy: bool,
+  /// `None` when the registrar's listing does not include nameservers.
+  pub nameservers: Option<Vec<String>>,
+  pub extras: ProviderExtras,
+}
+
+/// Fields that only one registrar reports.
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "provider", rename_all = "kebab-case")]
+pub enum ProviderExtras {
+  Porkbun {
+    status: String,
+    tld: String,
+    not_local: bool,
+    labels: Vec<String>,
+  },
+  NameCom {
+    renewal_price: Option<f64>,
+  },
+}
+
+/// Parses the timestamp formats used by the supported registrars.
+///
+/// Porkbun returns `YYYY-MM-DD HH:MM:SS` without an offset (treated as UTC),
+/// while Name.com returns RFC 3339 timestamps.
+#[cfg(any(feature = "porkbun", feature = "name-com"))]
+pub(crate) fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+  if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+    return Some(dt.with_timezone(&Utc));
+  }
+  chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+    .ok()
+    .map(|naive| naive.and_utc())
+}
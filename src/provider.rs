@@ -0,0 +1,46 @@
+//! Identifies the registrars supported by this crate.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A domain registrar supported by this crate.
+///
+/// Used by the provider-agnostic abstractions to report which registrar an
+/// item came from. Variants exist regardless of which provider features are
+/// enabled, so data tagged with a provider can always be deserialized.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum Provider {
+  Porkbun,
+  #[serde(alias = "name_com")]
+  NameCom,
+}
+
+impl Provider {
+  /// The canonical, lower-case identifier (`"porkbun"` or `"name-com"`).
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Provider::Porkbun => "porkbun",
+      Provider::NameCom => "name-com",
+    }
+  }
+}
+
+impl fmt::Display for Provider {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl FromStr for Provider {
+  type Err = crate::Error;
+
+  fn from_str(s: &str) -> crate::Result<Self> {
+    match s.to_ascii_lowercase().as_str() {
+      "porkbun" => Ok(Provider::Porkbun),
+      "name-com" | "name_com" | "namecom" | "name.com" => Ok(Provider::NameCom),
+      other => Err(crate::Error::InvalidInput(format!("unknown provider '{}'", other))),
+    }
+  }
+}
@@ -0,0 +1,96 @@
+//! # DNS Records
+//!
+//! A provider-agnostic model of the DNS records hosted by a registrar.
+//!
+//! Porkbun and Name.com expose their zone editors with different record
+//! shapes: Porkbun reports fully qualified names and string TTLs, while
+//! Name.com uses relative hosts and numeric IDs. The [`DnsRecords`] trait maps
+//! both onto a single [`Record`] type whose `host` is always relative to the
+//! domain, so tools such as ACME solvers and dynamic DNS updaters can be
+//! written once against `dyn DnsRecords`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use registrar::records::{DnsRecords, Record};
+//!
+//! # async fn run(client: &dyn DnsRecords) -> registrar::Result<()> {
+//! let id = client.create_record("example.com", &Record::new("www", "A", "192.0.2.1")).await?;
+//! for record in client.list_records("example.com").await? {
+//!   println!("{} {} {}", record.host, record.record_type, record.content);
+//! }
+//! client.delete_record("example.com", &id).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+#[cfg(feature = "name-com")]
+mod name_com;
+#[cfg(feature = "porkbun")]
+mod porkbun;
+pub mod types;
+
+pub use types::Record;
+
+/// DNS zone operations shared by every supported registrar.
+#[async_trait]
+pub trait DnsRecords: Send + Sync {
+  /// Lists every record in the domain's zone.
+  async fn list_records(&self, domain: &str) -> Result<Vec<Record>>;
+
+  /// Creates a record and returns the ID the registrar assigned to it. The `id` field of `record` is ignored.
+  async fn create_record(&self, domain: &str, record: &Record) -> Result<String>;
+
+  /// Replaces an existing record, identified by `record.id`.
+  async fn update_record(&self, domain: &str, record: &Record) -> Result<()>;
+
+  /// Deletes a record by the `id` reported in [`DnsRecords::list_records`].
+  async fn delete_record(&self, domain: &str, id: &str) -> Result<()>;
+}
+
+/// A [`DnsRecords`] client bound to a single zone.
+#[derive(Clone)]
+pub struct ZoneRecords {
+  records: Arc<dyn DnsRecords>,
+  zone: String,
+}
+
+impl ZoneRecords {
+  /// Binds `records` to `zone`.
+  ///
+  /// # Arguments
+  /// * `records` - The client of the account hosting the zone.
+  /// * `zone` - The domain whose records are managed, such as `example.com`.
+  pub fn new(records: Arc<dyn DnsRecords>, zone: impl Into<String>) -> Self {
+    Self { records, zone: zone.into() }
+  }
+
+  /// The domain this client manages.
+  pub fn zone(&self) -> &str {
+    &self.zone
+  }
+
+  /// Lists every record in the zone.
+  pub async fn list_records(&self) -> Result<Vec<Record>> {
+    self.records.list_records(&self.zone).await
+  }
+
+  /// Creates a record and returns the ID the registrar assigned to it.
+  pub async fn create_record(&self, record: &Record) -> Result<String> {
+    self.records.create_record(&self.zone, record).await
+  }
+
+  /// Replaces an existing record, identified by `record.id`.
+  pub async fn update_record(&self, record: &Record) -> Result<()> {
+    self.records.update_record(&self.zone, record).await
+  }
+
+  /// Deletes a record by its ID.
+  pub async fn delete_record(&self, id: &str) -> Result<()> {
+    self.records.delete_record(&self.zone, id).await
+  }
+}
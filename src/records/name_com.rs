@@ -0,0 +1,76 @@
+//! `DnsRecords` implementation for the Name.com client.
+
+use super::{
+  DnsRecords,
+  types::{Record, relative_host},
+};
+use crate::name_com::{
+  NameDotCom,
+  dns::types::{DnsRecord, DnsRecordPayload},
+};
+use crate::{DomainName, Error, Result};
+use async_trait::async_trait;
+
+/// The TTL Name.com requires when none is given; it is also the lowest it accepts.
+const DEFAULT_TTL: i64 = 300;
+
+impl From<DnsRecord> for Record {
+  fn from(record: DnsRecord) -> Self {
+    let host = match record.host {
+      Some(host) => host,
+      None => relative_host(&record.fqdn, &record.domain_name),
+    };
+    Record {
+      id: Some(record.id.to_string()),
+      host,
+      record_type: record.r#type,
+      content: record.answer,
+      ttl: u32::try_from(record.ttl).ok(),
+      priority: record.priority.and_then(|priority| u32::try_from(priority).ok()),
+    }
+  }
+}
+
+impl<'a> From<&'a Record> for DnsRecordPayload<'a> {
+  fn from(record: &'a Record) -> Self {
+    DnsRecordPayload {
+      host: Some(record.host.as_str()).filter(|host| !host.is_empty()),
+      r#type: &record.record_type,
+      answer: &record.content,
+      ttl: record.ttl.map(i64::from).unwrap_or(DEFAULT_TTL),
+      priority: record.priority.map(i64::from),
+    }
+  }
+}
+
+fn parse_id(id: &str) -> Result<i32> {
+  id.parse()
+    .map_err(|_| Error::InvalidInput(format!("'{}' is not a Name.com record ID", id)))
+}
+
+#[async_trait]
+impl DnsRecords for NameDotCom {
+  async fn list_records(&self, domain: &str) -> Result<Vec<Record>> {
+    let domain = DomainName::new(domain)?;
+    let records = self.dns(&domain).list_records().await?;
+    Ok(records.into_iter().map(Record::from).collect())
+  }
+
+  async fn create_record(&self, domain: &str, record: &Record) -> Result<String> {
+    let domain = DomainName::new(domain)?;
+    let created = self.dns(&domain).create_record(record.into()).await?;
+    Ok(created.id.to_string())
+  }
+
+  async fn update_record(&self, domain: &str, record: &Record) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let id = parse_id(record.require_id()?)?;
+    self.dns(&domain).update_record(id, record.into()).await?;
+    Ok(())
+  }
+
+  async fn delete_record(&self, domain: &str, id: &str) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    self.dns(&domain).delete_record(parse_id(id)?).await
+  }
+}
@@ -0,0 +1,82 @@
+//! `DnsRecords` implementation for the Porkbun client.
+
+use super::{
+  DnsRecords,
+  types::{Record, relative_host},
+};
+use crate::porkbun::{
+  Porkbun,
+  dns::types::{DnsRecord, DnsRecordCreateOptions, DnsRecordEditOptions},
+};
+use crate::{DomainName, Error, Result};
+use async_trait::async_trait;
+
+impl Record {
+  fn from_porkbun(record: DnsRecord, domain: &str) -> Self {
+    // Porkbun reports a priority of "0" for records that do not use one.
+    let priority = record
+      .prio
+      .parse()
+      .ok()
+      .filter(|prio| *prio != 0 || matches!(record.r#type.as_str(), "MX" | "SRV"));
+    Record {
+      id: Some(record.id),
+      host: relative_host(&record.name, domain),
+      record_type: record.r#type,
+      content: record.content,
+      ttl: record.ttl.parse().ok(),
+      priority,
+    }
+  }
+}
+
+fn parse_id(id: &str) -> Result<u64> {
+  id.parse()
+    .map_err(|_| Error::InvalidInput(format!("'{}' is not a Porkbun record ID", id)))
+}
+
+#[async_trait]
+impl DnsRecords for Porkbun {
+  async fn list_records(&self, domain: &str) -> Result<Vec<Record>> {
+    let domain = DomainName::new(domain)?;
+    let records = self.dns(&domain).retrieve_all_records().await?;
+    Ok(records.into_iter().map(|record| Record::from_porkbun(record, &domain)).collect())
+  }
+
+  async fn create_record(&self, domain: &str, record: &Record) -> Result<String> {
+    let domain = DomainName::new(domain)?;
+    let ttl = record.ttl.map(|ttl| ttl.to_string());
+    let prio = record.priority.map(|prio| prio.to_string());
+    let options = DnsRecordCreateOptions {
+      name: Some(record.host.as_str()).filter(|host| !host.is_empty()),
+      r#type: &record.record_type,
+      content: &record.content,
+      ttl: ttl.as_deref(),
+      prio: prio.as_deref(),
+    };
+    let response = self.dns(&domain).create_record(options).await?;
+    Ok(response.id.to_string())
+  }
+
+  async fn update_record(&self, domain: &str, record: &Record) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let id = parse_id(record.require_id()?)?;
+    let ttl = record.ttl.map(|ttl| ttl.to_string());
+    let prio = record.priority.map(|prio| prio.to_string());
+    let options = DnsRecordEditOptions {
+      name: Some(&record.host),
+      r#type: Some(&record.record_type),
+      content: Some(&record.content),
+      ttl: ttl.as_deref(),
+      prio: prio.as_deref(),
+    };
+    self.dns(&domain).edit_record_by_id(id, options).await?;
+    Ok(())
+  }
+
+  async fn delete_record(&self, domain: &str, id: &str) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    self.dns(&domain).delete_record_by_id(parse_id(id)?).await?;
+    Ok(())
+  }
+}
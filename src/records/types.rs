@@ -0,0 +1,166 @@
+//! Contains the provider-agnostic DNS record type.
+
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// A registrar-independent DNS resource record.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+  /// The registrar's identifier for the record. `None` for records not yet created.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub id: Option<String>,
+  /// The name of the record relative to the domain. An empty string is the apex domain.
+  pub host: String,
+  /// The record type in upper case (e.g., "A", "TXT", "MX").
+  #[serde(rename = "type")]
+  pub record_type: String,
+  /// The record data in presentation format (e.g., an IP address or TXT string).
+  pub content: String,
+  /// The TTL in seconds, at least 600 at Porkbun and 300 at Name.com. `None` uses the registrar's default.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub ttl: Option<u32>,
+  /// The priority of MX and SRV records.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub priority: Option<u32>,
+}
+
+impl Record {
+  /// Creates a record with the registrar's default TTL and no priority.
+  ///
+  /// # Arguments
+  /// * `host` - The name relative to the domain. Use an empty string for the apex domain.
+  /// * `record_type` - The record type (e.g., "A"). It is upper-cased.
+  /// * `content` - The record data.
+  pub fn new(host: impl Into<String>, record_type: &str, content: impl Into<String>) -> Self {
+    Self {
+      id: None,
+      host: host.into(),
+      record_type: record_type.to_ascii_uppercase(),
+      content: content.into(),
+      ttl: None,
+      priority: None,
+    }
+  }
+
+  /// Sets the TTL in seconds.
+  pub fn with_ttl(mut self, ttl: u32) -> Self {
+    self.ttl = Some(ttl);
+    self
+  }
+
+  /// Sets the priority of an MX or SRV record.
+  pub fn with_priority(mut self, priority: u32) -> Self {
+    self.priority = Some(priority);
+    self
+  }
+
+  /// The fully qualified name of the record within `domain`.
+  pub fn fqdn(&self, domain: &str) -> String {
+    let domain = domain.trim_end_matches('.');
+    if self.host.is_empty() {
+      domain.to_string()
+    } else {
+      format!("{}.{}", self.host, domain)
+    }
+  }
+
+  /// Checks the record against the rules for its type before it is sent to a registrar.
+  ///
+  /// The host must be a valid relative name, A and AAAA content must be an
+  /// address of the right family, names in CNAME, ALIAS, NS and MX content must
+  /// be valid hostnames, and SRV and CAA content must have the expected fields.
+  /// Only MX and SRV records may have a priority. Registrar-specific limits
+  /// such as minimum TTLs are not checked.
+  pub fn validate(&self) -> Result<()> {
+    let invalid = |reason: String| Err(Error::InvalidInput(reason));
+    if !self.host.is_empty() && !is_hostname(&self.host, true) {
+      return invalid(format!("'{}' is not a valid host", self.host));
+    }
+    if self.content.trim().is_empty() {
+      return invalid(format!("A {} record needs content", self.record_type));
+    }
+    if self.ttl == Some(0) {
+      return invalid("The TTL must be at least one second".to_string());
+    }
+    if self.priority.is_some() && !matches!(self.record_type.as_str(), "MX" | "SRV") {
+      return invalid(format!("{} records do not have a priority", self.record_type));
+    }
+    let content = self.content.trim();
+    let valid = match self.record_type.as_str() {
+      "A" => content.parse::<Ipv4Addr>().is_ok(),
+      "AAAA" => content.parse::<Ipv6Addr>().is_ok(),
+      "CNAME" if self.host.is_empty() => return invalid("A CNAME record cannot be at the apex".to_string()),
+      "ALIAS" | "CNAME" | "MX" | "NS" => is_hostname(content.trim_end_matches('.'), false),
+      "SRV" => match content.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [weight, port, target] => {
+          weight.parse::<u16>().is_ok()
+            && port.parse::<u16>().is_ok()
+            && (*target == "." || is_hostname(target.trim_end_matches('.'), false))
+        }
+        _ => false,
+      },
+      "CAA" => match content.splitn(3, ' ').collect::<Vec<_>>().as_slice() {
+        [flags, tag, value] => flags.parse::<u8>().is_ok() && !tag.is_empty() && !value.is_empty(),
+        _ => false,
+      },
+      "TXT" | "HTTPS" | "SVCB" | "TLSA" | "SSHFP" => true,
+      other => return invalid(format!("Unsupported record type '{}'", other)),
+    };
+    if !valid {
+      return invalid(format!("'{}' is not valid {} record content", content, self.record_type));
+    }
+    Ok(())
+  }
+
+  /// The record's ID, or an error if it has not been created yet.
+  #[cfg(any(
+    feature = "porkbun",
+    feature = "name-com",
+    feature = "acme",
+    feature = "ddns",
+    feature = "external-dns",
+    feature = "rfc2136"
+  ))]
+  pub(crate) fn require_id(&self) -> Result<&str> {
+    self
+      .id
+      .as_deref()
+      .ok_or_else(|| Error::InvalidInput("Record has no ID; list the records to find it".to_string()))
+  }
+}
+
+/// Whether `name` is a valid DNS name of letters, digits, hyphens and underscores.
+/// With `wildcard`, the first label may be `*`.
+fn is_hostname(name: &str, wildcard: bool) -> bool {
+  name.len() <= 253
+    && name.split('.').enumerate().all(|(index, label)| {
+      (wildcard && index == 0 && label == "*")
+        || (!label.is_empty()
+          && label.len() <= 63
+          && !label.starts_with('-')
+          && !label.ends_with('-')
+          && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+    })
+}
+
+/// Converts a fully qualified name into a host relative to `domain`, using an
+/// empty string for the apex. Names outside the domain are returned unchanged.
+#[cfg(any(feature = "porkbun", feature = "name-com"))]
+pub(crate) fn relative_host(name: &str, domain: &str) -> String {
+  let name = name.trim_end_matches('.');
+  let domain = domain.trim_end_matches('.');
+  if name.eq_ignore_ascii_case(domain) {
+    return String::new();
+  }
+  match name.len().checked_sub(domain.len() + 1) {
+    Some(split)
+      if name.is_char_boundary(split)
+        && name[split..].starts_with('.')
+        && name[split + 1..].eq_ignore_ascii_case(domain) =>
+    {
+      name[..split].to_string()
+    }
+    _ => name.to_string(),
+  }
+}
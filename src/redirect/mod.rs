@@ -0,0 +1,39 @@
+//! # URL Redirects
+//!
+//! A provider-agnostic model of URL forwarding.
+//!
+//! Porkbun's `UrlForwardRecord` and Name.com's `UrlForwardingRecord` describe
+//! redirects with different vocabularies ("temporary"/"permanent" versus
+//! "redirect"/"masked"/"302", `"yes"`/`"no"` flags versus title and meta tags).
+//! The [`UrlRedirects`] trait maps both onto a single [`Redirect`] type.
+//!
+//! Features a registrar lacks are reported as [`crate::Error::Unsupported`]
+//! rather than being silently dropped. For example, Porkbun cannot mask a
+//! redirect and Name.com cannot preserve the request path.
+
+use crate::Result;
+use async_trait::async_trait;
+
+#[cfg(feature = "name-com")]
+mod name_com;
+#[cfg(feature = "porkbun")]
+mod porkbun;
+pub mod types;
+
+pub use types::{Redirect, RedirectKind};
+
+/// URL forwarding operations shared by every supported registrar.
+#[async_trait]
+pub trait UrlRedirects: Send + Sync {
+  /// Lists every redirect configured for the domain.
+  async fn list_redirects(&self, domain: &str) -> Result<Vec<Redirect>>;
+
+  /// Creates a redirect. The `id` field of `redirect` is ignored.
+  async fn create_redirect(&self, domain: &str, redirect: &Redirect) -> Result<()>;
+
+  /// Replaces an existing redirect, identified by `redirect.id`.
+  async fn update_redirect(&self, domain: &str, redirect: &Redirect) -> Result<()>;
+
+  /// Deletes a redirect by the `id` reported in [`UrlRedirects::list_redirects`].
+  async fn delete_redirect(&self, domain: &str, id: &str) -> Result<()>;
+}
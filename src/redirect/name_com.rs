@@ -0,0 +1,114 @@
+//! `UrlRedirects` implementation for the Name.com client.
+
+use super::{
+  UrlRedirects,
+  types::{Redirect, RedirectKind},
+};
+use crate::name_com::{
+  NameDotCom,
+  url_forwarding::types::{UrlForwardingCreatePayload, UrlForwardingRecord, UrlForwardingUpdatePayload},
+};
+use crate::{DomainName, Error, Result};
+use async_trait::async_trait;
+
+impl TryFrom<UrlForwardingRecord> for Redirect {
+  type Error = Error;
+
+  fn try_from(record: UrlForwardingRecord) -> Result<Self> {
+    let kind = name_com_kind(&record.r#type)?;
+    // Normalize to a host relative to the domain, keeping the API's value as the ID.
+    let host = if record.host.eq_ignore_ascii_case(&record.domain_name) {
+      String::new()
+    } else {
+      record
+        .host
+        .strip_suffix(&format!(".{}", record.domain_name))
+        .unwrap_or(&record.host)
+        .to_string()
+    };
+    Ok(Redirect {
+      id: Some(record.host),
+      host,
+      target: record.forwards_to,
+      kind,
+      include_path: false,
+      wildcard: false,
+      title: record.title,
+      meta: record.meta,
+    })
+  }
+}
+
+fn name_com_kind(value: &str) -> Result<RedirectKind> {
+  match value {
+    "redirect" => Ok(RedirectKind::Permanent),
+    "302" => Ok(RedirectKind::Temporary),
+    "masked" => Ok(RedirectKind::Masked),
+    other => Err(Error::Api(format!("Unrecognized Name.com forwarding type '{}'", other))),
+  }
+}
+
+/// The full hostname Name.com identifies a forwarding by: the domain itself
+/// for the apex, otherwise the relative host joined to the domain.
+fn fqdn(domain: &str, host: &str) -> String {
+  if host.is_empty() { domain.to_string() } else { format!("{}.{}", host, domain) }
+}
+
+/// Returns the Name.com forwarding type for a redirect, rejecting features Name.com lacks.
+fn name_com_type(redirect: &Redirect) -> Result<&'static str> {
+  if redirect.include_path {
+    return Err(Error::Unsupported(
+      "Name.com does not support preserving the request path on redirects".to_string(),
+    ));
+  }
+  if redirect.wildcard {
+    return Err(Error::Unsupported("Name.com does not support wildcard redirects".to_string()));
+  }
+  Ok(match redirect.kind {
+    RedirectKind::Permanent => "redirect",
+    RedirectKind::Temporary => "302",
+    RedirectKind::Masked => "masked",
+  })
+}
+
+#[async_trait]
+impl UrlRedirects for NameDotCom {
+  async fn list_redirects(&self, domain: &str) -> Result<Vec<Redirect>> {
+    let domain = DomainName::new(domain)?;
+    let records = self.url_forwarding(&domain).list().await?;
+    records.into_iter().map(Redirect::try_from).collect()
+  }
+
+  async fn create_redirect(&self, domain: &str, redirect: &Redirect) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let host = fqdn(&domain, &redirect.host);
+    let payload = UrlForwardingCreatePayload {
+      domain_name: &domain,
+      host: &host,
+      forwards_to: &redirect.target,
+      r#type: name_com_type(redirect)?,
+      title: redirect.title.as_deref(),
+      meta: redirect.meta.as_deref(),
+    };
+    self.url_forwarding(&domain).create(payload).await?;
+    Ok(())
+  }
+
+  async fn update_redirect(&self, domain: &str, redirect: &Redirect) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let host = redirect.id.clone().unwrap_or_else(|| fqdn(&domain, &redirect.host));
+    let payload = UrlForwardingUpdatePayload {
+      forwards_to: &redirect.target,
+      r#type: name_com_type(redirect)?,
+      title: redirect.title.as_deref(),
+      meta: redirect.meta.as_deref(),
+    };
+    self.url_forwarding(&domain).update(&host, payload).await?;
+    Ok(())
+  }
+
+  async fn delete_redirect(&self, domain: &str, id: &str) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    self.url_forwarding(&domain).delete(id).await
+  }
+}
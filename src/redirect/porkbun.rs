@@ -0,0 +1,90 @@
+//! `UrlRedirects` implementation for the Porkbun client.
+
+use super::{
+  UrlRedirects,
+  types::{Redirect, RedirectKind},
+};
+use crate::porkbun::{Porkbun, domain::types::UrlForwardRecord};
+use crate::{DomainName, Error, Result};
+use async_trait::async_trait;
+
+impl TryFrom<UrlForwardRecord> for Redirect {
+  type Error = Error;
+
+  fn try_from(record: UrlForwardRecord) -> Result<Self> {
+    let kind = match record.r#type.as_str() {
+      "permanent" => RedirectKind::Permanent,
+      "temporary" => RedirectKind::Temporary,
+      other => return Err(Error::Api(format!("Unrecognized Porkbun forward type '{}'", other))),
+    };
+    Ok(Redirect {
+      id: Some(record.id),
+      host: record.subdomain,
+      target: record.location,
+      kind,
+      include_path: record.include_path == "yes",
+      wildcard: record.wildcard == "yes",
+      title: None,
+      meta: None,
+    })
+  }
+}
+
+impl TryFrom<&Redirect> for UrlForwardRecord {
+  type Error = Error;
+
+  fn try_from(redirect: &Redirect) -> Result<Self> {
+    let r#type = match redirect.kind {
+      RedirectKind::Permanent => "permanent",
+      RedirectKind::Temporary => "temporary",
+      RedirectKind::Masked => {
+        return Err(Error::Unsupported("Porkbun does not support masked redirects".to_string()));
+      }
+    };
+    if redirect.title.is_some() || redirect.meta.is_some() {
+      return Err(Error::Unsupported(
+        "Porkbun does not support a title or meta tags on redirects".to_string(),
+      ));
+    }
+    let yes_no = |flag: bool| if flag { "yes" } else { "no" }.to_string();
+    Ok(UrlForwardRecord {
+      id: redirect.id.clone().unwrap_or_default(),
+      subdomain: redirect.host.clone(),
+      location: redirect.target.clone(),
+      r#type: r#type.to_string(),
+      include_path: yes_no(redirect.include_path),
+      wildcard: yes_no(redirect.wildcard),
+    })
+  }
+}
+
+#[async_trait]
+impl UrlRedirects for Porkbun {
+  async fn list_redirects(&self, domain: &str) -> Result<Vec<Redirect>> {
+    let domain = DomainName::new(domain)?;
+    let records = self.domain(&domain).get_url_forwarding().await?;
+    records.into_iter().map(Redirect::try_from).collect()
+  }
+
+  async fn create_redirect(&self, domain: &str, redirect: &Redirect) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let record = UrlForwardRecord::try_from(redirect)?;
+    self.domain(&domain).add_url_forward(&record).await?;
+    Ok(())
+  }
+
+  async fn update_redirect(&self, _domain: &str, _redirect: &Redirect) -> Result<()> {
+    Err(Error::Unsupported(
+      "Porkbun cannot edit URL forwards in place; delete and recreate the redirect instead".to_string(),
+    ))
+  }
+
+  async fn delete_redirect(&self, domain: &str, id: &str) -> Result<()> {
+    let domain = DomainName::new(domain)?;
+    let record_id = id
+      .parse::<u64>()
+      .map_err(|_| Error::Api(format!("Invalid Porkbun URL forward ID '{}'", id)))?;
+    self.domain(&domain).delete_url_forward(record_id).await?;
+    Ok(())
+  }
+}
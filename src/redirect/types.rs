@@ -0,0 +1,61 @@
+//! Contains the provider-agnostic redirect types.
+
+use serde::{Deserialize, Serialize};
+
+/// How the registrar's forwarding service answers requests.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum RedirectKind {
+  /// An HTTP 301 redirect.
+  Permanent,
+  /// An HTTP 302 redirect.
+  Temporary,
+  /// The target is served inside a frame so the original URL stays in the address bar.
+  Masked,
+}
+
+/// A registrar-independent URL forwarding rule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Redirect {
+  /// The registrar's identifier for the rule. `None` for rules not yet created.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub id: Option<String>,
+  /// The subdomain being forwarded. An empty string is the apex domain.
+  pub host: String,
+  /// The URL requests are forwarded to.
+  pub target: String,
+  pub kind: RedirectKind,
+  /// Appends the request path to `target` when forwarding.
+  #[serde(default)]
+  pub include_path: bool,
+  /// Also forwards every subdomain of `host`.
+  #[serde(default)]
+  pub wildcard: bool,
+  /// The page title shown for masked redirects.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub title: Option<String>,
+  /// Meta tags included in the page for masked redirects.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub meta: Option<String>,
+}
+
+impl Redirect {
+  /// Creates a redirect with no optional features enabled.
+  ///
+  /// # Arguments
+  /// * `host` - The subdomain to forward. Use an empty string for the apex domain.
+  /// * `target` - The URL to forward to.
+  /// * `kind` - The type of redirect.
+  pub fn new(host: impl Into<String>, target: impl Into<String>, kind: RedirectKind) -> Self {
+    Self {
+      id: None,
+      host: host.into(),
+      target: target.into(),
+      kind,
+      include_path: false,
+      wildcard: false,
+      title: None,
+      meta: None,
+    }
+  }
+}
@@ -0,0 +1,448 @@
+//! # RFC 2136 Dynamic Updates
+//!
+//! A DNS server that accepts TSIG-signed UPDATE messages, as sent by
+//! `nsupdate`, certbot-dns-rfc2136, Kea DHCP and external-dns's `rfc2136`
+//! provider, and applies them to a registrar-hosted zone through any
+//! [`DnsRecords`] implementation.
+//!
+//! Prerequisites (RFC 2136 §2.4) are checked against the zone's current
+//! records, then the additions and deletions of the update section are
+//! applied in order. Every request must be signed with one of the configured
+//! [`TsigKey`]s, and each key may only update the zones listed for it.
+//!
+//! A few differences from an authoritative server follow from going through a registrar API:
+//! - Updates are not atomic. If the registrar fails part-way, the changes
+//!   made so far remain and the client receives `SERVFAIL`.
+//! - The SOA record and the apex NS records are left to the registrar and
+//!   silently skipped, as RFC 2136 prescribes for protected records.
+//! - Only A, AAAA, CAA, CNAME, MX, NS, PTR, SRV and TXT records can be added
+//!   or removed individually. Other types are answered with `NOTIMP`.
+//!
+//! To use this module, you must enable the `rfc2136` feature in your `Cargo.toml`.
+
+use crate::records::{DnsRecords, Record};
+use crate::{Error, Result};
+use hickory_proto::op::{Message, MessageType, OpCode, ResponseCode};
+use hickory_proto::rr::{self, DNSClass, Name, RData, RecordType};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::net::IpAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use tokio::sync::Mutex;
+
+mod tsig;
+pub mod types;
+
+pub use tsig::TsigKey;
+pub use types::{Rfc2136Config, TsigAlgorithm, TsigKeyConfig};
+
+/// A callback receiving the zone and error of every failed registrar call.
+pub type ErrorLog = Arc<dyn Fn(&str, &Error) + Send + Sync>;
+
+/// A key and the zones it may update.
+struct KeyEntry {
+  key: TsigKey,
+  zones: Vec<String>,
+}
+
+/// An RFC 2136 UPDATE server backed by a registrar's DNS records.
+#[derive(Clone)]
+pub struct Rfc2136Server {
+  records: Arc<dyn DnsRecords>,
+  keys: Arc<HashMap<String, KeyEntry>>,
+  min_ttl: Option<u32>,
+  // Serializes updates so that prerequisites are checked against the records they are applied to.
+  update_lock: Arc<Mutex<()>>,
+  error_log: Option<ErrorLog>,
+}
+
+impl Rfc2136Server {
+  /// Creates a server, decoding and validating the configured keys.
+  ///
+  /// # Arguments
+  /// * `records` - The registrar hosting the zones.
+  /// * `config` - The accepted keys and the zones each may update.
+  pub fn new(records: Arc<dyn DnsRecords>, config: Rfc2136Config) -> Result<Self> {
+    let mut keys = HashMap::new();
+    for key_config in &config.keys {
+      let key = TsigKey::from_config(key_config)?;
+      if key_config.zones.is_empty() {
+        return Err(Error::InvalidInput(format!("TSIG key '{}' has no zones", key_config.name)));
+      }
+      let zones = key_config
+        .zones
+        .iter()
+        .map(|zone| zone.trim_end_matches('.').to_ascii_lowercase())
+        .collect();
+      if keys.insert(dns_name(key.name()), KeyEntry { key, zones }).is_some() {
+        return Err(Error::InvalidInput(format!("TSIG key '{}' is listed twice", key_config.name)));
+      }
+    }
+    Ok(Self {
+      records,
+      keys: Arc::new(keys),
+      min_ttl: config.min_ttl,
+      update_lock: Arc::new(Mutex::new(())),
+      error_log: None,
+    })
+  }
+
+  /// Reports registrar errors, which clients only see as `SERVFAIL`, to `log`.
+  pub fn with_error_log(mut self, log: ErrorLog) -> Self {
+    self.error_log = Some(log);
+    self
+  }
+
+  /// Answers a DNS message in wire format.
+  ///
+  /// Returns `None` for messages that cannot be parsed or are themselves
+  /// responses, which are dropped without an answer.
+  pub async fn handle(&self, request: &[u8]) -> Option<Vec<u8>> {
+    let message = Message::from_vec(request).ok()?;
+    if message.message_type() != MessageType::Query {
+      return None;
+    }
+    let reply = |code| response(&message, code);
+    if message.op_code() != OpCode::Update {
+      return Some(reply(ResponseCode::NotImp));
+    }
+    let Ok(Some((covered, tsig))) = tsig::split(request) else {
+      // Unsigned updates are never accepted.
+      return Some(reply(ResponseCode::Refused));
+    };
+    let entry = self
+      .keys
+      .get(&dns_name(&tsig.key_name))
+      .filter(|entry| entry.key.algorithm().name() == tsig.algorithm);
+    let Some(entry) = entry else {
+      return Some(tsig.append_unsigned(&reply(ResponseCode::NotAuth), tsig::BADKEY));
+    };
+    let now = tsig::now();
+    match entry.key.verify(&covered, &tsig, now) {
+      Ok(()) => {}
+      Err(tsig::BADTIME) => {
+        return Some(entry.key.sign_response(&reply(ResponseCode::NotAuth), &tsig, tsig::BADTIME, now));
+      }
+      Err(error) => return Some(tsig.append_unsigned(&reply(ResponseCode::NotAuth), error)),
+    }
+
+    let code = match self.update(&message, &entry.zones).await {
+      Ok(()) => ResponseCode::NoError,
+      Err(code) => code,
+    };
+    Some(entry.key.sign_response(&reply(code), &tsig, 0, now))
+  }
+
+  /// Applies a verified UPDATE message to one of `zones`.
+  async fn update(&self, message: &Message, zones: &[String]) -> std::result::Result<(), ResponseCode> {
+    let [zone] = message.queries() else {
+      return Err(ResponseCode::FormErr);
+    };
+    if zone.query_type() != RecordType::SOA {
+      return Err(ResponseCode::FormErr);
+    }
+    let zone = dns_name(zone.name());
+    if !zones.contains(&zone) {
+      return Err(ResponseCode::NotAuth);
+    }
+    let prerequisites = message.answers();
+    let updates = message.name_servers();
+    for record in prerequisites.iter().chain(updates) {
+      relative_host(&zone, record.name()).ok_or(ResponseCode::NotZone)?;
+    }
+    for record in updates {
+      prescan(record)?;
+    }
+
+    let _guard = self.update_lock.lock().await;
+    let mut existing = self.records.list_records(&zone).await.map_err(|e| self.fail(&zone, e))?;
+    check_prerequisites(&zone, &existing, prerequisites)?;
+    for record in updates {
+      self
+        .apply(&zone, &mut existing, record)
+        .await
+        .map_err(|e| self.fail(&zone, e))?;
+    }
+    Ok(())
+  }
+
+  /// Applies one record of the update section (RFC 2136 §3.4.2).
+  async fn apply(&self, zone: &str, existing: &mut Vec<Record>, update: &rr::Record) -> Result<()> {
+    let host = relative_host(zone, update.name()).unwrap_or_default();
+    let record_type = update.record_type();
+    let type_name = record_type.to_string();
+    match update.dns_class() {
+      DNSClass::ANY => {
+        let matches =
+          |record: &Record| record_type == RecordType::ANY || record.record_type.eq_ignore_ascii_case(&type_name);
+        self.delete(zone, existing, |record| record.host.eq_ignore_ascii_case(&host) && matches(record)).await
+      }
+      DNSClass::NONE => {
+        let Some(target) = update.data().and_then(|rdata| to_record(&host, rdata)) else {
+          return Ok(());
+        };
+        let target = identity(&target);
+        self.delete(zone, existing, |record| identity(record) == target).await
+      }
+      _ => {
+        let Some(mut record) = update.data().and_then(|rdata| to_record(&host, rdata)) else {
+          return Ok(());
+        };
+        if is_protected(&record) {
+          return Ok(());
+        }
+        record.ttl = Some(self.min_ttl.map_or(update.ttl(), |min_ttl| update.ttl().max(min_ttl)));
+        let at_host: Vec<&Record> = existing.iter().filter(|other| other.host.eq_ignore_ascii_case(&host)).collect();
+        if at_host.iter().any(|other| identity(other) == identity(&record)) {
+          return Ok(());
+        }
+
+        // A CNAME replaces the previous one and cannot coexist with other data at its name.
+        let is_cname = record_type == RecordType::CNAME;
+        if at_host.iter().any(|other| other.record_type.eq_ignore_ascii_case("CNAME") != is_cname) {
+          return Ok(());
+        }
+        if let Some(previous) = at_host.first().filter(|_| is_cname) {
+          record.id = previous.id.clone();
+          self.records.update_record(zone, &record).await?;
+          existing.retain(|other| other.id != record.id);
+        } else {
+          record.id = Some(self.records.create_record(zone, &record).await?);
+        }
+        existing.push(record);
+        Ok(())
+      }
+    }
+  }
+
+  /// Deletes every unprotected record matching `predicate`.
+  async fn delete(&self, zone: &str, existing: &mut Vec<Record>, predicate: impl Fn(&Record) -> bool) -> Result<()> {
+    let doomed: Vec<Record> = existing
+      .iter()
+      .filter(|record| predicate(record) && !is_protected(record))
+      .cloned()
+      .collect();
+    for record in &doomed {
+      self.records.delete_record(zone, record.require_id()?).await?;
+      existing.retain(|other| other.id != record.id);
+    }
+    Ok(())
+  }
+
+  fn fail(&self, zone: &str, e: Error) -> ResponseCode {
+    if let Some(log) = &self.error_log {
+      log(zone, &e);
+    }
+    ResponseCode::ServFail
+  }
+
+  /// Answers messages arriving on `socket` until the future is dropped.
+  pub async fn serve_udp(self, socket: UdpSocket) -> Result<()> {
+    let socket = Arc::new(socket);
+    let mut buf = vec![0u8; 65535];
+    loop {
+      let (len, peer) = socket.recv_from(&mut buf).await?;
+      let request = buf[..len].to_vec();
+      let (server, socket) = (self.clone(), socket.clone());
+      tokio::spawn(async move {
+        if let Some(response) = server.handle(&request).await {
+          let _ = socket.send_to(&response, peer).await;
+        }
+      });
+    }
+  }
+
+  /// Answers messages on connections accepted from `listener` until the future is dropped.
+  pub async fn serve_tcp(self, listener: TcpListener) -> Result<()> {
+    loop {
+      let (stream, _) = listener.accept().await?;
+      let server = self.clone();
+      tokio::spawn(async move {
+        let _ = server.serve_connection(stream).await;
+      });
+    }
+  }
+
+  async fn serve_connection(&self, mut stream: TcpStream) -> Result<()> {
+    loop {
+      let len = match stream.read_u16().await {
+        Ok(len) => usize::from(len),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+        Err(e) => return Err(e.into()),
+      };
+      let mut request = vec![0u8; len];
+      stream.read_exact(&mut request).await?;
+      let Some(response) = self.handle(&request).await else {
+        return Ok(());
+      };
+      stream.write_all(&(response.len() as u16).to_be_bytes()).await?;
+      stream.write_all(&response).await?;
+    }
+  }
+}
+
+/// Checks the prerequisite section against the zone's records (RFC 2136 §3.2).
+fn check_prerequisites(
+  zone: &str,
+  existing: &[Record],
+  prerequisites: &[rr::Record],
+) -> std::result::Result<(), ResponseCode> {
+  let mut value_sets: BTreeMap<(String, String), BTreeSet<Identity>> = BTreeMap::new();
+  for prerequisite in prerequisites {
+    let host = relative_host(zone, prerequisite.name()).unwrap_or_default();
+    let record_type = prerequisite.record_type();
+    let type_name = record_type.to_string();
+    let mut at_host = existing.iter().filter(|record| record.host.eq_ignore_ascii_case(&host));
+    if prerequisite.ttl() != 0 {
+      return Err(ResponseCode::FormErr);
+    }
+    match prerequisite.dns_class() {
+      DNSClass::ANY | DNSClass::NONE if prerequisite.data().is_some() => return Err(ResponseCode::FormErr),
+      DNSClass::ANY if record_type == RecordType::ANY => {
+        if at_host.next().is_none() {
+          return Err(ResponseCode::NXDomain);
+        }
+      }
+      DNSClass::ANY => {
+        if !at_host.any(|record| record.record_type.eq_ignore_ascii_case(&type_name)) {
+          return Err(ResponseCode::NXRRSet);
+        }
+      }
+      DNSClass::NONE if record_type == RecordType::ANY => {
+        if at_host.next().is_some() {
+          return Err(ResponseCode::YXDomain);
+        }
+      }
+      DNSClass::NONE => {
+        if at_host.any(|record| record.record_type.eq_ignore_ascii_case(&type_name)) {
+          return Err(ResponseCode::YXRRSet);
+        }
+      }
+      DNSClass::IN => {
+        let record = prerequisite.data().and_then(|rdata| to_record(&host, rdata));
+        let record = record.ok_or(ResponseCode::NotImp)?;
+        value_sets
+          .entry((host.to_ascii_lowercase(), type_name))
+          .or_default()
+          .insert(identity(&record));
+      }
+      _ => return Err(ResponseCode::FormErr),
+    }
+  }
+
+  for ((host, type_name), wanted) in value_sets {
+    let actual: BTreeSet<Identity> = existing
+      .iter()
+      .filter(|record| record.host.eq_ignore_ascii_case(&host) && record.record_type.eq_ignore_ascii_case(&type_name))
+      .map(identity)
+      .collect();
+    if actual != wanted {
+      return Err(ResponseCode::NXRRSet);
+    }
+  }
+  Ok(())
+}
+
+/// Rejects malformed or unsupported records in the update section (RFC 2136 §3.4.1.3).
+fn prescan(update: &rr::Record) -> std::result::Result<(), ResponseCode> {
+  let record_type = update.record_type();
+  let meta = matches!(
+    record_type,
+    RecordType::AXFR | RecordType::IXFR | RecordType::OPT | RecordType::TSIG
+  );
+  match update.dns_class() {
+    DNSClass::ANY if update.ttl() == 0 && update.data().is_none() && !meta => Ok(()),
+    DNSClass::NONE | DNSClass::IN if record_type != RecordType::ANY && !meta => {
+      if update.dns_class() == DNSClass::NONE && update.ttl() != 0 {
+        return Err(ResponseCode::FormErr);
+      }
+      let Some(rdata) = update.data() else {
+        return Err(ResponseCode::FormErr);
+      };
+      if record_type != RecordType::SOA && to_record("", rdata).is_none() {
+        return Err(ResponseCode::NotImp);
+      }
+      Ok(())
+    }
+    _ => Err(ResponseCode::FormErr),
+  }
+}
+
+/// Converts record data to the registrar's representation, for the supported types.
+fn to_record(host: &str, rdata: &RData) -> Option<Record> {
+  let (content, priority) = match rdata {
+    RData::A(a) => (a.to_string(), None),
+    RData::AAAA(aaaa) => (aaaa.to_string(), None),
+    RData::CAA(caa) => (caa.to_string(), None),
+    RData::CNAME(name) => (dns_name(&name.0), None),
+    RData::MX(mx) => (dns_name(mx.exchange()), Some(u32::from(mx.preference()))),
+    RData::NS(name) => (dns_name(&name.0), None),
+    RData::PTR(name) => (dns_name(&name.0), None),
+    RData::SRV(srv) => (
+      format!("{} {} {}", srv.weight(), srv.port(), dns_name(srv.target())),
+      Some(u32::from(srv.priority())),
+    ),
+    RData::TXT(txt) => (
+      txt.txt_data().iter().map(|part| String::from_utf8_lossy(part)).collect(),
+      None,
+    ),
+    _ => return None,
+  };
+  let mut record = Record::new(host, &rdata.record_type().to_string(), content);
+  record.priority = priority;
+  Some(record)
+}
+
+/// A record's host, type, data and priority, normalized so that wire and registrar forms compare equal.
+type Identity = (String, String, String, u32);
+
+fn identity(record: &Record) -> Identity {
+  let record_type = record.record_type.to_ascii_uppercase();
+  let mut content = record.content.trim();
+  if record_type == "TXT" && content.len() >= 2 && content.starts_with('"') && content.ends_with('"') {
+    content = &content[1..content.len() - 1];
+  }
+  let content = match record_type.as_str() {
+    "A" | "AAAA" => content.parse::<IpAddr>().map_or(content.to_string(), |ip| ip.to_string()),
+    "CNAME" | "MX" | "NS" | "PTR" | "SRV" => content.trim_end_matches('.').to_ascii_lowercase(),
+    _ => content.to_string(),
+  };
+  let priority = match record_type.as_str() {
+    "MX" | "SRV" => record.priority.unwrap_or(0),
+    _ => 0,
+  };
+  (record.host.to_ascii_lowercase(), record_type, content, priority)
+}
+
+/// Whether a record is left to the registrar: the SOA and the apex NS records.
+fn is_protected(record: &Record) -> bool {
+  record.record_type.eq_ignore_ascii_case("SOA")
+    || (record.host.is_empty() && record.record_type.eq_ignore_ascii_case("NS"))
+}
+
+/// A name in lowercase, without the trailing dot.
+fn dns_name(name: &Name) -> String {
+  name.to_ascii().trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// `name` relative to `zone`, or `None` if it lies outside it. The apex is an empty string.
+fn relative_host(zone: &str, name: &Name) -> Option<String> {
+  let name = dns_name(name);
+  if name == zone {
+    return Some(String::new());
+  }
+  name.strip_suffix(zone)?.strip_suffix('.').map(str::to_string)
+}
+
+/// An unsigned response echoing the request's zone section.
+fn response(request: &Message, code: ResponseCode) -> Vec<u8> {
+  let mut response = Message::new();
+  response
+    .set_id(request.id())
+    .set_message_type(MessageType::Response)
+    .set_op_code(request.op_code())
+    .set_response_code(code)
+    .add_queries(request.queries().iter().cloned());
+  response.to_vec().expect("a response without records always encodes")
+}
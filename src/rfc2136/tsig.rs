@@ -0,0 +1,293 @@
+//! TSIG message authentication (RFC 8945).
+
+use super::types::{TsigAlgorithm, TsigKeyConfig};
+use crate::secret::constant_time_eq;
+use crate::{Error, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hickory_proto::error::ProtoResult;
+use hickory_proto::op::{Header, Query};
+use hickory_proto::rr::{DNSClass, Name, RData, Record, RecordType};
+use hickory_proto::serialize::binary::{BinDecodable, BinDecoder};
+use hmac::digest::Digest;
+use hmac::digest::core_api::BlockSizeUser;
+use hmac::{Mac, SimpleHmac};
+use sha2::{Sha224, Sha256, Sha384, Sha512};
+
+/// The allowed clock difference, in seconds, for messages signed here.
+const DEFAULT_FUDGE: u16 = 300;
+
+/// The MAC did not verify.
+pub(crate) const BADSIG: u16 = 16;
+/// The key is unknown, or known with another algorithm.
+pub(crate) const BADKEY: u16 = 17;
+/// The signing time is outside the allowed clock difference.
+pub(crate) const BADTIME: u16 = 18;
+
+/// A shared secret used to sign and verify DNS messages.
+#[derive(Clone)]
+pub struct TsigKey {
+  name: Name,
+  algorithm: TsigAlgorithm,
+  secret: Vec<u8>,
+}
+
+impl std::fmt::Debug for TsigKey {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("TsigKey")
+      .field("name", &self.name)
+      .field("algorithm", &self.algorithm)
+      .finish_non_exhaustive()
+  }
+}
+
+impl TsigKey {
+  /// Creates a key.
+  ///
+  /// # Arguments
+  /// * `name` - The key name (e.g., "certbot").
+  /// * `algorithm` - The MAC algorithm.
+  /// * `secret` - The shared secret, already base64-decoded.
+  pub fn new(name: &str, algorithm: TsigAlgorithm, secret: &[u8]) -> Result<Self> {
+    let name = Name::from_ascii(name)
+      .map_err(|e| Error::InvalidInput(format!("Invalid TSIG key name '{}': {}", name, e)))?;
+    if secret.is_empty() {
+      return Err(Error::InvalidInput(format!("TSIG key '{}' has an empty secret", name)));
+    }
+    Ok(Self {
+      name: name.to_lowercase(),
+      algorithm,
+      secret: secret.to_vec(),
+    })
+  }
+
+  /// Creates a key from its configuration entry, decoding the base64 secret.
+  pub fn from_config(config: &TsigKeyConfig) -> Result<Self> {
+    let secret = STANDARD
+      .decode(config.secret.trim())
+      .map_err(|e| Error::InvalidInput(format!("Invalid secret for TSIG key '{}': {}", config.name, e)))?;
+    Self::new(&config.name, config.algorithm, &secret)
+  }
+
+  /// The key name.
+  pub fn name(&self) -> &Name {
+    &self.name
+  }
+
+  /// The MAC algorithm.
+  pub fn algorithm(&self) -> TsigAlgorithm {
+    self.algorithm
+  }
+
+  /// Signs a request in wire format, appending a TSIG record for the current time.
+  ///
+  /// # Arguments
+  /// * `message` - The complete, unsigned message.
+  pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+    if message.len() < 12 {
+      return Err(Error::InvalidInput("A DNS message is at least 12 bytes long".to_string()));
+    }
+    let mut tsig = self.tsig(now(), u16::from_be_bytes([message[0], message[1]]), 0);
+    tsig.mac = self.mac(&[message, &tsig.variables()]);
+    Ok(tsig.append_to(message))
+  }
+
+  /// Checks the MAC and signing time of a request split by [`split`].
+  pub(crate) fn verify(&self, covered: &[u8], tsig: &Tsig, now: u64) -> std::result::Result<(), u16> {
+    let expected = self.mac(&[covered, &tsig.variables()]);
+    if !constant_time_eq(&expected, &tsig.mac) {
+      return Err(BADSIG);
+    }
+    if now.abs_diff(tsig.time_signed) > u64::from(tsig.fudge) {
+      return Err(BADTIME);
+    }
+    Ok(())
+  }
+
+  /// Signs a response to a verified request.
+  ///
+  /// # Arguments
+  /// * `response` - The complete, unsigned response.
+  /// * `request` - The request's TSIG record, whose MAC is covered by the response's.
+  /// * `error` - The TSIG error code, `0` or [`BADTIME`].
+  /// * `now` - The current time, in seconds since the epoch.
+  pub(crate) fn sign_response(&self, response: &[u8], request: &Tsig, error: u16, now: u64) -> Vec<u8> {
+    let mut tsig = self.tsig(now, request.original_id, error);
+    if error == BADTIME {
+      // Echo the client's time and report ours, so the client can tell its clock is off.
+      tsig.time_signed = request.time_signed;
+      tsig.other = now.to_be_bytes()[2..].to_vec();
+    }
+    let request_mac_len = (request.mac.len() as u16).to_be_bytes();
+    tsig.mac = self.mac(&[&request_mac_len, &request.mac, response, &tsig.variables()]);
+    tsig.append_to(response)
+  }
+
+  fn tsig(&self, time_signed: u64, original_id: u16, error: u16) -> Tsig {
+    Tsig {
+      key_name: self.name.clone(),
+      algorithm: self.algorithm.name(),
+      time_signed,
+      fudge: DEFAULT_FUDGE,
+      mac: Vec::new(),
+      original_id,
+      error,
+      other: Vec::new(),
+    }
+  }
+
+  fn mac(&self, parts: &[&[u8]]) -> Vec<u8> {
+    match self.algorithm {
+      TsigAlgorithm::HmacSha224 => hmac::<Sha224>(&self.secret, parts),
+      TsigAlgorithm::HmacSha256 => hmac::<Sha256>(&self.secret, parts),
+      TsigAlgorithm::HmacSha384 => hmac::<Sha384>(&self.secret, parts),
+      TsigAlgorithm::HmacSha512 => hmac::<Sha512>(&self.secret, parts),
+    }
+  }
+}
+
+fn hmac<D: Digest + BlockSizeUser>(key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+  let mut mac = <SimpleHmac<D> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+  for part in parts {
+    mac.update(part);
+  }
+  mac.finalize().into_bytes().to_vec()
+}
+
+/// The TSIG record of a message.
+#[derive(Debug, Clone)]
+pub(crate) struct Tsig {
+  pub key_name: Name,
+  pub algorithm: Name,
+  /// Seconds since the epoch; 48 bits on the wire.
+  pub time_signed: u64,
+  pub fudge: u16,
+  pub mac: Vec<u8>,
+  pub original_id: u16,
+  pub error: u16,
+  pub other: Vec<u8>,
+}
+
+impl Tsig {
+  /// The TSIG variables covered by the MAC.
+  fn variables(&self) -> Vec<u8> {
+    let mut bytes = canonical_name(&self.key_name);
+    bytes.extend_from_slice(&u16::from(DNSClass::ANY).to_be_bytes());
+    bytes.extend_from_slice(&0u32.to_be_bytes());
+    bytes.extend(canonical_name(&self.algorithm));
+    bytes.extend_from_slice(&self.time_signed.to_be_bytes()[2..]);
+    bytes.extend_from_slice(&self.fudge.to_be_bytes());
+    bytes.extend_from_slice(&self.error.to_be_bytes());
+    bytes.extend_from_slice(&(self.other.len() as u16).to_be_bytes());
+    bytes.extend_from_slice(&self.other);
+    bytes
+  }
+
+  /// Appends this record to `message`, incrementing its additional record count.
+  pub fn append_to(&self, message: &[u8]) -> Vec<u8> {
+    let mut rdata = canonical_name(&self.algorithm);
+    rdata.extend_from_slice(&self.time_signed.to_be_bytes()[2..]);
+    rdata.extend_from_slice(&self.fudge.to_be_bytes());
+    rdata.extend_from_slice(&(self.mac.len() as u16).to_be_bytes());
+    rdata.extend_from_slice(&self.mac);
+    rdata.extend_from_slice(&self.original_id.to_be_bytes());
+    rdata.extend_from_slice(&self.error.to_be_bytes());
+    rdata.extend_from_slice(&(self.other.len() as u16).to_be_bytes());
+    rdata.extend_from_slice(&self.other);
+
+    let mut signed = message.to_vec();
+    let additional_count = u16::from_be_bytes([signed[10], signed[11]]).wrapping_add(1);
+    signed[10..12].copy_from_slice(&additional_count.to_be_bytes());
+    signed.extend(canonical_name(&self.key_name));
+    signed.extend_from_slice(&u16::from(RecordType::TSIG).to_be_bytes());
+    signed.extend_from_slice(&u16::from(DNSClass::ANY).to_be_bytes());
+    signed.extend_from_slice(&0u32.to_be_bytes());
+    signed.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+    signed.extend(rdata);
+    signed
+  }
+
+  /// Appends this record to an error response without a MAC, as for [`BADKEY`] and [`BADSIG`].
+  pub fn append_unsigned(&self, response: &[u8], error: u16) -> Vec<u8> {
+    let tsig = Tsig {
+      mac: Vec::new(),
+      error,
+      other: Vec::new(),
+      ..self.clone()
+    };
+    tsig.append_to(response)
+  }
+}
+
+/// Splits a message ending with a TSIG record into the bytes its MAC covers and the record.
+///
+/// The covered bytes are the message without the TSIG record, with the
+/// additional record count decremented and the original ID restored.
+pub(crate) fn split(message: &[u8]) -> ProtoResult<Option<(Vec<u8>, Tsig)>> {
+  let mut decoder = BinDecoder::new(message);
+  let header = Header::read(&mut decoder)?;
+  if header.additional_count() == 0 {
+    return Ok(None);
+  }
+  for _ in 0..header.query_count() {
+    Query::read(&mut decoder)?;
+  }
+  let records = usize::from(header.answer_count())
+    + usize::from(header.name_server_count())
+    + usize::from(header.additional_count())
+    - 1;
+  for _ in 0..records {
+    Record::<RData>::read(&mut decoder)?;
+  }
+
+  let start = decoder.index();
+  let key_name = Name::read(&mut decoder)?;
+  if RecordType::read(&mut decoder)? != RecordType::TSIG {
+    return Ok(None);
+  }
+  // CLASS, TTL and RDLENGTH carry nothing the MAC depends on.
+  decoder.read_u16()?;
+  decoder.read_u32()?;
+  decoder.read_u16()?;
+  let algorithm = Name::read(&mut decoder)?;
+  let time_high = u64::from(decoder.read_u16()?.unverified());
+  let time_signed = (time_high << 32) | u64::from(decoder.read_u32()?.unverified());
+  let fudge = decoder.read_u16()?.unverified();
+  let mac_len = decoder.read_u16()?.unverified();
+  let mac = decoder.read_slice(usize::from(mac_len))?.unverified().to_vec();
+  let original_id = decoder.read_u16()?.unverified();
+  let error = decoder.read_u16()?.unverified();
+  let other_len = decoder.read_u16()?.unverified();
+  let other = decoder.read_slice(usize::from(other_len))?.unverified().to_vec();
+
+  let mut covered = message[..start].to_vec();
+  covered[..2].copy_from_slice(&original_id.to_be_bytes());
+  covered[10..12].copy_from_slice(&(header.additional_count() - 1).to_be_bytes());
+  let tsig = Tsig {
+    key_name,
+    algorithm,
+    time_signed,
+    fudge,
+    mac,
+    original_id,
+    error,
+    other,
+  };
+  Ok(Some((covered, tsig)))
+}
+
+/// A name in canonical wire format: lowercase and uncompressed.
+fn canonical_name(name: &Name) -> Vec<u8> {
+  let mut bytes = Vec::new();
+  for label in name.to_lowercase().iter() {
+    bytes.push(label.len() as u8);
+    bytes.extend_from_slice(label);
+  }
+  bytes.push(0);
+  bytes
+}
+
+/// The current time, in seconds since the epoch.
+pub(crate) fn now() -> u64 {
+  chrono::Utc::now().timestamp().max(0) as u64
+}
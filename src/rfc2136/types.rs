@@ -0,0 +1,95 @@
+//! Contains the RFC 2136 server configuration and TSIG algorithm types.
+
+use crate::{Result, config};
+use hickory_proto::rr::Name;
+use serde::Deserialize;
+use std::path::Path;
+
+/// The configuration of an RFC 2136 server.
+///
+/// ```yaml
+/// min_ttl: 600
+/// keys:
+///   - name: certbot
+///     algorithm: hmac-sha256
+///     secret: "dXBkYXRlLWtleS1mb3ItZXhhbXBsZS5jb20tMzItYnl0ZXMh"
+///     zones:
+///       - example.com
+/// ```
+///
+/// Keys generated by `tsig-keygen` or `ddns-confgen` can be copied over as they are.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Rfc2136Config {
+  /// The TSIG keys accepted, each limited to a set of zones.
+  pub keys: Vec<TsigKeyConfig>,
+  /// The lowest TTL given to records added by an UPDATE message. Lower TTLs
+  /// in the message are raised to it; unset keeps them as sent. Clients such
+  /// as certbot-dns-rfc2136 send short TTLs that the registrar would reject,
+  /// so set this to its minimum.
+  #[serde(default)]
+  pub min_ttl: Option<u32>,
+}
+
+impl Rfc2136Config {
+  /// Parses a configuration from YAML text.
+  pub fn from_yaml(yaml: &str) -> Result<Self> {
+    config::parse_yaml(yaml, "RFC 2136")
+  }
+
+  /// Loads a configuration file. A file that cannot be read is an `Error::Io`.
+  pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+    config::load_yaml(path.as_ref(), "RFC 2136")
+  }
+}
+
+/// A TSIG key and the zones it may update.
+#[derive(Deserialize, Debug, Clone)]
+pub struct TsigKeyConfig {
+  /// The key name, as given to `nsupdate -y` or in the client's key file.
+  pub name: String,
+  #[serde(default)]
+  pub algorithm: TsigAlgorithm,
+  /// The base64-encoded shared secret.
+  pub secret: String,
+  /// The registered domains this key may update.
+  pub zones: Vec<String>,
+}
+
+/// A TSIG MAC algorithm (RFC 8945).
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TsigAlgorithm {
+  #[serde(rename = "hmac-sha224")]
+  HmacSha224,
+  #[default]
+  #[serde(rename = "hmac-sha256")]
+  HmacSha256,
+  #[serde(rename = "hmac-sha384")]
+  HmacSha384,
+  #[serde(rename = "hmac-sha512")]
+  HmacSha512,
+}
+
+impl TsigAlgorithm {
+  /// The algorithm's name as it appears in TSIG records (e.g., "hmac-sha256.").
+  pub fn name(&self) -> Name {
+    let name = match self {
+      TsigAlgorithm::HmacSha224 => "hmac-sha224.",
+      TsigAlgorithm::HmacSha256 => "hmac-sha256.",
+      TsigAlgorithm::HmacSha384 => "hmac-sha384.",
+      TsigAlgorithm::HmacSha512 => "hmac-sha512.",
+    };
+    Name::from_ascii(name).expect("algorithm names are valid domain names")
+  }
+
+  /// The algorithm named in a TSIG record, if supported.
+  pub fn from_name(name: &Name) -> Option<Self> {
+    [
+      TsigAlgorithm::HmacSha224,
+      TsigAlgorithm::HmacSha256,
+      TsigAlgorithm::HmacSha384,
+      TsigAlgorithm::HmacSha512,
+    ]
+    .into_iter()
+    .find(|algorithm| algorithm.name() == *name)
+  }
+}
@@ -0,0 +1,187 @@
+//! # Domain Router
+//!
+//! Finds the account that hosts a name when domains are spread across several
+//! registrar accounts.
+//!
+//! Automation is usually handed a fully qualified name, such as
+//! `_acme-challenge.api.eu.example.co.uk`, and has to work out which account
+//! holds the zone and what the name is relative to it. A [`DomainRouter`]
+//! indexes the domains of every account it is given, matches names against
+//! the longest registered domain they end with, and returns a [`Route`] with a
+//! DNS client already bound to that zone.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use registrar::config::Config;
+//! use registrar::records::Record;
+//! use registrar::router::DomainRouter;
+//!
+//! # async fn run() -> registrar::Result<()> {
+//! let router = DomainRouter::from_registry(&Config::load_default()?.registry()?)?;
+//! let route = router.route("_acme-challenge.api.eu.example.co.uk").await?;
+//! // route.zone == "example.co.uk", route.host == "_acme-challenge.api.eu"
+//! route.records.create_record(&Record::new(&route.host, "TXT", "token")).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The index is listed on first use and again once it is older than
+//! [`DomainRouter::DEFAULT_MAX_AGE`] (see [`DomainRouter::with_max_age`]). A
+//! name that matches no domain also triggers a refresh, at most once a minute,
+//! so that newly registered domains are found without waiting.
+//!
+//! To use this module, you must enable the `router` feature in your `Cargo.toml`.
+
+use crate::portfolio::DomainPortfolio;
+use crate::records::{DnsRecords, ZoneRecords};
+use crate::{DomainName, Error, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+pub mod types;
+
+pub use types::Route;
+
+/// How long after a refresh a name that matches no domain may cause another.
+const MISS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A registrar account known to the router.
+struct Account {
+  name: String,
+  portfolio: Arc<dyn DomainPortfolio>,
+  records: Arc<dyn DnsRecords>,
+}
+
+/// The domains of every account, by normalised name, and when they were listed.
+struct Index {
+  domains: HashMap<String, usize>,
+  refreshed: Instant,
+}
+
+/// Routes fully qualified names to the account and zone that hold them.
+pub struct DomainRouter {
+  accounts: Vec<Account>,
+  index: RwLock<Option<Arc<Index>>>,
+  max_age: Duration,
+}
+
+impl Default for DomainRouter {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl DomainRouter {
+  /// How long the domain index is used before it is listed again.
+  pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+  /// Creates a router without accounts.
+  pub fn new() -> Self {
+    Self { accounts: Vec::new(), index: RwLock::new(None), max_age: Self::DEFAULT_MAX_AGE }
+  }
+
+  /// Creates a router over every account in `registry`, in name order.
+  #[cfg(all(feature = "config", any(feature = "porkbun", feature = "name-com")))]
+  pub fn from_registry(registry: &crate::config::AccountRegistry) -> Result<Self> {
+    registry.names().try_fold(Self::new(), |router, name| {
+      Ok(router.with_account(name, registry.portfolio(name)?.into(), registry.dns_records(name)?.into()))
+    })
+  }
+
+  /// Adds an account. When a domain is listed by several accounts, the one added first is used.
+  ///
+  /// # Arguments
+  /// * `name` - The name reported in [`Route::account`].
+  /// * `portfolio` - Lists the account's domains.
+  /// * `records` - Manages the account's DNS records.
+  pub fn with_account(
+    mut self,
+    name: impl Into<String>,
+    portfolio: Arc<dyn DomainPortfolio>,
+    records: Arc<dyn DnsRecords>,
+  ) -> Self {
+    self.accounts.push(Account { name: name.into(), portfolio, records });
+    self.invalidate();
+    self
+  }
+
+  /// Sets how long the domain index is used before it is listed again.
+  pub fn with_max_age(mut self, max_age: Duration) -> Self {
+    self.max_age = max_age;
+    self
+  }
+
+  /// Discards the domain index, so that the next route lists every account again.
+  pub fn invalidate(&self) {
+    *self.index.write().unwrap_or_else(|e| e.into_inner()) = None;
+  }
+
+  /// Lists the domains of every account now. On failure, the previous index is kept.
+  pub async fn refresh(&self) -> Result<()> {
+    self.reload().await.map(|_| ())
+  }
+
+  /// Finds the account and zone holding `name`.
+  ///
+  /// # Arguments
+  /// * `name` - A fully qualified name, in Unicode or ASCII form, with or without a trailing dot.
+  pub async fn route(&self, name: &str) -> Result<Route> {
+    let name = DomainName::new(name)?;
+    let index = match self.cached() {
+      Some(index) => index,
+      None => self.reload().await?,
+    };
+    if let Some(route) = self.lookup(&index, &name) {
+      return Ok(route);
+    }
+    if index.refreshed.elapsed() >= MISS_REFRESH_INTERVAL
+      && let Some(route) = self.lookup(self.reload().await?.as_ref(), &name)
+    {
+      return Ok(route);
+    }
+    Err(Error::InvalidInput(format!("No configured account holds a domain containing '{}'", name)))
+  }
+
+  /// The index, unless it is missing or older than the maximum age.
+  fn cached(&self) -> Option<Arc<Index>> {
+    let index = self.index.read().unwrap_or_else(|e| e.into_inner());
+    index.as_ref().filter(|index| index.refreshed.elapsed() < self.max_age).cloned()
+  }
+
+  async fn reload(&self) -> Result<Arc<Index>> {
+    let mut domains = HashMap::new();
+    for (position, account) in self.accounts.iter().enumerate() {
+      for domain in account.portfolio.list_domains().await? {
+        let name = match DomainName::new(&domain.name) {
+          Ok(name) => name.into(),
+          Err(_) => domain.name.to_ascii_lowercase(),
+        };
+        domains.entry(name).or_insert(position);
+      }
+    }
+    let index = Arc::new(Index { domains, refreshed: Instant::now() });
+    *self.index.write().unwrap_or_else(|e| e.into_inner()) = Some(index.clone());
+    Ok(index)
+  }
+
+  /// Matches `name` against the longest indexed domain it equals or ends with.
+  fn lookup(&self, index: &Index, name: &str) -> Option<Route> {
+    let mut zone = name;
+    loop {
+      if let Some(&position) = index.domains.get(zone) {
+        let account = &self.accounts[position];
+        let host = name[..name.len() - zone.len()].trim_end_matches('.');
+        return Some(Route {
+          account: account.name.clone(),
+          provider: account.portfolio.provider(),
+          zone: zone.to_string(),
+          host: host.to_string(),
+          records: ZoneRecords::new(account.records.clone(), zone),
+        });
+      }
+      zone = zone.split_once('.')?.1;
+    }
+  }
+}
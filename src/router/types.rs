@@ -0,0 +1,31 @@
+//! Contains the result of routing a name to the account that hosts it.
+
+use crate::Provider;
+use crate::records::ZoneRecords;
+
+/// Where a fully qualified name lives: the account and zone holding it, and
+/// its name relative to that zone.
+#[derive(Clone)]
+pub struct Route {
+  /// The name of the account holding the zone.
+  pub account: String,
+  pub provider: Provider,
+  /// The registered domain containing the name, such as `example.co.uk`.
+  pub zone: String,
+  /// The name relative to the zone, as expected by [`crate::records::Record::host`].
+  /// An empty string is the apex.
+  pub host: String,
+  /// A DNS client bound to the zone.
+  pub records: ZoneRecords,
+}
+
+impl std::fmt::Debug for Route {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Route")
+      .field("account", &self.account)
+      .field("provider", &self.provider)
+      .field("zone", &self.zone)
+      .field("host", &self.host)
+      .finish_non_exhaustive()
+  }
+}
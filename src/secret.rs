@@ -0,0 +1,7 @@
+//! Comparisons of passwords, tokens and MACs.
+
+/// Compares two byte strings without exiting early on the first difference,
+/// so the time taken does not reveal how much of a secret was guessed.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+  a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
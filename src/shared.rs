@@ -0,0 +1,34 @@
+//! The parent client held by a scoped client.
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A client that is either borrowed, for scoped clients created from a
+/// reference, or shared through an `Arc`, for owned scoped clients.
+///
+/// Cloning is cheap in both cases.
+#[derive(Debug)]
+pub(crate) enum Shared<'a, T> {
+  Borrowed(&'a T),
+  Owned(Arc<T>),
+}
+
+impl<T> Clone for Shared<'_, T> {
+  fn clone(&self) -> Self {
+    match self {
+      Self::Borrowed(client) => Self::Borrowed(client),
+      Self::Owned(client) => Self::Owned(client.clone()),
+    }
+  }
+}
+
+impl<T> Deref for Shared<'_, T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    match self {
+      Self::Borrowed(client) => client,
+      Self::Owned(client) => client,
+    }
+  }
+}
@@ -0,0 +1,172 @@
+mod dns_server;
+mod memory_records;
+
+use dns_server::TestDnsServer;
+use hickory_proto::rr::rdata::NS;
+use hickory_proto::rr::{Name, RData, RecordType};
+use memory_records::MemoryRecords;
+use registrar::acme::{Dns01Solver, txt_value};
+use registrar::lookup::DnsLookup;
+use registrar::records::{DnsRecords, Record};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// An in-memory registrar whose TXT records are optionally served by a test DNS server.
+struct PublishedRecords {
+  records: MemoryRecords,
+  server: Option<TestDnsServer>,
+  /// The names given TXT records on the server, so they can be cleared once their last record is gone.
+  published: Mutex<BTreeSet<String>>,
+}
+
+impl PublishedRecords {
+  fn new(server: Option<TestDnsServer>) -> Arc<Self> {
+    Arc::new(Self { records: MemoryRecords::default(), server, published: Mutex::default() })
+  }
+
+  /// Serves the current TXT records, and none at names that no longer have any.
+  fn publish(&self) {
+    let Some(server) = &self.server else { return };
+    let mut values: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (domain, record) in self.records.all() {
+      if record.record_type == "TXT" {
+        values.entry(record.fqdn(&domain)).or_default().push(record.content);
+      }
+    }
+    let mut published = self.published.lock().unwrap();
+    for fqdn in published.iter().filter(|fqdn| !values.contains_key(*fqdn)) {
+      server.set_txt(fqdn, &[]);
+    }
+    for (fqdn, contents) in &values {
+      server.set_txt(fqdn, &contents.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+    *published = values.into_keys().collect();
+  }
+}
+
+#[async_trait::async_trait]
+impl DnsRecords for PublishedRecords {
+  async fn list_records(&self, domain: &str) -> registrar::Result<Vec<Record>> {
+    self.records.list_records(domain).await
+  }
+
+  async fn create_record(&self, domain: &str, record: &Record) -> registrar::Result<String> {
+    let id = self.records.create_record(domain, record).await?;
+    self.publish();
+    Ok(id)
+  }
+
+  async fn update_record(&self, domain: &str, record: &Record) -> registrar::Result<()> {
+    self.records.update_record(domain, record).await?;
+    self.publish();
+    Ok(())
+  }
+
+  async fn delete_record(&self, domain: &str, id: &str) -> registrar::Result<()> {
+    self.records.delete_record(domain, id).await?;
+    self.publish();
+    Ok(())
+  }
+}
+
+async fn zone_server() -> TestDnsServer {
+  let server = TestDnsServer::start().await;
+  let ns = RData::NS(NS(Name::from_ascii("ns1.example.com.").unwrap()));
+  server.set("example.com", RecordType::NS, vec![ns]);
+  server
+}
+
+#[tokio::test]
+async fn solves_challenge_and_cleans_up() {
+  let server = zone_server().await;
+  let records = PublishedRecords::new(Some(server.clone()));
+  let lookup = DnsLookup::new(vec![server.addr]);
+  let solver = Dns01Solver::with_resolver(records.clone(), lookup.clone())
+    .with_authoritative(lookup)
+    .with_propagation(Duration::from_secs(2), Duration::from_millis(10));
+
+  let seen = solver
+    .solve("*.www.example.com", "token.thumbprint", || async {
+      records.list_records("example.com").await
+    })
+    .await
+    .unwrap();
+  assert_eq!(seen.len(), 1);
+  assert_eq!(seen[0].host, "_acme-challenge.www");
+  assert_eq!(seen[0].record_type, "TXT");
+  assert_eq!(seen[0].content, txt_value("token.thumbprint"));
+  assert!(records.list_records("example.com").await.unwrap().is_empty());
+
+  // The record is removed even when validation fails.
+  let result: registrar::Result<()> = solver
+    .solve("example.com", "token.thumbprint", || async {
+      Err(registrar::Error::Api("invalid authorization".to_string()))
+    })
+    .await;
+  assert!(matches!(result, Err(registrar::Error::Api(_))));
+  assert!(records.list_records("example.com").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn removes_record_when_propagation_times_out() {
+  let server = zone_server().await;
+  // The registrar accepts the record but the nameserver never serves it.
+  let records = PublishedRecords::new(None);
+  let lookup = DnsLookup::new(vec![server.addr]);
+  let solver = Dns01Solver::with_resolver(records.clone(), lookup.clone())
+    .with_authoritative(lookup)
+    .with_zone("example.com")
+    .with_propagation(Duration::from_millis(50), Duration::from_millis(10));
+
+  let result = solver.present("www.example.com", "token.thumbprint").await;
+  assert!(matches!(result, Err(registrar::Error::Dns(_))));
+  assert!(records.list_records("example.com").await.unwrap().is_empty());
+}
+
+#[test]
+fn txt_value_is_unpadded_base64url_sha256() {
+  // SHA-256 of the empty string, base64url-encoded without padding.
+  assert_eq!(txt_value(""), "47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU");
+}
+
+#[tokio::test]
+async fn cleans_up_by_value_without_the_challenge_handle() {
+  let server = zone_server().await;
+  let records = PublishedRecords::new(Some(server.clone()));
+  let lookup = DnsLookup::new(vec![server.addr]);
+  let solver = Dns01Solver::with_resolver(records.clone(), lookup.clone())
+    .with_authoritative(lookup)
+    .with_propagation(Duration::from_secs(2), Duration::from_millis(10));
+
+  // Wildcard and apex challenges share a record name but carry different values.
+  solver.present_value("*.example.com", "first").await.unwrap();
+  solver.present_value("example.com", "second").await.unwrap();
+  solver.cleanup_value("example.com", "first").await.unwrap();
+  let remaining = records.list_records("example.com").await.unwrap();
+  assert_eq!(remaining.len(), 1);
+  assert_eq!(remaining[0].content, "second");
+
+  // Cleaning up a record that is already gone is not an error.
+  solver.cleanup_value("example.com", "first").await.unwrap();
+}
+
+#[tokio::test]
+async fn reports_lookup_failures_when_propagation_times_out() {
+  let server = zone_server().await;
+  let records = PublishedRecords::new(Some(server.clone()));
+  // Nothing answers on this port, so every propagation check fails.
+  let silent = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+  let unreachable = DnsLookup::new(vec![silent.local_addr().unwrap()]).with_timeout(Duration::from_millis(10));
+  let solver = Dns01Solver::with_resolver(records.clone(), DnsLookup::new(vec![server.addr]))
+    .with_authoritative(unreachable)
+    .with_zone("example.com")
+    .with_propagation(Duration::from_millis(50), Duration::from_millis(10));
+
+  let Err(registrar::Error::Dns(message)) = solver.present("www.example.com", "token.thumbprint").await else {
+    panic!("expected a DNS error");
+  };
+  // The lookup error follows the record name.
+  assert!(message.contains("the TXT record at _acme-challenge.www.example.com: "), "{}", message);
+  assert!(records.list_records("example.com").await.unwrap().is_empty());
+}
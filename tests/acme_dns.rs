@@ -0,0 +1,159 @@
+mod memory_records;
+
+use memory_records::MemoryRecords;
+use registrar::acme_dns::{AcmeDnsConfig, AcmeDnsServer, Registration};
+use serde_json::{Value, json};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+const CONFIG: &str = r#"
+domain: acme.example.com
+zone: example.com
+"#;
+
+const TXT_1: &str = "LPsIwTo7o8BoG0-vjCyGQGBWSVIPxI-i_X336eUOQZo";
+const TXT_2: &str = "VrdGW0hDmIs4bAWupVURjmwz7n0pRZ4wtXlTR0kxUSo";
+const TXT_3: &str = "ArTRoBvu0N7eJf4y8Fc8WBJ1u_L7xoVUkZzuwkqymH8";
+const TXT_4: &str = "47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU";
+
+async fn start(records: Arc<MemoryRecords>, config: &str) -> SocketAddr {
+  let server = AcmeDnsServer::new(records, AcmeDnsConfig::from_yaml(config).unwrap()).unwrap();
+  let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let addr = listener.local_addr().unwrap();
+  tokio::spawn(server.serve(listener));
+  addr
+}
+
+async fn register(addr: SocketAddr, body: Value) -> (u16, Value) {
+  let response = reqwest::Client::new()
+    .post(format!("http://{}/register", addr))
+    .json(&body)
+    .send()
+    .await
+    .unwrap();
+  (response.status().as_u16(), response.json().await.unwrap())
+}
+
+async fn update(addr: SocketAddr, account: &Registration, subdomain: &str, txt: &str) -> (u16, Value) {
+  let response = reqwest::Client::new()
+    .post(format!("http://{}/update", addr))
+    .header("X-Api-User", &account.username)
+    .header("X-Api-Key", &account.password)
+    .json(&json!({ "subdomain": subdomain, "txt": txt }))
+    .send()
+    .await
+    .unwrap();
+  (response.status().as_u16(), response.json().await.unwrap())
+}
+
+#[tokio::test]
+async fn registers_and_keeps_two_most_recent_values() {
+  let records = Arc::new(MemoryRecords::default());
+  let addr = start(records.clone(), CONFIG).await;
+
+  let (status, body) = register(addr, json!({})).await;
+  assert_eq!(status, 201);
+  let account: Registration = serde_json::from_value(body).unwrap();
+  assert_eq!(account.fulldomain, format!("{}.acme.example.com", account.subdomain));
+  assert_eq!(account.password.len(), 40);
+
+  let host = format!("{}.acme", account.subdomain);
+  for txt in [TXT_1, TXT_2, TXT_3] {
+    let (status, body) = update(addr, &account, &account.subdomain, txt).await;
+    assert_eq!((status, body), (200, json!({ "txt": txt })));
+  }
+  let mut values: Vec<String> = records
+    .find("example.com", &host, "TXT")
+    .into_iter()
+    .map(|record| record.content)
+    .collect();
+  values.sort();
+  assert_eq!(values, [TXT_3, TXT_2]);
+}
+
+#[tokio::test]
+async fn retries_deleting_an_old_value_after_a_failure() {
+  let records = Arc::new(MemoryRecords::default());
+  let addr = start(records.clone(), CONFIG).await;
+  let (_, body) = register(addr, json!({})).await;
+  let account: Registration = serde_json::from_value(body).unwrap();
+  let host = format!("{}.acme", account.subdomain);
+
+  update(addr, &account, &account.subdomain, TXT_1).await;
+  update(addr, &account, &account.subdomain, TXT_2).await;
+  records.fail_deletes.store(true, std::sync::atomic::Ordering::SeqCst);
+  let (status, _) = update(addr, &account, &account.subdomain, TXT_3).await;
+  assert_eq!(status, 500);
+  assert_eq!(records.find("example.com", &host, "TXT").len(), 3);
+
+  // The next update deletes the value whose deletion failed, not just the one it displaces.
+  records.fail_deletes.store(false, std::sync::atomic::Ordering::SeqCst);
+  let (status, _) = update(addr, &account, &account.subdomain, TXT_4).await;
+  assert_eq!(status, 200);
+  let mut values: Vec<String> = records
+    .find("example.com", &host, "TXT")
+    .into_iter()
+    .map(|record| record.content)
+    .collect();
+  values.sort();
+  assert_eq!(values, [TXT_4, TXT_3]);
+}
+
+#[tokio::test]
+async fn rejects_bad_credentials_and_values() {
+  let records = Arc::new(MemoryRecords::default());
+  let addr = start(records.clone(), CONFIG).await;
+  let (_, body) = register(addr, json!({})).await;
+  let account: Registration = serde_json::from_value(body).unwrap();
+
+  let mut impostor = account.clone();
+  impostor.password = "x".repeat(40);
+  let (status, body) = update(addr, &impostor, &account.subdomain, TXT_1).await;
+  assert_eq!((status, body), (401, json!({ "error": "forbidden" })));
+
+  let (status, body) = update(addr, &account, &account.subdomain, "too-short").await;
+  assert_eq!((status, body), (400, json!({ "error": "bad_txt" })));
+
+  let (status, body) = update(addr, &account, "someone-else", TXT_1).await;
+  assert_eq!((status, body), (400, json!({ "error": "bad_subdomain" })));
+
+  assert_eq!(records.calls(), 0);
+}
+
+#[tokio::test]
+async fn enforces_allowfrom() {
+  let records = Arc::new(MemoryRecords::default());
+  let addr = start(records.clone(), CONFIG).await;
+
+  let (status, body) = register(addr, json!({ "allowfrom": ["not-a-network"] })).await;
+  assert_eq!((status, body), (400, json!({ "error": "invalid_allowfrom_cidr" })));
+
+  let (_, body) = register(addr, json!({ "allowfrom": ["192.0.2.0/24"] })).await;
+  let account: Registration = serde_json::from_value(body).unwrap();
+  assert_eq!(account.allow_from, ["192.0.2.0/24"]);
+  let (status, _) = update(addr, &account, &account.subdomain, TXT_1).await;
+  assert_eq!(status, 401);
+
+  let (_, body) = register(addr, json!({ "allowfrom": ["127.0.0.0/8"] })).await;
+  let account: Registration = serde_json::from_value(body).unwrap();
+  let (status, _) = update(addr, &account, &account.subdomain, TXT_1).await;
+  assert_eq!(status, 200);
+}
+
+#[tokio::test]
+async fn persists_accounts_and_can_disable_registration() {
+  let path = std::env::temp_dir().join(format!("registrar-acme-dns-{}.json", uuid::Uuid::new_v4()));
+  let config = format!("{}storage: {}\n", CONFIG, path.display());
+  let records = Arc::new(MemoryRecords::default());
+  let (_, body) = register(start(records.clone(), &config).await, json!({})).await;
+  let account: Registration = serde_json::from_value(body).unwrap();
+
+  let config = format!("{}disable_registration: true\n", config);
+  let addr = start(records.clone(), &config).await;
+  let (status, body) = register(addr, json!({})).await;
+  assert_eq!((status, body), (401, json!({ "error": "registration_disabled" })));
+  let (status, _) = update(addr, &account, &account.subdomain, TXT_1).await;
+  assert_eq!(status, 200);
+
+  std::fs::remove_file(path).unwrap();
+}
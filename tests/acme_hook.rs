@@ -0,0 +1,93 @@
+use registrar::acme::{HookAction, txt_value};
+use std::collections::HashMap;
+use std::process::Command;
+
+fn parse(args: &[&str], env: &[(&str, &str)]) -> registrar::Result<HookAction> {
+  let env: HashMap<String, String> = env.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+  HookAction::parse(args, |name| env.get(name).cloned())
+}
+
+fn present(identifier: &str, value: &str) -> HookAction {
+  HookAction::Present { identifier: identifier.to_string(), value: value.to_string() }
+}
+
+fn cleanup(identifier: &str, value: &str) -> HookAction {
+  HookAction::Cleanup { identifier: identifier.to_string(), value: value.to_string() }
+}
+
+#[test]
+fn reads_certbot_hooks_from_the_environment() {
+  let env = [("CERTBOT_DOMAIN", "www.example.com"), ("CERTBOT_VALIDATION", "abc")];
+  assert_eq!(parse(&[], &env).unwrap(), present("www.example.com", "abc"));
+  assert_eq!(parse(&["auth"], &env).unwrap(), present("www.example.com", "abc"));
+
+  // certbot only sets CERTBOT_AUTH_OUTPUT when running the cleanup hook.
+  let cleanup_env = [env[0], env[1], ("CERTBOT_AUTH_OUTPUT", "")];
+  assert_eq!(parse(&[], &cleanup_env).unwrap(), cleanup("www.example.com", "abc"));
+  assert_eq!(parse(&["cleanup"], &env).unwrap(), cleanup("www.example.com", "abc"));
+
+  assert!(matches!(parse(&[], &env[..1]), Err(registrar::Error::InvalidInput(_))));
+  assert!(matches!(parse(&[], &[]), Err(registrar::Error::InvalidInput(_))));
+}
+
+#[test]
+fn reads_the_challenge_fqdn_and_value() {
+  // lego's default mode and acme.sh pass the challenge record name with or without a trailing dot.
+  assert_eq!(
+    parse(&["present", "_acme-challenge.www.example.com.", "-abc"], &[]).unwrap(),
+    present("www.example.com", "-abc")
+  );
+  assert_eq!(parse(&["cleanup", "_acme-challenge.example.com", "abc"], &[]).unwrap(), cleanup("example.com", "abc"));
+  // A name without the challenge label is taken as the identifier.
+  assert_eq!(parse(&["present", "example.com", "abc"], &[]).unwrap(), present("example.com", "abc"));
+  assert_eq!(
+    parse(&["present", "_acme-challenge-x.example.com", "abc"], &[]).unwrap(),
+    present("_acme-challenge-x.example.com", "abc")
+  );
+}
+
+#[test]
+fn reads_lego_raw_mode() {
+  let value = txt_value("token.thumbprint");
+  assert_eq!(
+    parse(&["present", "--", "example.com", "token", "token.thumbprint"], &[]).unwrap(),
+    present("example.com", &value)
+  );
+  assert_eq!(
+    parse(&["cleanup", "--", "example.com", "token", "token.thumbprint"], &[]).unwrap(),
+    cleanup("example.com", &value)
+  );
+  // RAW mode needs the separator; without it, three arguments are ambiguous.
+  assert!(parse(&["present", "example.com", "token", "token.thumbprint"], &[]).is_err());
+}
+
+#[test]
+fn rejects_unknown_modes() {
+  assert!(matches!(parse(&["renew", "example.com", "abc"], &[]), Err(registrar::Error::InvalidInput(_))));
+}
+
+#[test]
+fn prints_help_successfully() {
+  let output = Command::new(env!("CARGO_BIN_EXE_registrar-acme-hook")).arg("--help").output().unwrap();
+  assert!(output.status.success());
+  let help = String::from_utf8(output.stdout).unwrap();
+  assert!(help.contains("EXEC_MODE=RAW"), "{}", help);
+}
+
+#[test]
+fn passes_the_raw_separator_to_the_mode() {
+  let missing = std::env::temp_dir().join(format!("registrar-acme-hook-missing-{}.yaml", std::process::id()));
+  let run = |args: &[&str]| {
+    let output = Command::new(env!("CARGO_BIN_EXE_registrar-acme-hook"))
+      .args(args)
+      .env("REGISTRAR_CONFIG", &missing)
+      .env_remove("REGISTRAR_PROVIDER")
+      .env_remove("REGISTRAR_ACCOUNT")
+      .output()
+      .unwrap();
+    output.status.code()
+  };
+  // Arguments that parse reach the configuration, which is missing (1); usage errors exit with 2.
+  assert_eq!(run(&["present", "--", "example.com", "token", "token.thumbprint"]), Some(1));
+  assert_eq!(run(&["present", "example.com", "token", "token.thumbprint"]), Some(2));
+}
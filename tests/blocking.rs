@@ -0,0 +1,38 @@
+mod mock_name_com;
+
+use mock_name_com::MockNameCom;
+use registrar::blocking::NameDotCom;
+use registrar::DomainName;
+use registrar::name_com::types::PageCursor;
+use std::time::Duration;
+
+#[test]
+fn lists_without_an_async_runtime() {
+  let mock = MockNameCom::start(5, Duration::from_millis(50));
+  let client = NameDotCom::from_client(mock.client()).unwrap().with_page_concurrency(2);
+
+  let domains = client.domains().list().unwrap();
+  assert_eq!(domains.len(), 5);
+  assert_eq!(domains[4].domain_name, "domain0005.com");
+  // Pages after the first are still fetched concurrently.
+  assert_eq!(mock.max_in_flight(), 2);
+
+  let records = client.dns(&DomainName::new("example.com").unwrap()).list_records().unwrap();
+  assert_eq!(records.iter().map(|record| record.id).collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn iterates_page_by_page() {
+  let mock = MockNameCom::start(5, Duration::ZERO);
+  let client = NameDotCom::from_client(mock.client()).unwrap().with_page_concurrency(1);
+  let domains = client.domains();
+
+  let mut pages = domains.pages(PageCursor::first());
+  let first = pages.next().unwrap().unwrap();
+  assert_eq!(first.items.len(), 2);
+  assert_eq!(mock.requests(), ["/core/v1/domains/?page=1"]);
+  assert_eq!(pages.map(|page| page.unwrap().items.len()).collect::<Vec<_>>(), [2, 1]);
+
+  let names: Vec<_> = domains.iter(first.next.unwrap()).map(|domain| domain.unwrap().domain_name).collect();
+  assert_eq!(names, ["domain0003.com", "domain0004.com", "domain0005.com"]);
+}
@@ -0,0 +1,121 @@
+mod dns_server;
+
+use dns_server::TestDnsServer;
+use hickory_proto::rr::RecordType;
+use registrar::dnssec::{CdsSync, DsRecord, DsRegistry};
+use registrar::lookup::DnsLookup;
+use std::sync::Mutex;
+
+/// An in-memory registry standing in for a registrar.
+#[derive(Default)]
+struct MemoryRegistry {
+  records: Mutex<Vec<DsRecord>>,
+  /// Removes every record sharing the key tag, as Porkbun does.
+  by_key_tag: bool,
+}
+
+#[async_trait::async_trait]
+impl DsRegistry for MemoryRegistry {
+  async fn list_ds(&self, _domain: &str) -> registrar::Result<Vec<DsRecord>> {
+    Ok(self.records.lock().unwrap().clone())
+  }
+
+  async fn add_ds(&self, _domain: &str, ds: &DsRecord) -> registrar::Result<()> {
+    self.records.lock().unwrap().push(ds.clone());
+    Ok(())
+  }
+
+  async fn remove_ds(&self, _domain: &str, ds: &DsRecord) -> registrar::Result<()> {
+    if self.by_key_tag {
+      self.records.lock().unwrap().retain(|existing| existing.key_tag != ds.key_tag);
+    } else {
+      self.records.lock().unwrap().retain(|existing| existing != ds);
+    }
+    Ok(())
+  }
+
+  fn removes_by_key_tag(&self) -> bool {
+    self.by_key_tag
+  }
+}
+
+/// Encodes DS RDATA in wire format.
+fn ds_rdata(ds: &DsRecord) -> Vec<u8> {
+  let mut rdata = ds.key_tag.to_be_bytes().to_vec();
+  rdata.push(ds.algorithm.into());
+  rdata.push(ds.digest_type.into());
+  rdata.extend((0..ds.digest.len()).step_by(2).map(|i| u8::from_str_radix(&ds.digest[i..i + 2], 16).unwrap()));
+  rdata
+}
+
+// The CDNSKEY and expected DS from RFC 6605.
+const CDNSKEY_RDATA_B64: &str =
+  "GojIhhXUN/u4v54ZQqGSnyhWJwaubCvTmeexv7bR6edbkrSqQpF64cYbcB7wNcP+e+MAnLr+Wi9xMWyQLc8NAA==";
+const EXPECTED_DS: &str = "55648 13 2 B4C8C1FE2E7477127B27115656AD6256F424625BF5C1E2770CE6D6E37DF61D17";
+
+#[tokio::test]
+async fn syncs_ds_set_from_cds_and_cdnskey() {
+  use base64::Engine;
+  let server = TestDnsServer::start().await;
+  let registry = MemoryRegistry::default();
+  let old: DsRecord = "11111 13 2 AAAA".parse().unwrap();
+  registry.records.lock().unwrap().push(old.clone());
+
+  // No CDS/CDNSKEY published: nothing changes.
+  let sync = CdsSync::new(&registry, DnsLookup::new(vec![server.addr]));
+  assert!(sync.sync("example.net").await.unwrap().is_empty());
+
+  // A CDNSKEY is converted to its SHA-256 DS and replaces the old record.
+  let mut cdnskey = vec![0x01, 0x01, 3, 13];
+  cdnskey.extend(base64::engine::general_purpose::STANDARD.decode(CDNSKEY_RDATA_B64).unwrap());
+  server.set_raw("example.net", RecordType::CDNSKEY, vec![cdnskey]);
+  let plan = sync.sync("example.net").await.unwrap();
+  let expected: DsRecord = EXPECTED_DS.parse().unwrap();
+  assert_eq!(plan.add, vec![expected.clone()]);
+  assert_eq!(plan.remove, vec![old]);
+  assert_eq!(*registry.records.lock().unwrap(), vec![expected.clone()]);
+
+  // A CDS record takes precedence and the sync is idempotent.
+  server.set_raw("example.net", RecordType::CDS, vec![ds_rdata(&expected)]);
+  assert!(sync.sync("example.net").await.unwrap().is_empty());
+
+  // The RFC 8078 delete signal removes every DS record.
+  server.set_raw("example.net", RecordType::CDS, vec![vec![0, 0, 0, 0, 0]]);
+  let plan = sync.sync("example.net").await.unwrap();
+  assert_eq!(plan.remove, vec![expected]);
+  assert!(registry.records.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn refuses_to_remove_a_ds_sharing_a_desired_key_tag() {
+  let server = TestDnsServer::start().await;
+  let expected: DsRecord = EXPECTED_DS.parse().unwrap();
+  // The same key with a SHA-1 digest, which removal by key tag would take down with the new record.
+  let old: DsRecord = "55648 13 1 AAAA".parse().unwrap();
+  let registry = MemoryRegistry { by_key_tag: true, ..Default::default() };
+  registry.records.lock().unwrap().push(old.clone());
+  server.set_raw("example.net", RecordType::CDS, vec![ds_rdata(&expected)]);
+
+  let sync = CdsSync::new(&registry, DnsLookup::new(vec![server.addr]));
+  assert!(matches!(sync.plan("example.net").await, Err(registrar::Error::Unsupported(_))));
+  assert!(matches!(sync.sync("example.net").await, Err(registrar::Error::Unsupported(_))));
+  assert_eq!(*registry.records.lock().unwrap(), vec![old]);
+
+  // The delete signal removes every record, so sharing a key tag is harmless.
+  server.set_raw("example.net", RecordType::CDS, vec![vec![0, 0, 0, 0, 0]]);
+  sync.sync("example.net").await.unwrap();
+  assert!(registry.records.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn refuses_to_sync_when_servers_disagree() {
+  let first = TestDnsServer::start().await;
+  let second = TestDnsServer::start().await;
+  let ds: DsRecord = "22222 13 2 BBBB".parse().unwrap();
+  first.set_raw("example.net", RecordType::CDS, vec![ds_rdata(&ds)]);
+
+  let registry = MemoryRegistry::default();
+  let sync = CdsSync::new(&registry, DnsLookup::new(vec![first.addr, second.addr]));
+  assert!(matches!(sync.sync("example.net").await, Err(registrar::Error::Dns(_))));
+  assert!(registry.records.lock().unwrap().is_empty());
+}
@@ -8,7 +8,7 @@ use std::thread::{self, JoinHandle};
 use c5store::{C5Store, C5StoreOptions, create_c5store};
 use ctor::{ctor, dtor};
 use once_cell::sync::{Lazy, OnceCell};
-use registrar::{name_com::NameDotCom, porkbun::Porkbun};
+use registrar::{DomainName, name_com::NameDotCom, porkbun::Porkbun};
 use serde::Deserialize;
 use std::path::PathBuf;
 use tokio::runtime::Handle;
@@ -105,14 +105,14 @@ pub fn namecom_dev_client() -> NameDotCom {
 
 pub struct PorkbunDnsRecordGuard {
   pub id: u64,
-  pub domain_name: String,
+  pub domain_name: DomainName,
   client: Porkbun,
 }
 impl PorkbunDnsRecordGuard {
   pub fn new(id: u64, domain_name: &str, client: &Porkbun) -> Self {
     Self {
       id,
-      domain_name: domain_name.to_string(),
+      domain_name: DomainName::new(domain_name).unwrap(),
       client: client.clone(),
     }
   }
@@ -127,14 +127,14 @@ impl PorkbunDnsRecordGuard {
 
 pub struct PorkbunUrlForwardGuard {
   pub id: u64,
-  pub domain_name: String,
+  pub domain_name: DomainName,
   client: Porkbun,
 }
 impl PorkbunUrlForwardGuard {
   pub fn new(id: u64, domain_name: &str, client: &Porkbun) -> Self {
     Self {
       id,
-      domain_name: domain_name.to_string(),
+      domain_name: DomainName::new(domain_name).unwrap(),
       client: client.clone(),
     }
   }
@@ -149,14 +149,14 @@ impl PorkbunUrlForwardGuard {
 
 pub struct PorkbunGlueRecordGuard {
   pub subdomain: String,
-  pub domain_name: String,
+  pub domain_name: DomainName,
   client: Porkbun,
 }
 impl PorkbunGlueRecordGuard {
   pub fn new(subdomain: String, domain_name: &str, client: &Porkbun) -> Self {
     Self {
       subdomain,
-      domain_name: domain_name.to_string(),
+      domain_name: DomainName::new(domain_name).unwrap(),
       client: client.clone(),
     }
   }
@@ -187,14 +187,14 @@ impl Drop for PorkbunGlueRecordGuard {
 
 pub struct NameComDnsRecordGuard {
   pub id: i32,
-  pub domain_name: String,
+  pub domain_name: DomainName,
   client: NameDotCom,
 }
 impl NameComDnsRecordGuard {
   pub fn new(id: i32, domain_name: &str, client: &NameDotCom) -> Self {
     Self {
       id,
-      domain_name: domain_name.to_string(),
+      domain_name: DomainName::new(domain_name).unwrap(),
       client: client.clone(),
     }
   }
@@ -209,14 +209,14 @@ impl NameComDnsRecordGuard {
 
 pub struct NameComUrlForwardGuard {
   pub host: String, // This is the subdomain
-  pub domain_name: String,
+  pub domain_name: DomainName,
   client: NameDotCom,
 }
 impl NameComUrlForwardGuard {
   pub fn new(host: String, domain_name: &str, client: &NameDotCom) -> Self {
     Self {
       host,
-      domain_name: domain_name.to_string(),
+      domain_name: DomainName::new(domain_name).unwrap(),
       client: client.clone(),
     }
   }
@@ -231,14 +231,14 @@ impl NameComUrlForwardGuard {
 
 pub struct NameComVanityNsGuard {
   pub hostname: String, // This is the full hostname
-  pub domain_name: String,
+  pub domain_name: DomainName,
   client: NameDotCom,
 }
 impl NameComVanityNsGuard {
   pub fn new(hostname: String, domain_name: &str, client: &NameDotCom) -> Self {
     Self {
       hostname,
-      domain_name: domain_name.to_string(),
+      domain_name: DomainName::new(domain_name).unwrap(),
       client: client.clone(),
     }
   }
@@ -0,0 +1,146 @@
+use registrar::Provider;
+use registrar::config::{AccountConfig, Config, ConfigFormat};
+
+#[test]
+fn parses_credentials_and_selects_provider() {
+  let config = Config::from_yaml(
+    r#"
+porkbun:
+  apikey: "pk1_key"
+  secretapikey: "sk1_secret"
+"#,
+  )
+  .unwrap();
+  assert_eq!(config.porkbun.as_ref().unwrap().apikey, "pk1_key");
+  assert!(config.name_com.is_none());
+  assert_eq!(config.select_provider(None).unwrap(), Provider::Porkbun);
+  assert_eq!(config.select_provider(Some(Provider::NameCom)).unwrap(), Provider::NameCom);
+  assert!(config.name_com().is_err());
+}
+
+#[test]
+fn requires_a_choice_between_several_providers() {
+  let yaml = r#"
+porkbun: { apikey: a, secretapikey: b }
+name_com: { username: u, token: t, host: "https://api.dev.name.com" }
+"#;
+  let config = Config::from_yaml(yaml).unwrap();
+  assert!(matches!(config.select_provider(None), Err(registrar::Error::InvalidInput(_))));
+
+  let config = Config::from_yaml(&format!("provider: name_com\n{}", yaml)).unwrap();
+  assert_eq!(config.select_provider(None).unwrap(), Provider::NameCom);
+  assert!(config.dns_records(Provider::NameCom).is_ok());
+}
+
+#[test]
+fn rejects_malformed_yaml() {
+  assert!(matches!(
+    Config::from_yaml("porkbun: [1, 2"),
+    Err(registrar::Error::InvalidInput(_))
+  ));
+}
+
+#[test]
+fn takes_credentials_from_variables() {
+  let config = Config::from_yaml("porkbun: { apikey: file, secretapikey: file }").unwrap();
+  let vars = |name: &str| match name {
+    "PORKBUN_API_KEY" => Some("pk1_env".to_string()),
+    "PORKBUN_SECRET_KEY" => Some("sk1_env".to_string()),
+    "NAMECOM_USERNAME" => Some("user".to_string()),
+    _ => None,
+  };
+  let config = config.with_vars(vars);
+  assert_eq!(config.porkbun.as_ref().unwrap().apikey, "pk1_env");
+  // Name.com needs a token as well as a username.
+  assert!(config.name_com.is_none());
+
+  let config = Config::default().with_vars(|name| Some(format!("{}-value", name)));
+  let name_com = config.name_com.as_ref().unwrap();
+  assert_eq!(name_com.username, "NAMECOM_USERNAME-value");
+  assert_eq!(name_com.host.as_deref(), Some("NAMECOM_HOST-value"));
+}
+
+#[test]
+fn selects_named_accounts() {
+  let yaml = r#"
+default_account: personal
+porkbun: { apikey: a, secretapikey: b }
+accounts:
+  personal: { provider: porkbun, apikey: c, secretapikey: d }
+  agency: { provider: name-com, username: u, token: t }
+  client: { provider: name_com, username: v, token: w }
+"#;
+  let config = Config::from_yaml(yaml).unwrap();
+  // The provider-level helpers go through the same selection.
+  assert!(config.portfolio(Provider::Porkbun).is_ok());
+  assert!(config.dns_records(Provider::NameCom).is_err());
+
+  let registry = config.registry().unwrap();
+  assert_eq!(registry.names().collect::<Vec<_>>(), ["agency", "client", "personal", "porkbun"]);
+  assert_eq!(registry.provider("client").unwrap(), Provider::NameCom);
+  assert_eq!(registry.select(None, None).unwrap(), "personal");
+  assert_eq!(registry.select(Some("agency"), None).unwrap(), "agency");
+  assert!(registry.select(Some("agency"), Some(Provider::Porkbun)).is_err());
+  assert!(registry.select(Some("missing"), None).is_err());
+  // Two Name.com accounts and no default among them.
+  assert!(registry.select(None, Some(Provider::NameCom)).is_err());
+  assert!(registry.dns_records("agency").is_ok());
+  assert!(registry.portfolio("missing").is_err());
+
+  let collision = r#"
+porkbun: { apikey: a, secretapikey: b }
+accounts: { porkbun: { provider: porkbun, apikey: c, secretapikey: d } }
+"#;
+  assert!(Config::from_yaml(collision).unwrap().registry().is_err());
+  assert!(Config::from_yaml("default_account: nowhere").unwrap().registry().is_err());
+}
+
+#[test]
+fn substitutes_variables() {
+  let yaml = r#"
+accounts:
+  main:
+    provider: name-com
+    username: "${USER_NAME}"
+    token: "tok-${TOKEN}-$${literal}"
+    host: "${HOST:-https://api.dev.name.com}"
+"#;
+  let vars = |name: &str| match name {
+    "USER_NAME" => Some("alice".to_string()),
+    "TOKEN" => Some("123".to_string()),
+    _ => None,
+  };
+  let config = Config::parse(yaml, ConfigFormat::Yaml, &vars).unwrap();
+  let Some(AccountConfig::NameCom(credentials)) = config.accounts.get("main") else { panic!("not a Name.com account") };
+  assert_eq!(credentials.username, "alice");
+  assert_eq!(credentials.token, "tok-123-${literal}");
+  assert_eq!(credentials.host.as_deref(), Some("https://api.dev.name.com"));
+
+  assert!(Config::parse("porkbun: { apikey: '${MISSING}', secretapikey: b }", ConfigFormat::Yaml, &vars).is_err());
+  assert!(Config::parse("porkbun: { apikey: '${OPEN', secretapikey: b }", ConfigFormat::Yaml, &vars).is_err());
+}
+
+#[cfg(feature = "config-toml")]
+#[test]
+fn parses_toml() {
+  let toml = r#"
+default_account = "personal"
+
+[accounts.personal]
+provider = "porkbun"
+apikey = "${KEY}"
+secretapikey = "sk1"
+"#;
+  let config = Config::parse(toml, ConfigFormat::Toml, &|_| Some("pk1".to_string())).unwrap();
+  let Some(AccountConfig::Porkbun(credentials)) = config.accounts.get("personal") else {
+    panic!("not a Porkbun account")
+  };
+  assert_eq!(credentials.apikey, "pk1");
+  assert_eq!(config.registry().unwrap().select(None, None).unwrap(), "personal");
+}
+
+#[test]
+fn reports_unreadable_files_as_io_errors() {
+  let missing = std::env::temp_dir().join(format!("registrar-missing-{}.yaml", std::process::id()));
+  assert!(matches!(Config::load(&missing), Err(registrar::Error::Io(_))));
+}
@@ -0,0 +1,118 @@
+mod memory_records;
+
+use memory_records::MemoryRecords;
+use registrar::ddns::{AddressChange, DdnsUpdater, IpFamily, IpSource, set_address};
+use registrar::records::{DnsRecords, Record};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// An address source whose answer the test controls.
+#[derive(Default)]
+struct FixedIp(Mutex<Option<IpAddr>>);
+
+impl FixedIp {
+  fn set(&self, ip: &str) {
+    *self.0.lock().unwrap() = Some(ip.parse().unwrap());
+  }
+}
+
+#[async_trait::async_trait]
+impl IpSource for FixedIp {
+  async fn public_ip(&self, family: IpFamily) -> registrar::Result<Option<IpAddr>> {
+    Ok(self.0.lock().unwrap().filter(|ip| family.matches(ip)))
+  }
+}
+
+fn contents(records: &[Record]) -> Vec<&str> {
+  records.iter().map(|r| r.content.as_str()).collect()
+}
+
+#[tokio::test]
+async fn updates_only_on_change_and_skips_with_state_file() {
+  let records = Arc::new(MemoryRecords::default());
+  let ipv4 = Arc::new(FixedIp::default());
+  let ipv6 = Arc::new(FixedIp::default());
+  ipv4.set("198.51.100.7");
+  ipv6.set("2001:db8::7");
+  let state_file = std::env::temp_dir().join(format!("registrar-ddns-{}.json", uuid::Uuid::new_v4()));
+
+  let new_updater = || {
+    DdnsUpdater::new(records.clone(), "example.com")
+      .with_host("home")
+      .with_host("@")
+      .with_ipv4_source(ipv4.clone())
+      .with_ipv6_source(ipv6.clone())
+      .with_state_file(&state_file)
+      .unwrap()
+  };
+  let mut updater = new_updater();
+  let report = updater.update().await.unwrap();
+  assert!(report.hosts.iter().all(|host| host.change == AddressChange::Created));
+  assert_eq!(report.hosts.len(), 4);
+  assert_eq!(contents(&records.find("example.com", "home", "AAAA")), ["2001:db8::7"]);
+  assert_eq!(contents(&records.find("example.com", "", "A")), ["198.51.100.7"]);
+
+  // A fresh process with the same state file makes no API calls while nothing changes.
+  let calls = records.calls();
+  let mut updater = new_updater();
+  assert!(updater.update().await.unwrap().skipped);
+  assert_eq!(records.calls(), calls);
+
+  // A new IPv4 address updates the A records in place and leaves the AAAA records alone.
+  ipv4.set("198.51.100.8");
+  let report = updater.update().await.unwrap();
+  assert!(report.changed());
+  assert_eq!(contents(&records.find("example.com", "home", "A")), ["198.51.100.8"]);
+  assert_eq!(records.find("example.com", "home", "AAAA").len(), 1);
+  let changes: Vec<_> = report.hosts.iter().map(|host| host.change.clone()).collect();
+  assert!(changes.contains(&AddressChange::Updated {
+    previous: "198.51.100.7".to_string()
+  }));
+  assert!(changes.contains(&AddressChange::Unchanged));
+
+  std::fs::remove_file(&state_file).unwrap();
+}
+
+#[tokio::test]
+async fn resyncs_records_edited_elsewhere() {
+  let records = Arc::new(MemoryRecords::default());
+  let ipv4 = Arc::new(FixedIp::default());
+  ipv4.set("198.51.100.7");
+  let mut updater = DdnsUpdater::new(records.clone(), "example.com")
+    .with_host("home")
+    .with_ipv4_source(ipv4)
+    .with_resync(Duration::ZERO);
+  updater.update().await.unwrap();
+
+  let id = records.find("example.com", "home", "A")[0].id.clone().unwrap();
+  records.delete_record("example.com", &id).await.unwrap();
+  let report = updater.update().await.unwrap();
+  assert!(!report.skipped);
+  assert_eq!(report.hosts[0].change, AddressChange::Created);
+}
+
+#[tokio::test]
+async fn set_address_removes_duplicates() {
+  let records = MemoryRecords::default();
+  records.insert("example.com", Record::new("nas", "A", "192.0.2.1"));
+  records.insert("example.com", Record::new("nas", "A", "192.0.2.2"));
+  records.insert("example.com", Record::new("nas", "TXT", "keep me"));
+
+  let change = set_address(&records, "example.com", "nas", "192.0.2.2".parse().unwrap(), None)
+    .await
+    .unwrap();
+  assert_eq!(
+    change,
+    AddressChange::Updated {
+      previous: "192.0.2.1".to_string()
+    }
+  );
+  assert_eq!(contents(&records.find("example.com", "nas", "A")), ["192.0.2.2"]);
+  assert_eq!(records.find("example.com", "nas", "TXT").len(), 1);
+
+  let change = set_address(&records, "example.com", "nas", "192.0.2.2".parse().unwrap(), None)
+    .await
+    .unwrap();
+  assert_eq!(change, AddressChange::Unchanged);
+}
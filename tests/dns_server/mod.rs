@@ -0,0 +1,90 @@
+// tests/dns_server/mod.rs
+
+//! A minimal authoritative DNS server for tests, standing in for a zone's nameservers.
+
+use hickory_proto::op::{Message, MessageType, ResponseCode};
+use hickory_proto::rr::rdata::{NULL, TXT};
+use hickory_proto::rr::{Name, RData, Record, RecordType};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::UdpSocket;
+
+type Zone = Arc<Mutex<HashMap<(String, RecordType), Vec<RData>>>>;
+
+/// A UDP DNS server answering from an in-memory map of records.
+#[derive(Clone)]
+pub struct TestDnsServer {
+  pub addr: SocketAddr,
+  zone: Zone,
+}
+
+#[allow(dead_code)]
+impl TestDnsServer {
+  /// Starts the server on an ephemeral localhost port.
+  pub async fn start() -> Self {
+    let socket = UdpSocket::bind("127.0.0.1:0").await.expect("Failed to bind test DNS server");
+    let addr = socket.local_addr().unwrap();
+    let zone: Zone = Arc::default();
+    let served = zone.clone();
+    tokio::spawn(async move {
+      let mut buf = vec![0u8; 4096];
+      loop {
+        let Ok((len, peer)) = socket.recv_from(&mut buf).await else { break };
+        let Ok(request) = Message::from_vec(&buf[..len]) else { continue };
+        let response = answer(&request, &served);
+        let _ = socket.send_to(&response.to_vec().unwrap(), peer).await;
+      }
+    });
+    Self { addr, zone }
+  }
+
+  /// Replaces the records of one type at `name`.
+  pub fn set(&self, name: &str, record_type: RecordType, rdata: Vec<RData>) {
+    self.zone.lock().unwrap().insert((key(name), record_type), rdata);
+  }
+
+  /// Publishes TXT records at `name`.
+  pub fn set_txt(&self, name: &str, values: &[&str]) {
+    let rdata = values.iter().map(|v| RData::TXT(TXT::new(vec![v.to_string()]))).collect();
+    self.set(name, RecordType::TXT, rdata);
+  }
+
+  /// Publishes records of a type without dedicated RDATA support (e.g., CDS) from raw wire bytes.
+  pub fn set_raw(&self, name: &str, record_type: RecordType, rdata: Vec<Vec<u8>>) {
+    let rdata = rdata
+      .into_iter()
+      .map(|bytes| RData::Unknown {
+        code: record_type,
+        rdata: NULL::with(bytes),
+      })
+      .collect();
+    self.set(name, record_type, rdata);
+  }
+}
+
+fn key(name: &str) -> String {
+  name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+fn answer(request: &Message, zone: &Zone) -> Message {
+  let mut response = Message::new();
+  response
+    .set_id(request.id())
+    .set_message_type(MessageType::Response)
+    .set_op_code(request.op_code())
+    .set_authoritative(true)
+    .add_queries(request.queries().to_vec());
+  let zone = zone.lock().unwrap();
+  for query in request.queries() {
+    let name = query.name().to_ascii();
+    if let Some(rdata) = zone.get(&(key(&name), query.query_type())) {
+      for data in rdata {
+        let owner = Name::from_ascii(&name).unwrap();
+        response.add_answer(Record::from_rdata(owner, 300, data.clone()));
+      }
+    }
+  }
+  response.set_response_code(ResponseCode::NoError);
+  response
+}
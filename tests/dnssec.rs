@@ -0,0 +1,158 @@
+use registrar::dnssec::{DigestType, Dnskey, DnssecAlgorithm, DsRecord};
+
+// Test vectors from RFC 4509 (SHA-256) and RFC 6605 (ECDSA, SHA-256 and SHA-384).
+
+const RFC4509_DNSKEY: &str = "dskey.example.com. 86400 IN DNSKEY 256 3 5 ( AQOeiiR0GOMYkDshWoSKz9Xz
+  fwJr1AYtsmx3TGkJaNXVbfi/
+  2pHm822aJ5iI9BMzNXxeYCmZ
+  DRD99WYwYqUSdjMmmAphXdvx
+  egXd/M5+X7OrzKBaMbCVdFLU
+  Uh6DhweJBjEVv5f2wwjM9Xzc
+  nOf+EPbtG9DMBmADjFDc2w/r
+  ljwvFw==
+  ) ; key id = 60485";
+
+#[test]
+fn computes_key_tag_and_sha256_digest() {
+  let dnskey: Dnskey = RFC4509_DNSKEY.parse().expect("Failed to parse DNSKEY");
+  assert_eq!(dnskey.key_tag(), 60485);
+
+  let ds = dnskey.to_ds(DigestType::Sha256).expect("Failed to compute DS");
+  assert_eq!(ds.algorithm, DnssecAlgorithm::RsaSha1);
+  assert_eq!(
+    ds.digest,
+    "D4B7D520E7BB5F0F67674A0CCEB1E3E0614B93C4F9E99B8383F6A1E4469DA50A"
+  );
+}
+
+#[test]
+fn computes_ecdsa_ds_records() {
+  let p256: Dnskey = "example.net. 3600 IN DNSKEY 257 3 13 (
+    GojIhhXUN/u4v54ZQqGSnyhWJwaubCvTmeexv7bR6edb
+    krSqQpF64cYbcB7wNcP+e+MAnLr+Wi9xMWyQLc8NAA== )"
+    .parse()
+    .expect("Failed to parse P-256 DNSKEY");
+  let ds = p256.to_ds(DigestType::Sha256).unwrap();
+  assert_eq!(
+    ds.to_string(),
+    "55648 13 2 B4C8C1FE2E7477127B27115656AD6256F424625BF5C1E2770CE6D6E37DF61D17"
+  );
+
+  let p384: Dnskey = "example.net. 3600 IN DNSKEY 257 3 ECDSAP384SHA384 (
+    xKYaNhWdGOfJ+nPrL8/arkwf2EY3MDJ+SErKivBVSum1
+    w/egsXvSADtNJhyem5RCOpgQ6K8X1DRSEkrbYQ+OB+v8
+    /uX45NBwY8rp65F6Glur8I/mlVNgF6W/qTI37m40 )"
+    .parse()
+    .expect("Failed to parse P-384 DNSKEY");
+  let ds = p384.to_ds(DigestType::Sha384).unwrap();
+  let expected: DsRecord = "example.net. 3600 IN DS 10771 14 4 (
+    72d7b62976ce06438e9c0bf319013cf801f09ecc84b8
+    d7e9495f27e305c6a9b0563a9b5f4d288405c3008a94
+    6df983d6 )"
+    .parse()
+    .expect("Failed to parse DS");
+  assert_eq!(ds, expected);
+}
+
+#[test]
+fn rejects_unsupported_digest_and_bad_input() {
+  let dnskey: Dnskey = RFC4509_DNSKEY.parse().unwrap();
+  assert!(matches!(
+    dnskey.to_ds(DigestType::Sha1),
+    Err(registrar::Error::Unsupported(_))
+  ));
+  assert!("60485 5 2 XYZ".parse::<DsRecord>().is_err());
+  assert!("0 0 0 00".parse::<DsRecord>().unwrap().is_delete_signal());
+}
+
+// --- Rollover ---
+
+use registrar::dnssec::{DsRegistry, Rollover, RolloverAction, RolloverState, RolloverStep};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// An in-memory registry standing in for a registrar.
+#[derive(Default)]
+struct MemoryRegistry {
+  records: Mutex<Vec<DsRecord>>,
+  /// Removes every record sharing the key tag, as Porkbun does.
+  by_key_tag: bool,
+}
+
+#[async_trait::async_trait]
+impl DsRegistry for MemoryRegistry {
+  async fn list_ds(&self, _domain: &str) -> registrar::Result<Vec<DsRecord>> {
+    Ok(self.records.lock().unwrap().clone())
+  }
+
+  async fn add_ds(&self, _domain: &str, ds: &DsRecord) -> registrar::Result<()> {
+    self.records.lock().unwrap().push(ds.clone());
+    Ok(())
+  }
+
+  async fn remove_ds(&self, _domain: &str, ds: &DsRecord) -> registrar::Result<()> {
+    if self.by_key_tag {
+      self.records.lock().unwrap().retain(|existing| existing.key_tag != ds.key_tag);
+    } else {
+      self.records.lock().unwrap().retain(|existing| existing != ds);
+    }
+    Ok(())
+  }
+
+  fn removes_by_key_tag(&self) -> bool {
+    self.by_key_tag
+  }
+}
+
+#[tokio::test]
+async fn rollover_replaces_old_ds_and_resumes_from_state_file() {
+  let old: DsRecord = "11111 13 2 AAAA".parse().unwrap();
+  let new: DsRecord = "22222 13 2 BBBB".parse().unwrap();
+  let registry = MemoryRegistry::default();
+  registry.records.lock().unwrap().push(old.clone());
+
+  let state_file = std::env::temp_dir().join(format!("rollover-{}.json", uuid::Uuid::new_v4()));
+  let state = RolloverState::new("example.com", new.clone(), Duration::ZERO);
+  let mut rollover = Rollover::new(&registry, state).with_state_file(&state_file);
+
+  // The dry run reports every step and changes nothing.
+  let plan = rollover.plan().await.unwrap();
+  assert_eq!(plan.first(), Some(&RolloverAction::Publish(new.clone())));
+  assert_eq!(plan.last(), Some(&RolloverAction::Remove(old.clone())));
+  assert_eq!(*registry.records.lock().unwrap(), vec![old.clone()]);
+
+  // Publish, then simulate a crash by dropping the rollover.
+  rollover.step().await.unwrap();
+  drop(rollover);
+  assert_eq!(registry.records.lock().unwrap().len(), 2);
+
+  let mut resumed = Rollover::resume(&registry, &state_file).unwrap().expect("state file exists");
+  assert_eq!(resumed.state().old_ds, vec![old]);
+  resumed.run().await.unwrap();
+  assert_eq!(resumed.state().step, RolloverStep::Done);
+  assert_eq!(*registry.records.lock().unwrap(), vec![new]);
+
+  std::fs::remove_file(state_file).unwrap();
+}
+
+#[tokio::test]
+async fn rollover_refuses_to_remove_a_ds_sharing_the_new_key_tag() {
+  // The same key, moving from a SHA-1 to a SHA-256 digest.
+  let old: DsRecord = "11111 13 1 AAAA".parse().unwrap();
+  let new: DsRecord = "11111 13 2 BBBB".parse().unwrap();
+  let registry = MemoryRegistry { by_key_tag: true, ..Default::default() };
+  registry.records.lock().unwrap().push(old.clone());
+
+  let state = RolloverState::new("example.com", new.clone(), Duration::ZERO);
+  let mut rollover = Rollover::new(&registry, state);
+  assert!(matches!(rollover.plan().await, Err(registrar::Error::Unsupported(_))));
+  assert!(matches!(rollover.run().await, Err(registrar::Error::Unsupported(_))));
+  assert_eq!(*registry.records.lock().unwrap(), vec![old.clone()]);
+
+  // A registry that removes single records can roll over the same key.
+  let registry = MemoryRegistry::default();
+  registry.records.lock().unwrap().push(old);
+  let state = RolloverState::new("example.com", new.clone(), Duration::ZERO);
+  Rollover::new(&registry, state).run().await.unwrap();
+  assert_eq!(*registry.records.lock().unwrap(), vec![new]);
+}
@@ -0,0 +1,45 @@
+use registrar::DomainName;
+
+#[test]
+fn normalises_and_splits_names() {
+  let name = DomainName::new("_ACME-Challenge.api.EU.Bücher.co.uk.").unwrap();
+  assert_eq!(name.as_str(), "_acme-challenge.api.eu.xn--bcher-kva.co.uk");
+  assert_eq!(name.public_suffix(), "co.uk");
+  assert_eq!(name.registrable(), Some("xn--bcher-kva.co.uk"));
+  assert_eq!(name.subdomain(), Some("_acme-challenge.api.eu"));
+  assert_eq!(name.to_unicode(), "_acme-challenge.api.eu.bücher.co.uk");
+  assert!(!name.is_registrable());
+
+  let apex = name.registrable_domain().unwrap();
+  assert!(apex.is_registrable());
+  assert_eq!((apex.public_suffix(), apex.subdomain()), ("co.uk", None));
+  assert!(name.is_within(&apex));
+  assert!(!apex.is_within(&name));
+  assert!(!DomainName::new("notexample.com").unwrap().is_within(&DomainName::new("example.com").unwrap()));
+
+  // Wildcard and exception rules, and top-level domains missing from the list.
+  assert_eq!(DomainName::new("a.b.example.ck").unwrap().registrable(), Some("b.example.ck"));
+  assert_eq!(DomainName::new("www.ck").unwrap().registrable(), Some("www.ck"));
+  assert_eq!(DomainName::new("host.example.internal").unwrap().registrable(), Some("example.internal"));
+
+  let suffix = DomainName::new("co.uk").unwrap();
+  assert_eq!((suffix.registrable(), suffix.subdomain()), (None, None));
+
+  assert_eq!("Example.COM".parse::<DomainName>().unwrap(), DomainName::new("example.com.").unwrap());
+  let json = serde_json::to_string(&name).unwrap();
+  assert_eq!(serde_json::from_str::<DomainName>(&json).unwrap(), name);
+}
+
+#[test]
+fn rejects_malformed_names() {
+  let long_label = format!("{}.com", "a".repeat(64));
+  let long_name = format!("{}com", "abcdefghi.".repeat(26));
+  for name in ["", ".", "a..com", "-a.com", "a-.com", "a b.com", "a/b.com", long_label.as_str(), long_name.as_str()] {
+    assert!(
+      matches!(DomainName::new(name), Err(registrar::Error::InvalidInput(_))),
+      "'{}' was accepted",
+      name
+    );
+  }
+  assert!(serde_json::from_str::<DomainName>("\"a..com\"").is_err());
+}
@@ -0,0 +1,97 @@
+mod memory_records;
+
+use memory_records::MemoryRecords;
+use registrar::dyndns2::{Dyndns2Config, Dyndns2Server};
+use registrar::records::Record;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+// "secret" hashed with SHA-256.
+const CONFIG: &str = r#"
+zones: [example.com]
+users:
+  - username: router
+    password_sha256: "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b"
+    hosts: [home.example.com, vpn.example.com]
+  - username: nas
+    password: "hunter2"
+    hosts: [nas.example.com]
+"#;
+
+async fn start(records: Arc<MemoryRecords>) -> SocketAddr {
+  let server = Dyndns2Server::new(records, Dyndns2Config::from_yaml(CONFIG).unwrap()).unwrap();
+  let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let addr = listener.local_addr().unwrap();
+  tokio::spawn(server.serve(listener));
+  addr
+}
+
+async fn get(addr: SocketAddr, query: &str, credentials: Option<(&str, &str)>) -> (u16, String) {
+  let mut request = reqwest::Client::new().get(format!("http://{}/nic/update?{}", addr, query));
+  if let Some((username, password)) = credentials {
+    request = request.basic_auth(username, Some(password));
+  }
+  let response = request.send().await.unwrap();
+  (response.status().as_u16(), response.text().await.unwrap())
+}
+
+#[tokio::test]
+async fn updates_authorized_hosts() {
+  let records = Arc::new(MemoryRecords::default());
+  records.insert("example.com", Record::new("home", "A", "192.0.2.1"));
+  let addr = start(records.clone()).await;
+  let router = Some(("router", "secret"));
+
+  let (status, body) = get(addr, "hostname=home.example.com&myip=198.51.100.7", router).await;
+  assert_eq!((status, body.as_str()), (200, "good 198.51.100.7"));
+  assert_eq!(records.find("example.com", "home", "A")[0].content, "198.51.100.7");
+
+  let (_, body) = get(addr, "hostname=home.example.com&myip=198.51.100.7", router).await;
+  assert_eq!(body, "nochg 198.51.100.7");
+
+  // Several hostnames and both address families, one response line per hostname.
+  let (_, body) = get(
+    addr,
+    "hostname=home.example.com,vpn.example.com,nas.example.com&myip=198.51.100.7,2001:db8::7",
+    router,
+  )
+  .await;
+  assert_eq!(
+    body,
+    "good 198.51.100.7,2001:db8::7\ngood 198.51.100.7,2001:db8::7\nnohost"
+  );
+  assert!(records.find("example.com", "nas", "A").is_empty());
+
+  // Without myip, the client's own address is used.
+  let (_, body) = get(addr, "hostname=nas.example.com", Some(("nas", "hunter2"))).await;
+  assert_eq!(body, "good 127.0.0.1");
+}
+
+#[tokio::test]
+async fn rejects_bad_requests() {
+  let records = Arc::new(MemoryRecords::default());
+  let addr = start(records.clone()).await;
+
+  let (status, body) = get(addr, "hostname=home.example.com", None).await;
+  assert_eq!((status, body.as_str()), (401, "badauth"));
+  let (status, body) = get(addr, "hostname=home.example.com", Some(("router", "wrong"))).await;
+  assert_eq!((status, body.as_str()), (401, "badauth"));
+
+  let router = Some(("router", "secret"));
+  assert_eq!(get(addr, "myip=198.51.100.7", router).await.1, "notfqdn");
+  assert_eq!(get(addr, "hostname=other.example.net", router).await.1, "nohost");
+  assert_eq!(records.calls(), 0);
+}
+
+#[test]
+fn rejects_hosts_outside_zones() {
+  let config = Dyndns2Config::from_yaml(
+    "zones: [example.com]\nusers:\n  - { username: a, password: b, hosts: [home.example.net] }\n",
+  )
+  .unwrap();
+  let records = Arc::new(MemoryRecords::default());
+  assert!(matches!(
+    Dyndns2Server::new(records, config),
+    Err(registrar::Error::InvalidInput(_))
+  ));
+}
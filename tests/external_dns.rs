@@ -0,0 +1,145 @@
+mod memory_records;
+
+use memory_records::MemoryRecords;
+use registrar::external_dns::{ExternalDnsProvider, MEDIA_TYPE};
+use registrar::records::Record;
+use serde_json::{Value, json};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+const OWNER: &str = "\"heritage=external-dns,external-dns/owner=default,external-dns/resource=ingress/web/www\"";
+
+async fn start(records: Arc<MemoryRecords>) -> SocketAddr {
+  let provider = ExternalDnsProvider::new(records)
+    .with_zone("example.com")
+    .with_exclude("internal.example.com")
+    .with_min_ttl(600);
+  let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let addr = listener.local_addr().unwrap();
+  tokio::spawn(provider.serve(listener));
+  addr
+}
+
+async fn get(addr: SocketAddr, path: &str) -> reqwest::Response {
+  reqwest::Client::new()
+    .get(format!("http://{}{}", addr, path))
+    .header("Accept", MEDIA_TYPE)
+    .send()
+    .await
+    .unwrap()
+}
+
+async fn post(addr: SocketAddr, path: &str, body: Value) -> reqwest::Response {
+  reqwest::Client::new()
+    .post(format!("http://{}{}", addr, path))
+    .header("Content-Type", MEDIA_TYPE)
+    .body(body.to_string())
+    .send()
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn negotiates_and_lists_records() {
+  let records = Arc::new(MemoryRecords::default());
+  records.insert("example.com", Record::new("", "NS", "ns1.registrar.net"));
+  records.insert("example.com", Record::new("www", "A", "192.0.2.1").with_ttl(600));
+  records.insert("example.com", Record::new("www", "A", "192.0.2.2").with_ttl(900));
+  records.insert("example.com", Record::new("", "MX", "mail.example.com").with_priority(10));
+  records.insert("example.com", Record::new("db.internal", "A", "10.0.0.1"));
+  let addr = start(records).await;
+
+  let response = get(addr, "/").await;
+  assert_eq!(response.headers()["content-type"], MEDIA_TYPE);
+  assert_eq!(
+    response.json::<Value>().await.unwrap(),
+    json!({ "include": ["example.com"], "exclude": ["internal.example.com"] })
+  );
+
+  // The apex NS records and excluded names are left out.
+  let endpoints: Value = get(addr, "/records").await.json().await.unwrap();
+  assert_eq!(
+    endpoints,
+    json!([
+      { "dnsName": "example.com", "targets": ["10 mail.example.com"], "recordType": "MX" },
+      { "dnsName": "www.example.com", "targets": ["192.0.2.1", "192.0.2.2"], "recordType": "A", "recordTTL": 600 },
+    ])
+  );
+}
+
+#[tokio::test]
+async fn applies_changes() {
+  let records = Arc::new(MemoryRecords::default());
+  records.insert("example.com", Record::new("old", "CNAME", "gone.example.com"));
+  records.insert("example.com", Record::new("www", "A", "192.0.2.1"));
+  records.insert("example.com", Record::new("www", "A", "192.0.2.2"));
+  let addr = start(records.clone()).await;
+
+  let changes = json!({
+    "Create": [
+      { "dnsName": "api.example.com", "targets": ["lb.example.net."], "recordType": "CNAME", "recordTTL": 60 },
+      { "dnsName": "www.example.com", "targets": [OWNER], "recordType": "TXT" },
+    ],
+    "UpdateOld": [{ "dnsName": "www.example.com", "targets": ["192.0.2.1", "192.0.2.2"], "recordType": "A" }],
+    "UpdateNew": [{ "dnsName": "www.example.com", "targets": ["192.0.2.2", "198.51.100.7"], "recordType": "A" }],
+    "Delete": [{ "dnsName": "old.example.com", "targets": ["gone.example.com"], "recordType": "CNAME" }],
+  });
+  let response = post(addr, "/records", changes).await;
+  assert_eq!(response.status(), 204);
+
+  let api = records.find("example.com", "api", "CNAME");
+  assert_eq!((api[0].content.as_str(), api[0].ttl), ("lb.example.net", Some(600)));
+  let mut www: Vec<String> = records.find("example.com", "www", "A").into_iter().map(|r| r.content).collect();
+  www.sort();
+  assert_eq!(www, ["192.0.2.2", "198.51.100.7"]);
+  assert!(records.find("example.com", "old", "CNAME").is_empty());
+
+  // The ownership record is stored unquoted and listed exactly as external-dns wrote it.
+  assert_eq!(records.find("example.com", "www", "TXT")[0].content, OWNER.trim_matches('"'));
+  let endpoints: Vec<Value> = get(addr, "/records").await.json().await.unwrap();
+  let txt = endpoints.iter().find(|endpoint| endpoint["recordType"] == "TXT").unwrap();
+  assert_eq!(txt["targets"], json!([OWNER]));
+
+  // Names outside the managed zones are refused.
+  let changes = json!({ "Create": [{ "dnsName": "a.example.org", "targets": ["192.0.2.9"], "recordType": "A" }] });
+  assert_eq!(post(addr, "/records", changes).await.status(), 500);
+}
+
+#[tokio::test]
+async fn leaves_apex_ns_records_alone() {
+  let records = Arc::new(MemoryRecords::default());
+  records.insert("example.com", Record::new("", "NS", "ns1.registrar.net"));
+  records.insert("example.com", Record::new("", "NS", "ns2.registrar.net"));
+  let addr = start(records.clone()).await;
+
+  let changes = json!({
+    "Create": [{ "dnsName": "dev.example.com", "targets": ["ns1.dev.example.net"], "recordType": "NS" }],
+    "UpdateNew": [{ "dnsName": "example.com", "targets": ["ns1.other.net"], "recordType": "NS" }],
+    "Delete": [{ "dnsName": "example.com", "targets": ["ns2.registrar.net"], "recordType": "NS" }],
+  });
+  assert_eq!(post(addr, "/records", changes).await.status(), 204);
+
+  let mut apex: Vec<String> = records.find("example.com", "", "NS").into_iter().map(|r| r.content).collect();
+  apex.sort();
+  assert_eq!(apex, ["ns1.registrar.net", "ns2.registrar.net"]);
+  // Delegations below the apex are still managed.
+  assert_eq!(records.find("example.com", "dev", "NS")[0].content, "ns1.dev.example.net");
+}
+
+#[tokio::test]
+async fn adjusts_endpoints() {
+  let addr = start(Arc::new(MemoryRecords::default())).await;
+  let endpoints = json!([
+    { "dnsName": "WWW.example.com.", "targets": ["LB.example.net."], "recordType": "cname", "recordTTL": 60 },
+    { "dnsName": "host.example.com", "targets": ["192.0.2.1"], "recordType": "A", "recordTTL": 0, "labels": null },
+    { "dnsName": "x.example.com", "targets": ["0 1 2 3"], "recordType": "NAPTR" },
+  ]);
+  let adjusted: Value = post(addr, "/adjustendpoints", endpoints).await.json().await.unwrap();
+  assert_eq!(
+    adjusted,
+    json!([
+      { "dnsName": "www.example.com", "targets": ["lb.example.net"], "recordType": "CNAME", "recordTTL": 600 },
+      { "dnsName": "host.example.com", "targets": ["192.0.2.1"], "recordType": "A" },
+    ])
+  );
+}
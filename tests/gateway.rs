@@ -0,0 +1,225 @@
+mod memory_records;
+
+use memory_records::MemoryRecords;
+use registrar::gateway::{ANONYMOUS_TOKEN, AuditEntry, AuditOutcome, Gateway, GatewayConfig};
+use registrar::records::Record;
+use serde_json::{Value, json};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+const CONFIG: &str = r#"
+tokens:
+  - name: web-team
+    # sha256("web-secret")
+    token_sha256: "761fed9dbb22427bedbc73c3f0ab93fff41104aa77eb145025d0113be8c035a3"
+    zones: [example.com]
+    names: ["www", "*.web"]
+    types: [A, CNAME]
+  - name: monitoring
+    token: "monitor-secret"
+    zones: [example.com, example.org]
+    read_only: true
+"#;
+
+async fn start(records: Arc<MemoryRecords>) -> (SocketAddr, Arc<Mutex<Vec<AuditEntry>>>) {
+  let config = GatewayConfig::from_yaml(CONFIG).unwrap();
+  let audit = Arc::new(Mutex::new(Vec::new()));
+  let sink = audit.clone();
+  let gateway = Gateway::new(records, config)
+    .unwrap()
+    .with_audit_sink(Arc::new(move |entry: &AuditEntry| sink.lock().unwrap().push(entry.clone())));
+  let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let addr = listener.local_addr().unwrap();
+  tokio::spawn(gateway.serve(listener));
+  (addr, audit)
+}
+
+fn request(method: reqwest::Method, addr: SocketAddr, path: &str, token: &str) -> reqwest::RequestBuilder {
+  reqwest::Client::new().request(method, format!("http://{}{}", addr, path)).bearer_auth(token)
+}
+
+fn seeded() -> Arc<MemoryRecords> {
+  let records = Arc::new(MemoryRecords::default());
+  records.insert("example.com", Record::new("www", "A", "192.0.2.1"));
+  records.insert("example.com", Record::new("api.web", "CNAME", "lb.example.net"));
+  records.insert("example.com", Record::new("", "MX", "mail.example.com").with_priority(10));
+  records.insert("example.com", Record::new("www", "TXT", "verification"));
+  records
+}
+
+#[tokio::test]
+async fn lists_only_records_within_scope() {
+  let (addr, _) = start(seeded()).await;
+
+  let zones: Value = request(reqwest::Method::GET, addr, "/v1/zones", "monitor-secret")
+    .send()
+    .await
+    .unwrap()
+    .json()
+    .await
+    .unwrap();
+  assert_eq!(zones, json!(["example.com", "example.org"]));
+
+  let records: Vec<Record> = request(reqwest::Method::GET, addr, "/v1/zones/example.com/records", "web-secret")
+    .send()
+    .await
+    .unwrap()
+    .json()
+    .await
+    .unwrap();
+  let mut names: Vec<(String, String)> = records.into_iter().map(|r| (r.host, r.record_type)).collect();
+  names.sort();
+  assert_eq!(names, [("api.web".to_string(), "CNAME".to_string()), ("www".to_string(), "A".to_string())]);
+
+  let response = request(reqwest::Method::GET, addr, "/v1/zones/example.org/records", "web-secret")
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 403);
+}
+
+#[tokio::test]
+async fn enforces_scope_on_changes_and_audits_them() {
+  let records = seeded();
+  let (addr, audit) = start(records.clone()).await;
+
+  let response = request(reqwest::Method::POST, addr, "/v1/zones/example.com/records", "web-secret")
+    .json(&Record::new("cdn.web", "CNAME", "cdn.example.net"))
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 201);
+  let id = response.json::<Value>().await.unwrap()["id"].as_str().unwrap().to_string();
+  assert_eq!(records.find("example.com", "cdn.web", "CNAME")[0].id.as_deref(), Some(id.as_str()));
+
+  // Outside the token's names and types.
+  let response = request(reqwest::Method::POST, addr, "/v1/zones/example.com/records", "web-secret")
+    .json(&Record::new("mail", "A", "192.0.2.25"))
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 403);
+  let response = request(reqwest::Method::POST, addr, "/v1/zones/example.com/records", "web-secret")
+    .json(&Record::new("www", "TXT", "other"))
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 403);
+
+  // The MX record exists but is outside the token's scope, so it cannot be deleted.
+  let mx = records.find("example.com", "", "MX")[0].id.clone().unwrap();
+  let response = request(reqwest::Method::DELETE, addr, &format!("/v1/zones/example.com/records/{}", mx), "web-secret")
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 404);
+  assert_eq!(records.find("example.com", "", "MX").len(), 1);
+
+  // A record within scope cannot be moved out of it.
+  let www = records.find("example.com", "www", "A")[0].id.clone().unwrap();
+  let path = format!("/v1/zones/example.com/records/{}", www);
+  let response = request(reqwest::Method::PUT, addr, &path, "web-secret")
+    .json(&Record::new("", "A", "192.0.2.2"))
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 403);
+  let response = request(reqwest::Method::PUT, addr, &path, "web-secret")
+    .json(&Record::new("www", "A", "192.0.2.2").with_ttl(600))
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 204);
+  assert_eq!(records.find("example.com", "www", "A")[0].content, "192.0.2.2");
+
+  let outcomes: Vec<(String, AuditOutcome)> =
+    audit.lock().unwrap().iter().map(|entry| (entry.action.clone(), entry.outcome.clone())).collect();
+  assert_eq!(outcomes.len(), 6);
+  assert_eq!(outcomes[0], ("create_record".to_string(), AuditOutcome::Applied));
+  assert!(matches!(&outcomes[1], (_, AuditOutcome::Denied(_))));
+  assert!(matches!(&outcomes[3], (action, AuditOutcome::Denied(_)) if action == "delete_record"));
+  assert_eq!(outcomes[5], ("update_record".to_string(), AuditOutcome::Applied));
+  assert!(audit.lock().unwrap().iter().all(|entry| entry.token == "web-team"));
+}
+
+#[tokio::test]
+async fn rejects_unknown_and_read_only_tokens() {
+  let records = seeded();
+  let (addr, audit) = start(records.clone()).await;
+  let calls = records.calls();
+
+  let response = reqwest::get(format!("http://{}/v1/zones", addr)).await.unwrap();
+  assert_eq!(response.status(), 401);
+  let response = request(reqwest::Method::GET, addr, "/v1/zones", "guess").send().await.unwrap();
+  assert_eq!(response.status(), 401);
+  assert!(response.json::<Value>().await.unwrap()["error"].is_string());
+
+  let response = request(reqwest::Method::POST, addr, "/v1/zones/example.com/records", "monitor-secret")
+    .json(&Record::new("www", "A", "192.0.2.3"))
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 403);
+
+  // Domain settings need their own permission.
+  let response = request(reqwest::Method::PUT, addr, "/v1/zones/example.com/lock", "web-secret")
+    .json(&json!({ "locked": false }))
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 403);
+
+  assert_eq!(records.calls(), calls);
+  let entries = audit.lock().unwrap();
+  assert_eq!(entries.len(), 2);
+  assert_eq!(entries[0].token, "monitoring");
+  assert_eq!(entries[1].action, "set_lock");
+}
+
+#[tokio::test]
+async fn audits_unauthenticated_and_malformed_changes() {
+  let records = seeded();
+  let (addr, audit) = start(records.clone()).await;
+  let calls = records.calls();
+
+  let response = request(reqwest::Method::DELETE, addr, "/v1/zones/example.com/records/1", "guess")
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 401);
+  let response = request(reqwest::Method::POST, addr, "/v1/zones/example.com/records", "web-secret")
+    .body("{not json")
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 400);
+  let response = request(reqwest::Method::PUT, addr, "/v1/zones/example.com/auto-renew", "web-secret")
+    .json(&json!({ "enabled": "yes" }))
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(response.status(), 400);
+
+  assert_eq!(records.calls(), calls);
+  let entries = audit.lock().unwrap();
+  let logged: Vec<(&str, &str, Option<&str>)> = entries
+    .iter()
+    .map(|entry| (entry.token.as_str(), entry.action.as_str(), entry.record_id.as_deref()))
+    .collect();
+  assert_eq!(
+    logged,
+    [
+      (ANONYMOUS_TOKEN, "delete_record", Some("1")),
+      ("web-team", "create_record", None),
+      ("web-team", "set_auto_renew", None),
+    ]
+  );
+  assert!(entries.iter().all(|entry| matches!(entry.outcome, AuditOutcome::Denied(_))));
+}
+
+#[test]
+fn reports_unreadable_configuration_files_as_io_errors() {
+  let missing = std::env::temp_dir().join(format!("registrar-gateway-missing-{}.yaml", std::process::id()));
+  assert!(matches!(GatewayConfig::load(&missing), Err(registrar::Error::Io(_))));
+  assert!(matches!(GatewayConfig::from_yaml("tokens: 3"), Err(registrar::Error::InvalidInput(_))));
+}
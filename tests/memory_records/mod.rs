@@ -0,0 +1,86 @@
+// tests/memory_records/mod.rs
+
+//! An in-memory `DnsRecords` implementation standing in for a registrar.
+
+use registrar::records::{DnsRecords, Record};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+#[derive(Default)]
+pub struct MemoryRecords {
+  records: Mutex<Vec<(String, Record)>>,
+  next_id: AtomicUsize,
+  /// The number of API calls made, of any kind.
+  pub calls: AtomicUsize,
+  /// Whether deletions fail, as during a registrar outage.
+  pub fail_deletes: AtomicBool,
+}
+
+#[allow(dead_code)]
+impl MemoryRecords {
+  /// Adds a record directly, as if it had been created outside the code under test.
+  pub fn insert(&self, domain: &str, mut record: Record) -> String {
+    let id = (self.next_id.fetch_add(1, Ordering::SeqCst) + 1).to_string();
+    record.id = Some(id.clone());
+    self.records.lock().unwrap().push((domain.to_string(), record));
+    id
+  }
+
+  /// The records of `domain` with the given host and type.
+  pub fn find(&self, domain: &str, host: &str, record_type: &str) -> Vec<Record> {
+    let records = self.records.lock().unwrap();
+    records
+      .iter()
+      .filter(|(d, r)| d == domain && r.host == host && r.record_type == record_type)
+      .map(|(_, r)| r.clone())
+      .collect()
+  }
+
+  /// Every record, with the domain holding it.
+  pub fn all(&self) -> Vec<(String, Record)> {
+    self.records.lock().unwrap().clone()
+  }
+
+  pub fn calls(&self) -> usize {
+    self.calls.load(Ordering::SeqCst)
+  }
+}
+
+#[async_trait::async_trait]
+impl DnsRecords for MemoryRecords {
+  async fn list_records(&self, domain: &str) -> registrar::Result<Vec<Record>> {
+    self.calls.fetch_add(1, Ordering::SeqCst);
+    let records = self.records.lock().unwrap();
+    Ok(records.iter().filter(|(d, _)| d == domain).map(|(_, r)| r.clone()).collect())
+  }
+
+  async fn create_record(&self, domain: &str, record: &Record) -> registrar::Result<String> {
+    self.calls.fetch_add(1, Ordering::SeqCst);
+    Ok(self.insert(domain, record.clone()))
+  }
+
+  async fn update_record(&self, domain: &str, record: &Record) -> registrar::Result<()> {
+    self.calls.fetch_add(1, Ordering::SeqCst);
+    let mut records = self.records.lock().unwrap();
+    let existing = records
+      .iter_mut()
+      .find(|(d, r)| d == domain && r.id == record.id)
+      .ok_or_else(|| registrar::Error::Api("Record not found".to_string()))?;
+    existing.1 = record.clone();
+    Ok(())
+  }
+
+  async fn delete_record(&self, domain: &str, id: &str) -> registrar::Result<()> {
+    self.calls.fetch_add(1, Ordering::SeqCst);
+    if self.fail_deletes.load(Ordering::SeqCst) {
+      return Err(registrar::Error::Api("Service unavailable".to_string()));
+    }
+    let mut records = self.records.lock().unwrap();
+    let before = records.len();
+    records.retain(|(d, r)| !(d == domain && r.id.as_deref() == Some(id)));
+    if records.len() == before {
+      return Err(registrar::Error::Api("Record not found".to_string()));
+    }
+    Ok(())
+  }
+}
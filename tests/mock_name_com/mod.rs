@@ -0,0 +1,276 @@
+// tests/mock_name_com/mod.rs
+
+//! A minimal stand-in for the Name.com API, serving paginated listings of
+//! generated domains and DNS records over plain HTTP, keeping URL
+//! forwardings and vanity nameservers in memory, and answering the lock and
+//! autorenew actions.
+
+use registrar::name_com::NameDotCom;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The page size used when a request does not give `perPage`.
+pub const DEFAULT_PER_PAGE: usize = 2;
+
+#[derive(Default)]
+struct State {
+  total: usize,
+  delay: Duration,
+  requests: Mutex<Vec<String>>,
+  /// URL forwardings and vanity nameservers by collection, domain and full hostname.
+  items: Mutex<BTreeMap<(Collection, String, String), Value>>,
+  /// Whether domain actions answer with `{ "domain": ... }` rather than the bare domain.
+  wrap_actions: AtomicBool,
+  in_flight: AtomicUsize,
+  max_in_flight: AtomicUsize,
+}
+
+pub struct MockNameCom {
+  host: String,
+  state: Arc<State>,
+}
+
+#[allow(dead_code)]
+impl MockNameCom {
+  /// Serves `total` domains, and `total` records for every domain, answering each request after `delay`.
+  pub fn start(total: usize, delay: Duration) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let host = format!("http://{}", listener.local_addr().unwrap());
+    let state = Arc::new(State { total, delay, ..Default::default() });
+    let shared = state.clone();
+    thread::spawn(move || {
+      for stream in listener.incoming().flatten() {
+        let state = shared.clone();
+        thread::spawn(move || serve(&state, stream));
+      }
+    });
+    Self { host, state }
+  }
+
+  pub fn client(&self) -> NameDotCom {
+    NameDotCom::with_host(self.host.clone(), "user".to_string(), "token".to_string())
+  }
+
+  /// The URL forwardings stored for `domain`, by full hostname.
+  pub fn forwards(&self, domain: &str) -> Vec<String> {
+    self.hostnames(Collection::UrlForwarding, domain)
+  }
+
+  /// The vanity nameservers stored for `domain`, by hostname.
+  pub fn vanity_nameservers(&self, domain: &str) -> Vec<String> {
+    self.hostnames(Collection::VanityNameservers, domain)
+  }
+
+  fn hostnames(&self, collection: Collection, domain: &str) -> Vec<String> {
+    let items = self.state.items.lock().unwrap();
+    items.keys().filter(|(c, d, _)| *c == collection && d == domain).map(|(_, _, host)| host.clone()).collect()
+  }
+
+  /// Answers domain actions with the domain wrapped in a `domain` field, or bare.
+  pub fn wrap_actions(&self, wrap: bool) {
+    self.state.wrap_actions.store(wrap, Ordering::SeqCst);
+  }
+
+  /// The request paths received, including query strings, in arrival order.
+  pub fn requests(&self) -> Vec<String> {
+    self.state.requests.lock().unwrap().clone()
+  }
+
+  /// The most requests that were being answered at the same time.
+  pub fn max_in_flight(&self) -> usize {
+    self.state.max_in_flight.load(Ordering::SeqCst)
+  }
+}
+
+fn serve(state: &State, mut stream: TcpStream) {
+  let mut reader = BufReader::new(stream.try_clone().unwrap());
+  let mut request_line = String::new();
+  reader.read_line(&mut request_line).unwrap();
+  let mut length = 0;
+  loop {
+    let mut header = String::new();
+    if reader.read_line(&mut header).unwrap() == 0 || header == "\r\n" {
+      break;
+    }
+    if let Some((name, value)) = header.split_once(':')
+      && name.eq_ignore_ascii_case("content-length")
+    {
+      length = value.trim().parse().unwrap();
+    }
+  }
+  let mut body = vec![0; length];
+  reader.read_exact(&mut body).unwrap();
+  let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+  let mut parts = request_line.split_whitespace();
+  let method = parts.next().unwrap_or_default().to_string();
+  let target = parts.next().unwrap_or_default().to_string();
+  state.requests.lock().unwrap().push(target.clone());
+
+  let in_flight = state.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+  state.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+  thread::sleep(state.delay);
+  let (status, body) = if let Some((collection, domain, host)) = stored(&target) {
+    item(state, collection, &method, domain, host, body)
+  } else if let Some(body) = action(state, &target) {
+    ("200 OK", Some(body))
+  } else {
+    ("200 OK", Some(respond(state.total, &target)))
+  };
+  state.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+  let body = body.map(|body| body.to_string()).unwrap_or_default();
+  let response = format!(
+    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    status,
+    body.len(),
+    body
+  );
+  stream.write_all(response.as_bytes()).unwrap();
+}
+
+/// The per-domain collections kept in memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Collection {
+  UrlForwarding,
+  VanityNameservers,
+}
+
+impl Collection {
+  /// The path after the domain, the field holding a listing, and the field naming an item.
+  fn fields(self) -> (&'static str, &'static str, &'static str) {
+    match self {
+      Collection::UrlForwarding => ("/url/forwarding", "urlForwarding", "host"),
+      Collection::VanityNameservers => ("/vanity_nameservers", "vanityNameservers", "hostname"),
+    }
+  }
+}
+
+/// Splits a stored collection's path into the collection, the domain and the hostname, if it names one.
+fn stored(target: &str) -> Option<(Collection, &str, Option<&str>)> {
+  let path = target.split_once('?').map_or(target, |(path, _)| path);
+  let path = path.strip_prefix("/core/v1/domains/")?;
+  [Collection::UrlForwarding, Collection::VanityNameservers].into_iter().find_map(|collection| {
+    let (domain, rest) = path.split_once(collection.fields().0)?;
+    Some((collection, domain, rest.strip_prefix('/')))
+  })
+}
+
+fn item(
+  state: &State,
+  collection: Collection,
+  method: &str,
+  domain: &str,
+  host: Option<&str>,
+  mut body: Value,
+) -> (&'static str, Option<Value>) {
+  let (_, list_field, key_field) = collection.fields();
+  let mut items = state.items.lock().unwrap();
+  let key = |host: &str| (collection, domain.to_string(), host.to_string());
+  let not_found = ("404 Not Found", Some(json!({ "message": "Not Found" })));
+  match (method, host) {
+    ("GET", None) => {
+      let list: Vec<_> = items
+        .iter()
+        .filter(|((c, d, _), _)| *c == collection && d == domain)
+        .map(|(_, item)| item.clone())
+        .collect();
+      ("200 OK", Some(json!({ list_field: list, "lastPage": 1 })))
+    }
+    ("POST", None) => {
+      let host = body[key_field].as_str().unwrap_or_default().to_string();
+      body["domainName"] = json!(domain);
+      items.insert(key(&host), body.clone());
+      ("200 OK", Some(body))
+    }
+    ("PUT", Some(host)) => match items.get_mut(&key(host)) {
+      Some(item) => {
+        for (field, value) in body.as_object().into_iter().flatten() {
+          item[field] = value.clone();
+        }
+        ("200 OK", Some(item.clone()))
+      }
+      None => not_found,
+    },
+    ("DELETE", Some(host)) => match items.remove(&key(host)) {
+      Some(_) => ("204 No Content", None),
+      None => not_found,
+    },
+    _ => not_found,
+  }
+}
+
+/// Answers a lock or autorenew action on a generated domain with the updated domain.
+fn action(state: &State, target: &str) -> Option<Value> {
+  let (name, action) = target.strip_prefix("/core/v1/domains/")?.split_once(':')?;
+  let (field, value) = match action {
+    "lock" => ("locked", true),
+    "unlock" => ("locked", false),
+    "enableAutorenew" => ("autorenewEnabled", true),
+    "disableAutorenew" => ("autorenewEnabled", false),
+    _ => return None,
+  };
+  let mut updated = domain(1);
+  updated["domainName"] = json!(name);
+  updated[field] = json!(value);
+  if state.wrap_actions.load(Ordering::SeqCst) {
+    Some(json!({ "domain": updated }))
+  } else {
+    Some(updated)
+  }
+}
+
+fn respond(total: usize, target: &str) -> Value {
+  let (path, query) = target.split_once('?').unwrap_or((target, ""));
+  let param = |name: &str| {
+    query
+      .split('&')
+      .filter_map(|pair| pair.split_once('='))
+      .find(|(key, _)| *key == name)
+      .and_then(|(_, value)| value.parse::<usize>().ok())
+  };
+  let page = param("page").unwrap_or(1);
+  let per_page = param("perPage").unwrap_or(DEFAULT_PER_PAGE);
+  let last_page = total.div_ceil(per_page).max(1);
+  let items = (per_page * (page - 1)..(per_page * page).min(total)).map(|index| index + 1);
+
+  let mut body = match path.strip_suffix("/records").and_then(|path| path.strip_prefix("/core/v1/domains/")) {
+    Some(domain) => json!({ "records": items.map(|id| record(domain, id)).collect::<Vec<_>>() }),
+    None => json!({ "domains": items.map(domain).collect::<Vec<_>>() }),
+  };
+  if page < last_page {
+    body["nextPage"] = json!(page + 1);
+  }
+  body["lastPage"] = json!(last_page);
+  body
+}
+
+fn domain(index: usize) -> Value {
+  json!({
+    "domainName": format!("domain{:04}.com", index),
+    "createDate": "2020-01-01T00:00:00Z",
+    "expireDate": "2030-01-01T00:00:00Z",
+    "autorenewEnabled": true,
+    "locked": true,
+    "privacyEnabled": false,
+    "contacts": { "registrant": {} },
+    "nameservers": ["ns1.name.com"],
+  })
+}
+
+fn record(domain: &str, id: usize) -> Value {
+  json!({
+    "id": id,
+    "domainName": domain,
+    "host": format!("host{}", id),
+    "fqdn": format!("host{}.{}.", id, domain),
+    "type": "A",
+    "answer": "192.0.2.1",
+    "ttl": 300,
+  })
+}
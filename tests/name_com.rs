@@ -1,5 +1,6 @@
 mod common;
 
+use registrar::DomainName;
 use registrar::name_com::{
   dns::types::DnsRecordPayload, url_forwarding::types::UrlForwardingCreatePayload,
   vanity_ns::types::VanityNsCreatePayload,
@@ -45,7 +46,9 @@ async fn can_create_and_get_dns_record() {
   let domain_name = &config.domain;
   ensure_domain_exists(&client, domain_name).await;
 
-  let dns_client = client.dns(domain_name);
+  let domain = DomainName::new(domain_name).unwrap();
+
+  let dns_client = client.dns(&domain);
   let unique_value = Uuid::new_v4().to_string();
   let record_host = "integration-test-dns";
 
@@ -86,7 +89,9 @@ async fn can_create_and_get_url_forward() {
   let domain_name = &config.domain;
   ensure_domain_exists(&client, domain_name).await;
 
-  let fwd_client = client.url_forwarding(domain_name);
+  let domain = DomainName::new(domain_name).unwrap();
+
+  let fwd_client = client.url_forwarding(&domain);
   let subdomain = format!("test-fwd-{}", Uuid::new_v4().to_string()[..8].to_string());
   let payload = UrlForwardingCreatePayload {
     domain_name,
@@ -124,7 +129,9 @@ async fn can_create_and_get_vanity_nameserver() {
   let domain_name = &config.domain;
   ensure_domain_exists(&client, domain_name).await;
 
-  let vns_client = client.vanity_ns(domain_name);
+  let domain = DomainName::new(domain_name).unwrap();
+
+  let vns_client = client.vanity_ns(&domain);
   let subdomain = format!("ns1-test-{}", Uuid::new_v4().to_string()[..8].to_string());
   let full_hostname = format!("{}.{}", subdomain, domain_name);
   let ips = vec!["1.2.3.4", "5.6.7.8"];
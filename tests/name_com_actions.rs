@@ -0,0 +1,35 @@
+mod mock_name_com;
+
+use mock_name_com::MockNameCom;
+use registrar::DomainName;
+use std::time::Duration;
+
+#[tokio::test]
+async fn domain_actions_accept_wrapped_and_bare_domains() {
+  let mock = MockNameCom::start(0, Duration::ZERO);
+  let client = mock.client();
+  let name = DomainName::new("Example.ORG.").unwrap();
+  let domain = client.domains().domain(&name);
+
+  mock.wrap_actions(true);
+  let locked = domain.lock().await.unwrap();
+  assert_eq!(locked.domain_name, "example.org");
+  assert!(locked.locked);
+  assert!(!domain.disable_autorenew().await.unwrap().autorenew_enabled);
+
+  mock.wrap_actions(false);
+  let unlocked = domain.unlock().await.unwrap();
+  assert_eq!(unlocked.domain_name, "example.org");
+  assert!(!unlocked.locked);
+  assert!(domain.enable_autorenew().await.unwrap().autorenew_enabled);
+
+  assert_eq!(
+    mock.requests(),
+    [
+      "/core/v1/domains/example.org:lock",
+      "/core/v1/domains/example.org:disableAutorenew",
+      "/core/v1/domains/example.org:unlock",
+      "/core/v1/domains/example.org:enableAutorenew",
+    ]
+  );
+}
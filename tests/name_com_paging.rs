@@ -0,0 +1,71 @@
+mod mock_name_com;
+
+use futures::{StreamExt, TryStreamExt};
+use mock_name_com::MockNameCom;
+use registrar::DomainName;
+use registrar::name_com::types::PageCursor;
+use std::time::Duration;
+
+#[tokio::test]
+async fn streams_items_as_pages_arrive() {
+  let mock = MockNameCom::start(5, Duration::ZERO);
+  let client = mock.client();
+
+  let mut stream = Box::pin(client.domains().stream(PageCursor::first()));
+  assert_eq!(stream.next().await.unwrap().unwrap().domain_name, "domain0001.com");
+  // Only the first page has been fetched so far.
+  assert_eq!(mock.requests(), ["/core/v1/domains/?page=1"]);
+  let rest: Vec<_> = stream.try_collect().await.unwrap();
+  assert_eq!(rest.len(), 4);
+  assert_eq!(mock.requests().len(), 3);
+
+  let domains = client.domains().list().await.unwrap();
+  assert_eq!(domains.last().unwrap().domain_name, "domain0005.com");
+
+  let cursor = PageCursor::first().with_per_page(4);
+  let domain = DomainName::new("example.com").unwrap();
+  let records: Vec<_> = client.dns(&domain).stream_records(cursor).try_collect().await.unwrap();
+  assert_eq!(records.iter().map(|record| record.id).collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+  assert!(mock.requests().ends_with(&[
+    "/core/v1/domains/example.com/records?page=1&perPage=4".to_string(),
+    "/core/v1/domains/example.com/records?page=2&perPage=4".to_string(),
+  ]));
+}
+
+#[tokio::test]
+async fn resumes_from_a_cursor() {
+  let mock = MockNameCom::start(5, Duration::ZERO);
+  let client = mock.client();
+
+  let first = Box::pin(client.domains().pages(PageCursor::first())).next().await.unwrap().unwrap();
+  assert_eq!(first.items.len(), 2);
+  let cursor = first.next.unwrap();
+  assert_eq!(cursor, PageCursor { page: 2, per_page: None });
+
+  // A cursor survives serialization, so a listing can continue in another process.
+  let cursor: PageCursor = serde_json::from_str(&serde_json::to_string(&cursor).unwrap()).unwrap();
+  let rest: Vec<_> = client.domains().pages(cursor).try_collect().await.unwrap();
+  assert_eq!(rest.iter().map(|page| page.items.len()).collect::<Vec<_>>(), [2, 1]);
+  assert!(rest.last().unwrap().next.is_none());
+  assert_eq!(rest[1].items[0].domain_name, "domain0005.com");
+}
+
+#[tokio::test]
+async fn fetches_remaining_pages_concurrently_in_order() {
+  let mock = MockNameCom::start(9, Duration::from_millis(100));
+  let client = mock.client().with_page_concurrency(2);
+
+  let domains = client.domains().list().await.unwrap();
+  let names: Vec<_> = domains.iter().map(|domain| domain.domain_name.clone()).collect();
+  let expected: Vec<_> = (1..=9).map(|index| format!("domain{:04}.com", index)).collect();
+  assert_eq!(names, expected);
+  // The first page is fetched alone, then the four others two at a time.
+  assert_eq!(mock.requests()[0], "/core/v1/domains/?page=1");
+  assert_eq!(mock.requests().len(), 5);
+  assert_eq!(mock.max_in_flight(), 2);
+
+  let pages: Vec<_> = client.domains().pages(PageCursor::first().with_per_page(4)).try_collect().await.unwrap();
+  let next: Vec<_> = pages.iter().map(|page| page.next.map(|cursor| cursor.page)).collect();
+  assert_eq!(next, [Some(2), Some(3), None]);
+  assert_eq!(pages[2].items.len(), 1);
+}
@@ -0,0 +1,112 @@
+mod mock_name_com;
+
+use mock_name_com::MockNameCom;
+use registrar::Error;
+use registrar::name_com::vanity_ns::types::VanityNameserver;
+use registrar::nameserver::{NameserverHost, NameserverHosts};
+use registrar::porkbun::Porkbun;
+use registrar::porkbun::domain::types::GlueRecordIps;
+use std::net::IpAddr;
+use std::time::Duration;
+
+fn ip(text: &str) -> IpAddr {
+  text.parse().unwrap()
+}
+
+#[test]
+fn normalises_hostnames() {
+  let host = NameserverHost::new("NS1.Example.COM.", vec![ip("192.0.2.1"), ip("2001:db8::1")]).unwrap();
+  assert_eq!(host.hostname(), "ns1.example.com");
+  assert_eq!(host.ipv4().collect::<Vec<_>>(), [&ip("192.0.2.1")]);
+  assert_eq!(host.ipv6().collect::<Vec<_>>(), [&ip("2001:db8::1")]);
+
+  let parsed = NameserverHost::parse("ns1.example.com", &[" 192.0.2.1 ", "2001:db8::1"]).unwrap();
+  assert_eq!(parsed, host);
+}
+
+#[test]
+fn rejects_invalid_hosts() {
+  let ips = vec![ip("192.0.2.1")];
+  assert!(matches!(NameserverHost::new("", ips.clone()), Err(Error::InvalidInput(_))));
+  assert!(matches!(NameserverHost::new(".", ips.clone()), Err(Error::InvalidInput(_))));
+  assert!(matches!(NameserverHost::new("localhost", ips), Err(Error::InvalidInput(_))));
+  assert!(matches!(NameserverHost::new("ns1.example.com", Vec::new()), Err(Error::InvalidInput(_))));
+  assert!(matches!(NameserverHost::parse("ns1.example.com", &["192.0.2.300"]), Err(Error::InvalidInput(_))));
+}
+
+#[test]
+fn finds_the_subdomain_within_a_domain() {
+  let host = NameserverHost::new("ns1.dns.example.com", vec![ip("192.0.2.1")]).unwrap();
+  assert_eq!(host.subdomain_of("example.com").unwrap(), "ns1.dns");
+  assert_eq!(host.subdomain_of("Example.COM.").unwrap(), "ns1.dns");
+  assert_eq!(host.subdomain_of("dns.example.com").unwrap(), "ns1");
+  // Neither a different domain, a suffix that is not a label boundary, nor the domain itself.
+  assert!(host.subdomain_of("example.org").is_err());
+  assert!(host.subdomain_of("s.example.com").is_err());
+  assert!(host.subdomain_of("ns1.dns.example.com").is_err());
+}
+
+#[test]
+fn converts_provider_records() {
+  let glue: GlueRecordIps = serde_json::from_value(serde_json::json!({
+    "v4": ["192.0.2.1"],
+    "v6": ["2001:db8::1"]
+  }))
+  .unwrap();
+  let host = NameserverHost::try_from(("NS1.example.com".to_string(), glue)).unwrap();
+  assert_eq!(host.hostname(), "ns1.example.com");
+  assert_eq!(host.ips(), [ip("192.0.2.1"), ip("2001:db8::1")]);
+
+  let vanity = VanityNameserver {
+    domain_name: "example.com".to_string(),
+    hostname: "ns2.example.com".to_string(),
+    ips: vec!["198.51.100.2".to_string()],
+  };
+  let host = NameserverHost::try_from(vanity).unwrap();
+  assert_eq!(host.hostname(), "ns2.example.com");
+  assert_eq!(host.ips(), [ip("198.51.100.2")]);
+
+  let invalid = VanityNameserver {
+    domain_name: "example.com".to_string(),
+    hostname: "ns2.example.com".to_string(),
+    ips: vec!["not an address".to_string()],
+  };
+  assert!(NameserverHost::try_from(invalid).is_err());
+}
+
+#[tokio::test]
+async fn name_com_hosts_round_trip() {
+  let mock = MockNameCom::start(0, Duration::ZERO);
+  let client = mock.client();
+
+  let ns1 = NameserverHost::new("ns1.example.com", vec![ip("192.0.2.1")]).unwrap();
+  let ns2 = NameserverHost::new("ns2.example.com", vec![ip("192.0.2.2"), ip("2001:db8::2")]).unwrap();
+  client.create_host("Example.COM.", &ns1).await.unwrap();
+  client.create_host("example.com", &ns2).await.unwrap();
+  assert_eq!(mock.vanity_nameservers("example.com"), ["ns1.example.com", "ns2.example.com"]);
+
+  let moved = NameserverHost::new("ns1.example.com", vec![ip("192.0.2.10")]).unwrap();
+  client.update_host("example.com", &moved).await.unwrap();
+  let listed = client.list_hosts("example.com").await.unwrap();
+  assert_eq!(listed, [moved, ns2]);
+
+  client.delete_host("example.com", "NS1.Example.com.").await.unwrap();
+  assert_eq!(mock.vanity_nameservers("example.com"), ["ns2.example.com"]);
+}
+
+#[tokio::test]
+async fn refuses_hosts_outside_the_domain() {
+  let mock = MockNameCom::start(0, Duration::ZERO);
+  let client = mock.client();
+  let outside = NameserverHost::new("ns1.example.org", vec![ip("192.0.2.1")]).unwrap();
+
+  assert!(matches!(client.create_host("example.com", &outside).await, Err(Error::InvalidInput(_))));
+  assert!(matches!(client.delete_host("example.com", "ns1.example.org").await, Err(Error::InvalidInput(_))));
+  assert!(matches!(client.delete_host("example.com", "example.com").await, Err(Error::InvalidInput(_))));
+  assert!(mock.requests().is_empty());
+
+  // Porkbun rejects the same hosts before making a request.
+  let porkbun = Porkbun::new("apikey".to_string(), "secret".to_string());
+  assert!(matches!(porkbun.create_host("example.com", &outside).await, Err(Error::InvalidInput(_))));
+  assert!(matches!(porkbun.delete_host("example.com", "ns1.example.org").await, Err(Error::InvalidInput(_))));
+}
@@ -0,0 +1,53 @@
+mod mock_name_com;
+
+use futures::TryStreamExt;
+use mock_name_com::MockNameCom;
+use registrar::DomainName;
+use registrar::name_com::dns::DnsClient;
+use registrar::name_com::domain::{DomainClient, DomainsClient};
+use registrar::name_com::types::PageCursor;
+use registrar::name_com::url_forwarding::UrlForwardingClient;
+use registrar::name_com::vanity_ns::VanityNameserverClient;
+use registrar::porkbun::dns::Dns;
+use registrar::porkbun::domain::Domain;
+use registrar::porkbun::ssl::Ssl;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn assert_owned<T: Clone + Send + Sync + 'static>() {}
+
+#[test]
+fn owned_clients_are_static() {
+  assert_owned::<Dns<'static>>();
+  assert_owned::<Domain<'static>>();
+  assert_owned::<Ssl<'static>>();
+  assert_owned::<DomainsClient<'static>>();
+  assert_owned::<DomainClient<'static>>();
+  assert_owned::<DnsClient<'static>>();
+  assert_owned::<UrlForwardingClient<'static>>();
+  assert_owned::<VanityNameserverClient<'static>>();
+}
+
+#[tokio::test]
+async fn owned_clients_move_into_spawned_tasks() {
+  let mock = MockNameCom::start(3, Duration::ZERO);
+  let client = Arc::new(mock.client());
+
+  let tasks: Vec<_> = ["one.example", "two.example"]
+    .into_iter()
+    .map(|name| {
+      let dns = DnsClient::owned(client.clone(), DomainName::new(name).unwrap());
+      tokio::spawn(async move { dns.list_records().await })
+    })
+    .collect();
+  for (task, name) in tasks.into_iter().zip(["one.example", "two.example"]) {
+    let records = task.await.unwrap().unwrap();
+    assert_eq!(records.len(), 3);
+    assert!(records.iter().all(|record| record.domain_name == name));
+  }
+
+  // A stream from an owned client does not borrow it.
+  let stream = DomainsClient::owned(client).stream(PageCursor::first());
+  let domains: Vec<_> = tokio::spawn(stream.try_collect::<Vec<_>>()).await.unwrap().unwrap();
+  assert_eq!(domains.len(), 3);
+}
@@ -3,6 +3,7 @@ mod common;
 use std::str::FromStr;
 use std::{net::IpAddr, time::Duration};
 
+use registrar::DomainName;
 use registrar::porkbun::{dns::types::DnsRecordCreateOptions, domain::types::UrlForwardRecord};
 use uuid::Uuid;
 
@@ -19,7 +20,8 @@ async fn can_create_and_get_dns_record() {
   // --- SETUP ---
   let client = common::porkbun_client();
   let config = common::get_config().porkbun;
-  let dns_client = client.dns(&config.domain);
+  let domain = DomainName::new(&config.domain).unwrap();
+  let dns_client = client.dns(&domain);
   let unique_value = Uuid::new_v4().to_string();
   let record_name = Some("integration-test-dns");
 
@@ -58,7 +60,8 @@ async fn can_create_and_get_url_forward() {
   // --- SETUP ---
   let client = common::porkbun_client();
   let config = common::get_config().porkbun;
-  let domain_client = client.domain(&config.domain);
+  let domain = DomainName::new(&config.domain).unwrap();
+  let domain_client = client.domain(&domain);
   let unique_subdomain = format!("test-fwd-{}", Uuid::new_v4().to_string()[..8].to_string());
 
   let options = UrlForwardRecord {
@@ -99,7 +102,8 @@ async fn can_create_and_get_glue_record() {
   // --- SETUP ---
   let client = common::porkbun_client();
   let config = common::get_config().porkbun;
-  let domain_client = client.domain(&config.domain);
+  let domain = DomainName::new(&config.domain).unwrap();
+  let domain_client = client.domain(&domain);
   let subdomain = format!("ns-drop-{}", Uuid::new_v4().to_string()[..8].to_string());
   let full_hostname = format!("{}.{}", subdomain, config.domain);
   let ips = vec![
@@ -0,0 +1,67 @@
+use chrono::{TimeZone, Utc};
+use registrar::Provider;
+use registrar::portfolio::{DomainSummary, ProviderExtras};
+
+#[test]
+fn converts_porkbun_domain_info() {
+  let info: registrar::porkbun::domain::types::DomainInfo = serde_json::from_value(serde_json::json!({
+    "domain": "example.com",
+    "status": "ACTIVE",
+    "tld": "com",
+    "createDate": "2020-01-02 03:04:05",
+    "expireDate": "not a date",
+    "securityLock": "1",
+    "whoisPrivacy": "0",
+    "autoRenew": 1,
+    "notLocal": 0,
+    "labels": [{ "id": "1", "title": "work", "color": "#000000" }]
+  }))
+  .unwrap();
+
+  let summary = DomainSummary::from(info);
+  assert_eq!(summary.provider, Provider::Porkbun);
+  assert_eq!(summary.name, "example.com");
+  // Porkbun timestamps carry no offset and are read as UTC; unparseable ones are dropped.
+  assert_eq!(summary.created, Some(Utc.with_ymd_and_hms(2020, 1, 2, 3, 4, 5).unwrap()));
+  assert_eq!(summary.expires, None);
+  assert!(summary.auto_renew && summary.locked && !summary.privacy);
+  assert_eq!(summary.nameservers, None);
+  let ProviderExtras::Porkbun { status, tld, not_local, labels } = summary.extras else {
+    panic!("expected Porkbun extras");
+  };
+  assert_eq!((status.as_str(), tld.as_str(), not_local), ("ACTIVE", "com", false));
+  assert_eq!(labels, vec!["work"]);
+}
+
+#[test]
+fn converts_name_com_domain() {
+  let domain: registrar::name_com::domain::types::Domain = serde_json::from_value(serde_json::json!({
+    "domainName": "example.org",
+    "createDate": "2020-01-02T03:04:05Z",
+    "expireDate": "2030-01-02T05:04:05+02:00",
+    "autorenewEnabled": false,
+    "locked": true,
+    "privacyEnabled": true,
+    "contacts": { "registrant": {} },
+    "nameservers": ["ns1.name.com", "ns2.name.com"],
+    "renewalPrice": 12.99
+  }))
+  .unwrap();
+
+  let summary = DomainSummary::from(domain);
+  assert_eq!(summary.provider, Provider::NameCom);
+  assert_eq!(summary.name, "example.org");
+  assert_eq!(summary.created, Some(Utc.with_ymd_and_hms(2020, 1, 2, 3, 4, 5).unwrap()));
+  // RFC 3339 offsets are converted to UTC.
+  assert_eq!(summary.expires, Some(Utc.with_ymd_and_hms(2030, 1, 2, 3, 4, 5).unwrap()));
+  assert!(!summary.auto_renew && summary.locked && summary.privacy);
+  assert_eq!(summary.nameservers, Some(vec!["ns1.name.com".to_string(), "ns2.name.com".to_string()]));
+  assert!(matches!(summary.extras, ProviderExtras::NameCom { renewal_price: Some(price) } if price == 12.99));
+}
+
+#[test]
+fn parses_provider_names() {
+  assert_eq!("Porkbun".parse::<Provider>().unwrap(), Provider::Porkbun);
+  assert_eq!("name.com".parse::<Provider>().unwrap(), Provider::NameCom);
+  assert!(matches!("godaddy".parse::<Provider>(), Err(registrar::Error::InvalidInput(_))));
+}
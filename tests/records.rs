@@ -0,0 +1,38 @@
+use registrar::records::Record;
+
+#[test]
+fn accepts_well_formed_records() {
+  let records = [
+    Record::new("", "A", "192.0.2.1"),
+    Record::new("*.dev", "AAAA", "2001:db8::1"),
+    Record::new("www", "CNAME", "lb.example.net."),
+    Record::new("", "MX", "mail.example.com").with_priority(10),
+    Record::new("_sip._tcp", "SRV", "5 5060 sip.example.com").with_priority(10),
+    Record::new("", "CAA", "0 issue \"letsencrypt.org\""),
+    Record::new("_acme-challenge", "TXT", "token value").with_ttl(600),
+  ];
+  for record in records {
+    assert!(record.validate().is_ok(), "{:?}", record);
+  }
+}
+
+#[test]
+fn rejects_malformed_records() {
+  let records = [
+    Record::new("www", "A", "2001:db8::1"),
+    Record::new("www", "AAAA", "192.0.2.1"),
+    Record::new("", "CNAME", "lb.example.net"),
+    Record::new("www", "CNAME", "not a name"),
+    Record::new("bad..host", "A", "192.0.2.1"),
+    Record::new("-www", "A", "192.0.2.1"),
+    Record::new("www", "TXT", "  "),
+    Record::new("www", "A", "192.0.2.1").with_priority(10),
+    Record::new("www", "A", "192.0.2.1").with_ttl(0),
+    Record::new("_sip._tcp", "SRV", "5060 sip.example.com"),
+    Record::new("", "CAA", "issue letsencrypt.org"),
+    Record::new("www", "WKS", "anything"),
+  ];
+  for record in records {
+    assert!(matches!(record.validate(), Err(registrar::Error::InvalidInput(_))), "{:?}", record);
+  }
+}
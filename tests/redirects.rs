@@ -0,0 +1,42 @@
+mod mock_name_com;
+
+use mock_name_com::MockNameCom;
+use registrar::redirect::{Redirect, RedirectKind, UrlRedirects};
+use std::time::Duration;
+
+fn redirect(host: &str, target: &str) -> Redirect {
+  Redirect {
+    id: None,
+    host: host.to_string(),
+    target: target.to_string(),
+    kind: RedirectKind::Permanent,
+    include_path: false,
+    wildcard: false,
+    title: None,
+    meta: None,
+  }
+}
+
+#[tokio::test]
+async fn name_com_redirects_round_trip_through_full_hostnames() {
+  let mock = MockNameCom::start(0, Duration::ZERO);
+  let client = mock.client();
+
+  client.create_redirect("example.org", &redirect("www", "https://one.example")).await.unwrap();
+  client.create_redirect("example.org", &redirect("", "https://apex.example")).await.unwrap();
+  assert_eq!(mock.forwards("example.org"), ["example.org", "www.example.org"]);
+
+  let mut listed = client.list_redirects("example.org").await.unwrap();
+  listed.sort_by(|a, b| a.host.cmp(&b.host));
+  assert_eq!(listed.iter().map(|r| r.host.as_str()).collect::<Vec<_>>(), ["", "www"]);
+  assert_eq!(listed[1].id.as_deref(), Some("www.example.org"));
+
+  // Without an id, the update finds the forwarding by its full hostname.
+  client.update_redirect("example.org", &redirect("www", "https://two.example")).await.unwrap();
+  let listed = client.list_redirects("example.org").await.unwrap();
+  let www = listed.iter().find(|r| r.host == "www").unwrap();
+  assert_eq!(www.target, "https://two.example");
+
+  client.delete_redirect("example.org", www.id.as_deref().unwrap()).await.unwrap();
+  assert_eq!(mock.forwards("example.org"), ["example.org"]);
+}
@@ -0,0 +1,190 @@
+mod memory_records;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hickory_proto::op::{Message, MessageType, OpCode, Query, ResponseCode};
+use hickory_proto::rr::rdata::{A, TXT};
+use hickory_proto::rr::{DNSClass, Name, RData, Record, RecordType};
+use memory_records::MemoryRecords;
+use registrar::records;
+use registrar::rfc2136::{Rfc2136Config, Rfc2136Server, TsigAlgorithm, TsigKey};
+use std::sync::Arc;
+
+const SECRET: &[u8] = b"a shared secret of thirty-two b!";
+
+fn config() -> Rfc2136Config {
+  Rfc2136Config::from_yaml(&format!(
+    r#"
+min_ttl: 600
+keys:
+  - name: certbot
+    secret: "{}"
+    zones: [example.com]
+"#,
+    STANDARD.encode(SECRET)
+  ))
+  .unwrap()
+}
+
+fn key() -> TsigKey {
+  TsigKey::new("certbot", TsigAlgorithm::HmacSha256, SECRET).unwrap()
+}
+
+fn name(name: &str) -> Name {
+  Name::from_ascii(name).unwrap()
+}
+
+/// An UPDATE message for `zone` with the given prerequisite and update sections.
+fn update(zone: &str, prerequisites: Vec<Record>, updates: Vec<Record>) -> Vec<u8> {
+  let mut message = Message::new();
+  message
+    .set_id(4321)
+    .set_message_type(MessageType::Query)
+    .set_op_code(OpCode::Update)
+    .add_query(Query::query(name(zone), RecordType::SOA));
+  message.add_answers(prerequisites);
+  message.add_name_servers(updates);
+  message.to_vec().unwrap()
+}
+
+/// A record of class `class` without data, as used for deletions and prerequisites.
+fn empty(owner: &str, record_type: RecordType, class: DNSClass) -> Record {
+  let mut record = Record::with(name(owner), record_type, 0);
+  record.set_dns_class(class);
+  record
+}
+
+fn txt(owner: &str, ttl: u32, value: &str) -> Record {
+  Record::from_rdata(name(owner), ttl, RData::TXT(TXT::new(vec![value.to_string()])))
+}
+
+async fn send(server: &Rfc2136Server, request: &[u8]) -> Message {
+  Message::from_vec(&server.handle(request).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn adds_and_deletes_records() {
+  let records = Arc::new(MemoryRecords::default());
+  records.insert("example.com", records::Record::new("www", "A", "192.0.2.1"));
+  let server = Rfc2136Server::new(records.clone(), config()).unwrap();
+
+  let a = Record::from_rdata(name("www.example.com."), 60, RData::A(A::new(198, 51, 100, 7)));
+  let challenge = txt("_acme-challenge.example.com.", 60, "token-1");
+  let request = update("example.com.", vec![], vec![a, challenge.clone()]);
+  let response = send(&server, &key().sign(&request).unwrap()).await;
+  assert_eq!(response.response_code(), ResponseCode::NoError);
+  assert_eq!(response.id(), 4321);
+  // The response carries the server's TSIG record.
+  assert_eq!(response.additionals().last().unwrap().record_type(), RecordType::TSIG);
+
+  let mut www: Vec<String> = records.find("example.com", "www", "A").into_iter().map(|r| r.content).collect();
+  www.sort();
+  assert_eq!(www, ["192.0.2.1", "198.51.100.7"]);
+  let challenges = records.find("example.com", "_acme-challenge", "TXT");
+  assert_eq!(challenges.len(), 1);
+  assert_eq!((challenges[0].content.as_str(), challenges[0].ttl), ("token-1", Some(600)));
+
+  // Delete the A RRset (class ANY) and the single TXT record (class NONE).
+  let mut delete_txt = challenge;
+  delete_txt.set_dns_class(DNSClass::NONE).set_ttl(0);
+  let request = update(
+    "example.com.",
+    vec![],
+    vec![empty("www.example.com.", RecordType::A, DNSClass::ANY), delete_txt],
+  );
+  let response = send(&server, &key().sign(&request).unwrap()).await;
+  assert_eq!(response.response_code(), ResponseCode::NoError);
+  assert!(records.find("example.com", "www", "A").is_empty());
+  assert!(records.find("example.com", "_acme-challenge", "TXT").is_empty());
+}
+
+#[tokio::test]
+async fn checks_prerequisites_before_changing_anything() {
+  let records = Arc::new(MemoryRecords::default());
+  records.insert("example.com", records::Record::new("taken", "TXT", "in use"));
+  let server = Rfc2136Server::new(records.clone(), config()).unwrap();
+  let addition = txt("new.example.com.", 300, "value");
+
+  // "Name is not in use" fails for a name with records.
+  let request = update(
+    "example.com.",
+    vec![empty("taken.example.com.", RecordType::ANY, DNSClass::NONE)],
+    vec![addition.clone()],
+  );
+  let response = send(&server, &key().sign(&request).unwrap()).await;
+  assert_eq!(response.response_code(), ResponseCode::YXDomain);
+
+  // "RRset exists" fails for a missing RRset.
+  let request = update(
+    "example.com.",
+    vec![empty("taken.example.com.", RecordType::A, DNSClass::ANY)],
+    vec![addition.clone()],
+  );
+  let response = send(&server, &key().sign(&request).unwrap()).await;
+  assert_eq!(response.response_code(), ResponseCode::NXRRSet);
+  assert!(records.find("example.com", "new", "TXT").is_empty());
+
+  // "RRset exists with these values" passes when the values match.
+  let request = update("example.com.", vec![txt("taken.example.com.", 0, "in use")], vec![addition]);
+  let response = send(&server, &key().sign(&request).unwrap()).await;
+  assert_eq!(response.response_code(), ResponseCode::NoError);
+  assert_eq!(records.find("example.com", "new", "TXT").len(), 1);
+}
+
+#[tokio::test]
+async fn rejects_unauthenticated_and_out_of_zone_updates() {
+  let records = Arc::new(MemoryRecords::default());
+  let server = Rfc2136Server::new(records.clone(), config()).unwrap();
+  let request = update("example.com.", vec![], vec![txt("a.example.com.", 300, "value")]);
+
+  let response = send(&server, &request).await;
+  assert_eq!(response.response_code(), ResponseCode::Refused);
+
+  let wrong_secret = TsigKey::new("certbot", TsigAlgorithm::HmacSha256, b"not the secret").unwrap();
+  let response = send(&server, &wrong_secret.sign(&request).unwrap()).await;
+  assert_eq!(response.response_code(), ResponseCode::NotAuth);
+
+  let unknown_key = TsigKey::new("someone", TsigAlgorithm::HmacSha256, SECRET).unwrap();
+  let response = send(&server, &unknown_key.sign(&request).unwrap()).await;
+  assert_eq!(response.response_code(), ResponseCode::NotAuth);
+
+  let other_zone = update("example.org.", vec![], vec![txt("a.example.org.", 300, "value")]);
+  let response = send(&server, &key().sign(&other_zone).unwrap()).await;
+  assert_eq!(response.response_code(), ResponseCode::NotAuth);
+
+  let outside = update("example.com.", vec![], vec![txt("a.example.org.", 300, "value")]);
+  let response = send(&server, &key().sign(&outside).unwrap()).await;
+  assert_eq!(response.response_code(), ResponseCode::NotZone);
+
+  assert_eq!(records.calls(), 0);
+}
+
+#[tokio::test]
+async fn serves_udp_and_tcp() {
+  let records = Arc::new(MemoryRecords::default());
+  let server = Rfc2136Server::new(records.clone(), config()).unwrap();
+  let socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+  let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let (udp_addr, tcp_addr) = (socket.local_addr().unwrap(), listener.local_addr().unwrap());
+  tokio::spawn(server.clone().serve_udp(socket));
+  tokio::spawn(server.serve_tcp(listener));
+
+  let request = key().sign(&update("example.com.", vec![], vec![txt("udp.example.com.", 300, "v")])).unwrap();
+  let client = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+  client.send_to(&request, udp_addr).await.unwrap();
+  let mut buf = vec![0u8; 4096];
+  let len = client.recv(&mut buf).await.unwrap();
+  assert_eq!(Message::from_vec(&buf[..len]).unwrap().response_code(), ResponseCode::NoError);
+
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+  let request = key().sign(&update("example.com.", vec![], vec![txt("tcp.example.com.", 300, "v")])).unwrap();
+  let mut stream = tokio::net::TcpStream::connect(tcp_addr).await.unwrap();
+  stream.write_all(&(request.len() as u16).to_be_bytes()).await.unwrap();
+  stream.write_all(&request).await.unwrap();
+  let mut response = vec![0u8; stream.read_u16().await.unwrap() as usize];
+  stream.read_exact(&mut response).await.unwrap();
+  assert_eq!(Message::from_vec(&response).unwrap().response_code(), ResponseCode::NoError);
+
+  assert_eq!(records.find("example.com", "udp", "TXT").len(), 1);
+  assert_eq!(records.find("example.com", "tcp", "TXT").len(), 1);
+}
@@ -0,0 +1,115 @@
+mod memory_records;
+
+use memory_records::MemoryRecords;
+use registrar::Provider;
+use registrar::portfolio::{DomainPortfolio, DomainSummary, ProviderExtras};
+use registrar::records::Record;
+use registrar::router::DomainRouter;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A portfolio whose domain list can be changed between listings.
+struct Domains {
+  names: Mutex<Vec<&'static str>>,
+  listings: AtomicUsize,
+}
+
+impl Domains {
+  fn new(names: &[&'static str]) -> Arc<Self> {
+    Arc::new(Self { names: Mutex::new(names.to_vec()), listings: AtomicUsize::new(0) })
+  }
+}
+
+#[async_trait::async_trait]
+impl DomainPortfolio for Domains {
+  fn provider(&self) -> Provider {
+    Provider::NameCom
+  }
+
+  async fn list_domains(&self) -> registrar::Result<Vec<DomainSummary>> {
+    self.listings.fetch_add(1, Ordering::SeqCst);
+    let names = self.names.lock().unwrap();
+    Ok(names.iter().map(|name| summary(name)).collect())
+  }
+
+  async fn get_domain(&self, domain: &str) -> registrar::Result<DomainSummary> {
+    Ok(summary(domain))
+  }
+
+  async fn set_nameservers(&self, _: &str, _: &[&str]) -> registrar::Result<()> {
+    Ok(())
+  }
+
+  async fn set_auto_renew(&self, _: &str, _: bool) -> registrar::Result<()> {
+    Ok(())
+  }
+
+  async fn set_lock(&self, _: &str, _: bool) -> registrar::Result<()> {
+    Ok(())
+  }
+}
+
+fn summary(name: &str) -> DomainSummary {
+  DomainSummary {
+    provider: Provider::NameCom,
+    name: name.to_string(),
+    created: None,
+    expires: None,
+    auto_renew: false,
+    locked: false,
+    privacy: false,
+    nameservers: None,
+    extras: ProviderExtras::NameCom { renewal_price: None },
+  }
+}
+
+#[tokio::test]
+async fn routes_to_the_longest_registered_domain() {
+  let (personal, agency) = (Domains::new(&["example.com"]), Domains::new(&["Example.co.uk", "eu.example.co.uk"]));
+  let (personal_records, agency_records) = (Arc::new(MemoryRecords::default()), Arc::new(MemoryRecords::default()));
+  let router = DomainRouter::new()
+    .with_account("personal", personal.clone(), personal_records.clone())
+    .with_account("agency", agency.clone(), agency_records.clone());
+
+  let route = router.route("_acme-challenge.API.eu.example.co.uk.").await.unwrap();
+  assert_eq!((route.account.as_str(), route.zone.as_str()), ("agency", "eu.example.co.uk"));
+  assert_eq!(route.host, "_acme-challenge.api");
+  route.records.create_record(&Record::new(&route.host, "TXT", "token")).await.unwrap();
+  assert_eq!(agency_records.find("eu.example.co.uk", "_acme-challenge.api", "TXT").len(), 1);
+
+  let route = router.route("www.example.co.uk").await.unwrap();
+  assert_eq!((route.zone.as_str(), route.host.as_str()), ("example.co.uk", "www"));
+  let route = router.route("example.com").await.unwrap();
+  assert_eq!((route.account.as_str(), route.host.as_str()), ("personal", ""));
+  assert_eq!(route.records.zone(), "example.com");
+  let route = router.route("Bücher.example.com").await.unwrap();
+  assert_eq!(route.host, "xn--bcher-kva");
+
+  // Suffixes must match whole labels.
+  assert!(router.route("notexample.com").await.is_err());
+  // Routing uses the cached index.
+  assert_eq!(personal.listings.load(Ordering::SeqCst), 1);
+  assert_eq!(personal_records.calls(), 0);
+}
+
+#[tokio::test]
+async fn refreshes_the_index() {
+  let domains = Domains::new(&["example.com"]);
+  let router = DomainRouter::new()
+    .with_account("main", domains.clone(), Arc::new(MemoryRecords::default()))
+    .with_max_age(Duration::ZERO);
+  router.route("www.example.com").await.unwrap();
+
+  domains.names.lock().unwrap().push("example.org");
+  assert_eq!(router.route("www.example.org").await.unwrap().zone, "example.org");
+  assert!(domains.listings.load(Ordering::SeqCst) >= 2);
+
+  let router = DomainRouter::new().with_account("main", domains.clone(), Arc::new(MemoryRecords::default()));
+  router.route("example.com").await.unwrap();
+  domains.names.lock().unwrap().push("example.net");
+  // A miss right after a listing does not list again, but an explicit refresh does.
+  assert!(router.route("example.net").await.is_err());
+  router.refresh().await.unwrap();
+  assert_eq!(router.route("example.net").await.unwrap().host, "");
+}